@@ -0,0 +1,18 @@
+-- Your SQL goes here
+
+CREATE TABLE "totp" (
+  "profile_id" bigint PRIMARY KEY NOT NULL REFERENCES "profile" ON DELETE CASCADE,
+  "secret" varchar(64) NOT NULL,
+  "last_used_step" bigint,
+  "confirmed_at" timestamp with TIME ZONE,
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE "recovery_code" (
+  "id" BIGSERIAL PRIMARY KEY NOT NULL,
+  "profile_id" bigint NOT NULL REFERENCES "profile" ON DELETE CASCADE,
+  "code_hash" varchar(64) NOT NULL,
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_recovery_code_profile on "recovery_code"("profile_id");
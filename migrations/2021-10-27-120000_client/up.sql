@@ -0,0 +1,12 @@
+-- Your SQL goes here
+
+CREATE TABLE "client" (
+  "id" varchar(32) PRIMARY KEY NOT NULL,
+  "name" varchar NOT NULL,
+  "secret_hash" varchar(64) NOT NULL,
+  "grant_types" text[] NOT NULL,
+  "access_token_exp" bigint CHECK ("access_token_exp" > 0),
+  "refresh_token_exp" bigint CHECK ("refresh_token_exp" > 0),
+  "revoked_at" timestamp with TIME ZONE,
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+
+CREATE TABLE "personal_access_token" (
+  "id" varchar(32) PRIMARY KEY NOT NULL,
+  "profile_id" bigint NOT NULL REFERENCES "profile" ON DELETE CASCADE,
+  "name" varchar NOT NULL,
+  "token_hash" varchar(64) NOT NULL UNIQUE,
+  "scopes" text[] NOT NULL,
+  "expires_at" timestamp with TIME ZONE,
+  "last_used_at" timestamp with TIME ZONE,
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_personal_access_token_profile on "personal_access_token"("profile_id");
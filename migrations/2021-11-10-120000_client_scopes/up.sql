@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+-- Clients registered so far are first-party apps, they keep full access.
+ALTER TABLE "client" ADD COLUMN "scopes" text[] NOT NULL DEFAULT '{profile:read,profile:write,posts:write,admin:read,admin:write}';
+ALTER TABLE "client" ALTER COLUMN "scopes" DROP DEFAULT;
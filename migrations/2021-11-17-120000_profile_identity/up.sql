@@ -0,0 +1,12 @@
+-- Your SQL goes here
+
+CREATE TABLE "profile_identity" (
+  "id" BIGSERIAL PRIMARY KEY NOT NULL,
+  "profile_id" bigint NOT NULL REFERENCES "profile" ON DELETE CASCADE,
+  "provider" varchar(48) NOT NULL,
+  "subject" varchar(255) NOT NULL,
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW(),
+  UNIQUE ("provider", "subject")
+);
+
+CREATE INDEX idx_profile_identity_profile on "profile_identity"("profile_id");
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS  "impersonation_audit_log" cascade;
+DROP FUNCTION IF EXISTS reject_impersonation_audit_log_change;
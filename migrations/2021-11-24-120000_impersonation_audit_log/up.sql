@@ -0,0 +1,26 @@
+-- Your SQL goes here
+
+-- The trail outlives the admin and the impersonated account, so the ids have no foreign keys.
+CREATE TABLE "impersonation_audit_log" (
+  "id" BIGSERIAL PRIMARY KEY NOT NULL,
+  "actor_id" bigint NOT NULL,
+  "profile_id" bigint NOT NULL,
+  -- 'token_issued' when an admin is given a token, 'request' for every request made with it.
+  "event" varchar(16) NOT NULL,
+  "method" varchar(16),
+  "path" text,
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_impersonation_audit_log_actor on "impersonation_audit_log"("actor_id");
+CREATE INDEX idx_impersonation_audit_log_profile on "impersonation_audit_log"("profile_id");
+
+CREATE FUNCTION reject_impersonation_audit_log_change() RETURNS trigger AS $$
+BEGIN
+  RAISE EXCEPTION 'impersonation_audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER impersonation_audit_log_append_only
+  BEFORE UPDATE OR DELETE ON "impersonation_audit_log"
+  FOR EACH ROW EXECUTE PROCEDURE reject_impersonation_audit_log_change();
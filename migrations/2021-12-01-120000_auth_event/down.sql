@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS  "auth_event" cascade;
+DROP FUNCTION IF EXISTS reject_auth_event_change;
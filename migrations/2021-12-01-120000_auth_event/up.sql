@@ -0,0 +1,25 @@
+-- Your SQL goes here
+
+-- Events outlive the accounts they belong to, so `user_id` has no foreign key.
+CREATE TABLE "auth_event" (
+  "id" BIGSERIAL PRIMARY KEY NOT NULL,
+  "user_id" bigint,
+  "event_type" varchar(32) NOT NULL,
+  "outcome" varchar(16) NOT NULL,
+  "ip" varchar(64),
+  "user_agent" text,
+  "client_id" varchar(32),
+  "created_at" timestamp with TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_auth_event_user on "auth_event"("user_id", "id");
+
+CREATE FUNCTION reject_auth_event_change() RETURNS trigger AS $$
+BEGIN
+  RAISE EXCEPTION 'auth_event is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER auth_event_append_only
+  BEFORE UPDATE OR DELETE ON "auth_event"
+  FOR EACH ROW EXECUTE PROCEDURE reject_auth_event_change();
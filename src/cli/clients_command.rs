@@ -0,0 +1,117 @@
+use diesel::{r2d2::ConnectionManager, PgConnection};
+
+use crate::{
+    config::{client_config::ClientConfig, common_config::CommonConfig},
+    features::{
+        auth::domain::user_role::UserRole,
+        client::{
+            domain::create_client_model::CreateClientModel,
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
+        },
+    },
+};
+
+type Interactor = ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>;
+
+// The command line has direct database access anyway, so it acts as an administrator. This
+// is also how the first client gets registered, before anyone is able to log in.
+const CLI_ROLE: UserRole = UserRole::ADMIN;
+
+pub async fn run(args: &[&str]) -> Result<(), String> {
+    let interactor = get_interactor()?;
+
+    return match args {
+        ["list"] => list(&interactor).await,
+        ["create", name, grant_types, scopes] => {
+            create(&interactor, name, grant_types, scopes).await
+        }
+        ["revoke", id] => revoke(&interactor, id).await,
+        _ => Err(super::USAGE.to_string()),
+    };
+}
+
+fn get_interactor() -> Result<Interactor, String> {
+    let common_config = CommonConfig::new();
+    let manager = ConnectionManager::<PgConnection>::new(common_config.db_url);
+    let pool = r2d2::Pool::new(manager).map_err(|err| err.to_string())?;
+
+    return Ok(ClientInteractor::new(
+        ClientRepositoryImpl::new(pool),
+        RandomClientSecretGenerator::new(),
+        ClientConfig::new(),
+    ));
+}
+
+async fn list(interactor: &Interactor) -> Result<(), String> {
+    let clients = interactor
+        .get_clients(&CLI_ROLE)
+        .await
+        .map_err(|failure| failure.message)?;
+    for client in clients.iter() {
+        let grant_types = client
+            .grant_types
+            .iter()
+            .map(String::from)
+            .collect::<Vec<String>>()
+            .join(",");
+        let scopes = client
+            .scopes
+            .iter()
+            .map(String::from)
+            .collect::<Vec<String>>()
+            .join(",");
+        let status = match client.revoked_at {
+            Some(revoked_at) => format!("revoked at {}", revoked_at),
+            None => "active".to_string(),
+        };
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            client.id, client.name, grant_types, scopes, status
+        );
+    }
+
+    return Ok(());
+}
+
+async fn create(
+    interactor: &Interactor,
+    name: &str,
+    grant_types: &str,
+    scopes: &str,
+) -> Result<(), String> {
+    let model = CreateClientModel {
+        name: name.to_string(),
+        grant_types: split_list(grant_types),
+        scopes: split_list(scopes),
+        access_token_exp: None,
+        refresh_token_exp: None,
+    };
+    let credentials = interactor
+        .create_client(&CLI_ROLE, &model)
+        .await
+        .map_err(|failure| failure.message)?;
+    println!("Client id: {}", credentials.client.id);
+    println!("Client secret: {}", credentials.client_secret);
+    println!("The secret is not stored and can not be shown again.");
+
+    return Ok(());
+}
+
+async fn revoke(interactor: &Interactor, id: &str) -> Result<(), String> {
+    interactor
+        .revoke_client(&CLI_ROLE, &id.to_string())
+        .await
+        .map_err(|failure| failure.message)?;
+    println!("Revoked client '{}'", id);
+
+    return Ok(());
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    return list
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .collect();
+}
@@ -0,0 +1,120 @@
+use std::{env, fs, path::Path};
+
+use chrono::Utc;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+
+use crate::{
+    config::{
+        auth_config::AuthConfig,
+        common_config::CommonConfig,
+        jwt_key_set::{JwtKeyConfig, JwtKeySet},
+    },
+    features::{
+        auth::infrastructure::jwt_key::JwtKey,
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientRepository,
+        },
+    },
+};
+
+pub async fn run(args: &[&str]) -> Result<(), String> {
+    let path =
+        env::var("JWT_KEY_SET_PATH").map_err(|_| "JWT_KEY_SET_PATH is not set".to_string())?;
+
+    return match args {
+        ["list"] => list(&path),
+        ["add", id, algorithm, key_path] => add(&path, id, algorithm, key_path),
+        ["promote", id] => promote(&path, id),
+        ["prune"] => prune(&path).await,
+        _ => Err(super::USAGE.to_string()),
+    };
+}
+
+fn list(path: &String) -> Result<(), String> {
+    let key_set = JwtKeySet::load(path)?;
+    for key in key_set.keys.iter() {
+        let status = match key.retired_at {
+            _ if key.id == key_set.current => "current".to_string(),
+            Some(retired_at) => format!("retired at {}", retired_at),
+            None => "verification only".to_string(),
+        };
+        println!("{}\t{}\t{}", key.id, key.algorithm, status);
+    }
+
+    return Ok(());
+}
+
+// Key paths are stored as given, so relative ones have to be relative to the key set file.
+fn add(path: &String, id: &str, algorithm: &str, key_path: &str) -> Result<(), String> {
+    let mut key_set = JwtKeySet::load(path)?;
+    let directory = Path::new(path).parent().unwrap_or(Path::new(""));
+    let pem = fs::read(directory.join(key_path))
+        .map_err(|err| format!("Failed to read key '{}': {}", key_path, err))?;
+    // HS256 key files hold the shared secret instead of a pem.
+    if algorithm == "HS256" {
+        if String::from_utf8_lossy(&pem).trim().is_empty() {
+            return Err(format!("Key '{}' has an empty secret", key_path));
+        }
+    } else {
+        JwtKey::from_pem(&id.to_string(), &algorithm.to_string(), &pem)
+            .map_err(|failure| failure.message)?;
+    }
+
+    key_set.add(JwtKeyConfig {
+        id: id.to_string(),
+        algorithm: algorithm.to_string(),
+        private_key_path: Some(key_path.to_string()),
+        retired_at: None,
+    })?;
+    key_set.save(path)?;
+    println!("Added key '{}'", id);
+
+    return Ok(());
+}
+
+fn promote(path: &String, id: &str) -> Result<(), String> {
+    let mut key_set = JwtKeySet::load(path)?;
+    key_set.promote(&id.to_string(), Utc::now().timestamp())?;
+    key_set.save(path)?;
+    println!("Key '{}' is now used for signing", id);
+
+    return Ok(());
+}
+
+async fn prune(path: &String) -> Result<(), String> {
+    let mut key_set = JwtKeySet::load(path)?;
+    let max_token_lifetime = get_max_token_lifetime().await? as i64;
+    let pruned = key_set.prune(Utc::now().timestamp(), max_token_lifetime);
+    key_set.save(path)?;
+    for id in pruned.iter() {
+        println!("Removed key '{}'", id);
+    }
+
+    return Ok(());
+}
+
+// Clients can be given longer living tokens than the defaults, so a key has to be kept until
+// the longest living token of any of them expired.
+async fn get_max_token_lifetime() -> Result<u64, String> {
+    let config = AuthConfig::new();
+    let manager = ConnectionManager::<PgConnection>::new(CommonConfig::new().db_url);
+    let pool = r2d2::Pool::new(manager).map_err(|err| err.to_string())?;
+    let lifetimes = ClientRepositoryImpl::new(pool)
+        .get_max_token_lifetimes()
+        .await
+        .map_err(|failure| failure.message)?;
+
+    return Ok([
+        config.access_token_exp,
+        config.refresh_token_exp,
+        config.mfa_token_exp,
+        config.impersonation_token_exp,
+        lifetimes.access_token_exp.unwrap_or(0),
+        lifetimes.refresh_token_exp.unwrap_or(0),
+    ]
+    .iter()
+    .copied()
+    .max()
+    .unwrap_or(0));
+}
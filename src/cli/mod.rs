@@ -0,0 +1,26 @@
+pub mod clients_command;
+pub mod keys_command;
+
+const USAGE: &str = "Usage:
+    vvvvv-backend                                   start the server
+    vvvvv-backend keys list                         list signing keys
+    vvvvv-backend keys add <id> <algorithm> <path>  add a verification-only key
+    vvvvv-backend keys promote <id>                 sign new tokens with the key
+    vvvvv-backend keys prune                        remove keys whose tokens expired
+    vvvvv-backend clients list                      list registered clients
+    vvvvv-backend clients create <name> <grants> <scopes>
+                                                    register a client, lists are comma separated
+    vvvvv-backend clients revoke <id>               revoke a client and its tokens
+
+Key commands operate on the file pointed to by JWT_KEY_SET_PATH, prune also reads the token
+lifetimes of the registered clients. Running servers pick up the changes after a restart.";
+
+pub async fn run(args: Vec<String>) -> Result<(), String> {
+    let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>();
+
+    return match args.as_slice() {
+        ["keys", command @ ..] => keys_command::run(command).await,
+        ["clients", command @ ..] => clients_command::run(command).await,
+        _ => Err(USAGE.to_string()),
+    };
+}
@@ -8,6 +8,7 @@ pub enum FailureType {
     Forbidden,
     NotFound,
     Conflict,
+    TooManyRequests,
     Unknown,
 }
 
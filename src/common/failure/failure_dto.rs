@@ -16,7 +16,7 @@ pub struct FailureDto {
 
 impl FailureDto {
     pub fn from_failure(failure: Failure) -> FailureDto {
-        let args = failure.args.unwrap_or(HashMap::new());
+        let args = failure.args.unwrap_or_default();
         FailureDto {
             message: failure.message,
             code: failure.code,
@@ -39,6 +39,7 @@ impl ResponseError for Failure {
             FailureType::Forbidden => StatusCode::FORBIDDEN,
             FailureType::NotFound => StatusCode::NOT_FOUND,
             FailureType::Conflict => StatusCode::CONFLICT,
+            FailureType::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
             FailureType::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
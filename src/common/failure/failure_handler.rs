@@ -11,6 +11,7 @@ pub fn handle_failure(failure: Failure) -> HttpResponse {
         FailureType::Forbidden => StatusCode::FORBIDDEN,
         FailureType::NotFound => StatusCode::NOT_FOUND,
         FailureType::Conflict => StatusCode::CONFLICT,
+        FailureType::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
         FailureType::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
     };
     let dto = FailureDto::from_failure(failure);
@@ -34,4 +35,18 @@ mod test {
 
         assert_eq!(result.status(), StatusCode::CONFLICT)
     }
+
+    #[test]
+    fn should_map_too_many_requests_failure_to_response() {
+        let failure = Failure {
+            code: "test_code".to_string(),
+            error_type: FailureType::TooManyRequests,
+            message: "test_message".to_string(),
+            args: None,
+        };
+
+        let result = handle_failure(failure);
+
+        assert_eq!(result.status(), StatusCode::TOO_MANY_REQUESTS)
+    }
 }
@@ -2,16 +2,22 @@ use std::env;
 
 use crate::features::auth::interactors::auth_interactor::AuthConfigProvider;
 
+use super::jwt_key_set::{JwtKeyConfig, JwtKeySet};
+
 pub struct AuthConfig {
-    pub client_secret: String,
     pub private_key: String,
     pub access_token_exp: u64,
     pub refresh_token_exp: u64,
+    pub mfa_token_exp: u64,
+    pub jwt_issuer: String,
+    pub jwt_audience: String,
+    pub jwt_key_set: JwtKeySet,
+    pub require_verified_email: bool,
+    pub impersonation_token_exp: u64,
 }
 
 impl AuthConfig {
     pub fn new() -> AuthConfig {
-        let client_secret = env::var("CLIENT_SECRET").unwrap_or("test_client_secret".to_string());
         let private_key = env::var("PRIVATE_KEY").unwrap_or("test_private_key".to_string());
         let access_token_exp = env::var("ACCESS_TOKEN_EXP")
             .unwrap_or("".to_string())
@@ -21,17 +27,57 @@ impl AuthConfig {
             .unwrap_or("".to_string())
             .parse::<u64>()
             .unwrap_or(604_800);
+        let mfa_token_exp = env::var("MFA_TOKEN_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(300);
+        let jwt_issuer = env::var("JWT_ISSUER").unwrap_or("vvvvv".to_string());
+        let jwt_audience = env::var("JWT_AUDIENCE").unwrap_or("vvvvv".to_string());
+        let require_verified_email = env::var("REQUIRE_VERIFIED_EMAIL")
+            .unwrap_or("".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+        let impersonation_token_exp = env::var("IMPERSONATION_TOKEN_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(900);
+        let jwt_key_set = match env::var("JWT_KEY_SET_PATH") {
+            Ok(path) => JwtKeySet::load(&path)
+                .map(|key_set| key_set.resolve_paths(&path))
+                .unwrap_or_else(|err| panic!("{}", err)),
+            Err(_) => AuthConfig::get_single_key_set(),
+        };
         return AuthConfig {
-            client_secret,
             private_key,
             access_token_exp,
             refresh_token_exp,
+            mfa_token_exp,
+            jwt_issuer,
+            jwt_audience,
+            jwt_key_set,
+            require_verified_email,
+            impersonation_token_exp,
+        };
+    }
+
+    fn get_single_key_set() -> JwtKeySet {
+        let algorithm = env::var("JWT_ALGORITHM").unwrap_or("HS256".to_string());
+        let id = env::var("JWT_KEY_ID").unwrap_or("default".to_string());
+        let private_key_path = env::var("JWT_PRIVATE_KEY_PATH").ok();
+        return JwtKeySet {
+            current: id.clone(),
+            keys: vec![JwtKeyConfig {
+                id,
+                algorithm,
+                private_key_path,
+                retired_at: None,
+            }],
         };
     }
 }
 
 impl AuthConfigProvider for AuthConfig {
-    fn get_client_secret(&self) -> String {
-        return self.client_secret.clone();
+    fn is_verified_email_required(&self) -> bool {
+        return self.require_verified_email;
     }
 }
@@ -0,0 +1,36 @@
+use std::env;
+
+use crate::features::client::interactors::client_interactor::ClientConfigProvider;
+
+// Upper bounds for the token lifetimes an admin can give a client.
+pub struct ClientConfig {
+    pub max_access_token_exp: u64,
+    pub max_refresh_token_exp: u64,
+}
+
+impl ClientConfig {
+    pub fn new() -> ClientConfig {
+        let max_access_token_exp = env::var("MAX_CLIENT_ACCESS_TOKEN_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(86_400);
+        let max_refresh_token_exp = env::var("MAX_CLIENT_REFRESH_TOKEN_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(7_776_000);
+        return ClientConfig {
+            max_access_token_exp,
+            max_refresh_token_exp,
+        };
+    }
+}
+
+impl ClientConfigProvider for ClientConfig {
+    fn get_max_access_token_exp(&self) -> u64 {
+        return self.max_access_token_exp;
+    }
+
+    fn get_max_refresh_token_exp(&self) -> u64 {
+        return self.max_refresh_token_exp;
+    }
+}
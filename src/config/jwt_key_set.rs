@@ -0,0 +1,203 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct JwtKeyConfig {
+    pub id: String,
+    pub algorithm: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retired_at: Option<i64>,
+}
+
+// The `current` key signs new tokens, every other key in the set is only used to verify
+// tokens signed before it was replaced.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct JwtKeySet {
+    pub current: String,
+    pub keys: Vec<JwtKeyConfig>,
+}
+
+impl JwtKeySet {
+    pub fn load(path: &String) -> Result<JwtKeySet, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read key set '{}': {}", path, err))?;
+        let key_set: JwtKeySet = serde_json::from_str(&content)
+            .map_err(|err| format!("Invalid key set '{}': {}", path, err))?;
+        key_set.validate()?;
+
+        return Ok(key_set);
+    }
+
+    // Relative key paths are resolved against the directory of the key set file.
+    pub fn resolve_paths(mut self, path: &String) -> JwtKeySet {
+        let directory = Path::new(path).parent().unwrap_or(Path::new(""));
+        for key in self.keys.iter_mut() {
+            if let Some(private_key_path) = &key.private_key_path {
+                let resolved = directory.join(private_key_path);
+                key.private_key_path = Some(resolved.to_string_lossy().to_string());
+            }
+        }
+
+        return self;
+    }
+
+    pub fn save(&self, path: &String) -> Result<(), String> {
+        self.validate()?;
+        let content = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+
+        return fs::write(path, content + "\n")
+            .map_err(|err| format!("Failed to write key set '{}': {}", path, err));
+    }
+
+    pub fn add(&mut self, key: JwtKeyConfig) -> Result<(), String> {
+        if self.keys.iter().any(|existing| existing.id == key.id) {
+            return Err(format!("Key '{}' already exists", key.id));
+        }
+        self.keys.push(key);
+
+        return Ok(());
+    }
+
+    pub fn promote(&mut self, id: &String, now: i64) -> Result<(), String> {
+        if !self.keys.iter().any(|key| &key.id == id) {
+            return Err(format!("Key '{}' does not exist", id));
+        }
+        for key in self.keys.iter_mut() {
+            if &key.id == id {
+                key.retired_at = None;
+            } else if key.id == self.current {
+                key.retired_at = Some(now);
+            }
+        }
+        self.current = id.to_owned();
+
+        return Ok(());
+    }
+
+    // Drops keys that were retired long enough ago for every token they signed to expire.
+    pub fn prune(&mut self, now: i64, max_token_lifetime: i64) -> Vec<String> {
+        let (pruned, kept): (Vec<JwtKeyConfig>, Vec<JwtKeyConfig>) =
+            self.keys.drain(..).partition(|key| match key.retired_at {
+                Some(retired_at) => retired_at + max_token_lifetime < now,
+                None => false,
+            });
+        self.keys = kept;
+
+        return pruned.into_iter().map(|key| key.id).collect();
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !self.keys.iter().any(|key| key.id == self.current) {
+            return Err(format!(
+                "Current key '{}' is not in the key set",
+                self.current
+            ));
+        }
+        // Otherwise every HS256 key would be signing with the same `PRIVATE_KEY` secret.
+        let shared_secret_key = self
+            .keys
+            .iter()
+            .find(|key| key.algorithm == "HS256" && key.private_key_path.is_none());
+        if let (Some(key), true) = (shared_secret_key, self.keys.len() > 1) {
+            return Err(format!(
+                "Key '{}' needs its own secret file in a set of several keys",
+                key.id
+            ));
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_key(id: &str, retired_at: Option<i64>) -> JwtKeyConfig {
+        return JwtKeyConfig {
+            id: id.to_string(),
+            algorithm: "EdDSA".to_string(),
+            private_key_path: Some(format!("{}.pem", id)),
+            retired_at,
+        };
+    }
+
+    fn get_key_set() -> JwtKeySet {
+        return JwtKeySet {
+            current: "first".to_string(),
+            keys: vec![get_key("first", None), get_key("second", None)],
+        };
+    }
+
+    #[test]
+    fn should_retire_previous_key_on_promote() {
+        let mut key_set = get_key_set();
+
+        let result = key_set.promote(&"second".to_string(), 100);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(key_set.current, "second".to_string());
+        assert_eq!(
+            key_set.keys,
+            vec![get_key("first", Some(100)), get_key("second", None)]
+        );
+    }
+
+    #[test]
+    fn should_not_promote_unknown_key() {
+        let mut key_set = get_key_set();
+
+        let result = key_set.promote(&"third".to_string(), 100);
+
+        assert!(result.is_err());
+        assert_eq!(key_set, get_key_set());
+    }
+
+    #[test]
+    fn should_not_add_duplicated_key() {
+        let mut key_set = get_key_set();
+
+        let result = key_set.add(get_key("first", None));
+
+        assert!(result.is_err());
+        assert_eq!(key_set.keys.len(), 2);
+    }
+
+    #[test]
+    fn should_prune_only_keys_retired_before_token_lifetime() {
+        let mut key_set = JwtKeySet {
+            current: "third".to_string(),
+            keys: vec![
+                get_key("first", Some(100)),
+                get_key("second", Some(500)),
+                get_key("third", None),
+            ],
+        };
+
+        let result = key_set.prune(1000, 600);
+
+        assert_eq!(result, vec!["first".to_string()]);
+        assert_eq!(
+            key_set.keys,
+            vec![get_key("second", Some(500)), get_key("third", None)]
+        );
+    }
+
+    #[test]
+    fn should_require_own_secret_for_hs256_keys_in_set_of_several_keys() {
+        let mut key_set = get_key_set();
+        key_set.keys.push(JwtKeyConfig {
+            id: "shared".to_string(),
+            algorithm: "HS256".to_string(),
+            private_key_path: None,
+            retired_at: None,
+        });
+
+        let result = key_set.validate();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use std::env;
+
+pub struct LoginThrottleConfig {
+    pub free_attempts: u64,
+    pub max_attempts: u64,
+    pub max_ip_attempts: u64,
+    pub backoff_base: u64,
+    pub lockout_duration: u64,
+}
+
+impl LoginThrottleConfig {
+    pub fn new() -> LoginThrottleConfig {
+        let free_attempts = env::var("LOGIN_FREE_ATTEMPTS")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(3);
+        let max_attempts = env::var("LOGIN_MAX_ATTEMPTS")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(10);
+        let max_ip_attempts = env::var("LOGIN_MAX_IP_ATTEMPTS")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(100);
+        let backoff_base = env::var("LOGIN_BACKOFF_BASE")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(1);
+        let lockout_duration = env::var("LOGIN_LOCKOUT_DURATION")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(900);
+        return LoginThrottleConfig {
+            free_attempts,
+            max_attempts,
+            max_ip_attempts,
+            backoff_base,
+            lockout_duration,
+        };
+    }
+}
@@ -0,0 +1,54 @@
+use std::env;
+
+use url::Url;
+
+use crate::features::magic_link::interactors::magic_link_interactor::MagicLinkConfigProvider;
+
+pub struct MagicLinkConfig {
+    pub magic_link_exp: u64,
+    pub magic_link_url: String,
+    pub resend_delay: u64,
+    pub max_ip_sends: u64,
+    pub ip_sends_window: u64,
+}
+
+impl MagicLinkConfig {
+    pub fn new() -> MagicLinkConfig {
+        let magic_link_exp = env::var("MAGIC_LINK_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(900);
+        // Page of the frontend that posts the code from the link to `/auth/magic-link/consume`.
+        let magic_link_url =
+            env::var("MAGIC_LINK_URL").unwrap_or("http://localhost:3000/magic-link".to_string());
+        // A new link can be requested for the same email only after the delay.
+        let resend_delay = env::var("MAGIC_LINK_RESEND_DELAY")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(60);
+        let max_ip_sends = env::var("MAGIC_LINK_MAX_IP_SENDS")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(20);
+        let ip_sends_window = env::var("MAGIC_LINK_IP_SENDS_WINDOW")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(3600);
+        return MagicLinkConfig {
+            magic_link_exp,
+            magic_link_url,
+            resend_delay,
+            max_ip_sends,
+            ip_sends_window,
+        };
+    }
+}
+
+impl MagicLinkConfigProvider for MagicLinkConfig {
+    fn get_magic_link(&self, code: &String) -> String {
+        return match Url::parse_with_params(&self.magic_link_url, &[("code", code)]) {
+            Ok(url) => url.to_string(),
+            Err(_) => format!("{}?code={}", self.magic_link_url, code),
+        };
+    }
+}
@@ -0,0 +1,12 @@
+use std::env;
+
+pub struct MfaConfig {
+    pub totp_issuer: String,
+}
+
+impl MfaConfig {
+    pub fn new() -> MfaConfig {
+        let totp_issuer = env::var("TOTP_ISSUER").unwrap_or("vvvvv".to_string());
+        return MfaConfig { totp_issuer };
+    }
+}
@@ -1,3 +1,12 @@
 pub mod auth_config;
+pub mod client_config;
 pub mod common_config;
+pub mod jwt_key_set;
+pub mod login_throttle_config;
+pub mod magic_link_config;
+pub mod mfa_config;
+pub mod oidc_config;
+pub mod password_config;
+pub mod password_policy_config;
 pub mod profile_config;
+pub mod proxy_config;
@@ -0,0 +1,69 @@
+use std::{collections::HashMap, env};
+
+#[derive(Clone)]
+pub struct OidcProviderConfig {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    // Keys the id tokens are signed with, id tokens are rejected without it.
+    pub jwks_uri: Option<String>,
+    // Used when the token response has no id token, e.g. for plain OAuth2 providers.
+    pub userinfo_endpoint: Option<String>,
+    // Lists the user's addresses with their verification status, for providers whose userinfo
+    // has no `email_verified`, e.g. "https://api.github.com/user/emails".
+    pub emails_endpoint: Option<String>,
+    pub redirect_uri: String,
+    pub scopes: String,
+}
+
+pub struct OidcConfig {
+    pub providers: HashMap<String, OidcProviderConfig>,
+    pub authorization_request_exp: u64,
+}
+
+impl OidcConfig {
+    // Providers are listed in `OIDC_PROVIDERS`, e.g. "google,github", and each one is
+    // configured with `OIDC_<NAME>_*` variables. Providers with missing variables are skipped.
+    pub fn new() -> OidcConfig {
+        let providers = env::var("OIDC_PROVIDERS")
+            .unwrap_or("".to_string())
+            .split(',')
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .filter_map(|name| {
+                let provider = OidcProviderConfig::load(&name)?;
+                Some((name, provider))
+            })
+            .collect();
+        let authorization_request_exp = env::var("OIDC_AUTHORIZATION_REQUEST_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(600);
+        return OidcConfig {
+            providers,
+            authorization_request_exp,
+        };
+    }
+}
+
+impl OidcProviderConfig {
+    fn load(name: &String) -> Option<OidcProviderConfig> {
+        let prefix = format!("OIDC_{}_", name.to_uppercase());
+        let get = |key: &str| env::var(format!("{}{}", prefix, key)).ok();
+
+        return Some(OidcProviderConfig {
+            issuer: get("ISSUER")?,
+            client_id: get("CLIENT_ID")?,
+            client_secret: get("CLIENT_SECRET")?,
+            authorization_endpoint: get("AUTHORIZATION_ENDPOINT")?,
+            token_endpoint: get("TOKEN_ENDPOINT")?,
+            jwks_uri: get("JWKS_URI"),
+            userinfo_endpoint: get("USERINFO_ENDPOINT"),
+            emails_endpoint: get("EMAILS_ENDPOINT"),
+            redirect_uri: get("REDIRECT_URI")?,
+            scopes: get("SCOPES").unwrap_or("openid email profile".to_string()),
+        });
+    }
+}
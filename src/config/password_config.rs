@@ -0,0 +1,40 @@
+use std::env;
+
+// Argon2id parameters used for new hashes. Existing hashes created with different parameters
+// are upgraded on the next successful login.
+#[derive(Clone)]
+pub struct PasswordConfig {
+    pub memory_cost: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    // Size of the dedicated thread pool, so at most this many hashes are computed at once.
+    pub hashing_threads: usize,
+}
+
+impl PasswordConfig {
+    pub fn new() -> PasswordConfig {
+        let memory_cost = env::var("ARGON2_MEMORY_COST")
+            .unwrap_or("".to_string())
+            .parse::<u32>()
+            .unwrap_or(19456);
+        let iterations = env::var("ARGON2_ITERATIONS")
+            .unwrap_or("".to_string())
+            .parse::<u32>()
+            .unwrap_or(2);
+        let parallelism = env::var("ARGON2_PARALLELISM")
+            .unwrap_or("".to_string())
+            .parse::<u32>()
+            .unwrap_or(1);
+        let hashing_threads = env::var("PASSWORD_HASHING_THREADS")
+            .unwrap_or("".to_string())
+            .parse::<usize>()
+            .unwrap_or(4)
+            .max(1);
+        return PasswordConfig {
+            memory_cost,
+            iterations,
+            parallelism,
+            hashing_threads,
+        };
+    }
+}
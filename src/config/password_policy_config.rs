@@ -0,0 +1,55 @@
+use std::{env, fs};
+
+pub struct PasswordPolicyConfig {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_special_character: bool,
+    pub reject_common_passwords: bool,
+    // Extends the bundled list, one password per line.
+    pub common_passwords_list: Option<String>,
+}
+
+impl PasswordPolicyConfig {
+    pub fn new() -> PasswordPolicyConfig {
+        let min_length = env::var("PASSWORD_MIN_LENGTH")
+            .unwrap_or("".to_string())
+            .parse::<usize>()
+            .unwrap_or(8);
+        let require_lowercase = env::var("PASSWORD_REQUIRE_LOWERCASE")
+            .unwrap_or("".to_string())
+            .parse::<bool>()
+            .unwrap_or(true);
+        let require_uppercase = env::var("PASSWORD_REQUIRE_UPPERCASE")
+            .unwrap_or("".to_string())
+            .parse::<bool>()
+            .unwrap_or(true);
+        let require_digit = env::var("PASSWORD_REQUIRE_DIGIT")
+            .unwrap_or("".to_string())
+            .parse::<bool>()
+            .unwrap_or(true);
+        let require_special_character = env::var("PASSWORD_REQUIRE_SPECIAL_CHARACTER")
+            .unwrap_or("".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+        let reject_common_passwords = env::var("PASSWORD_REJECT_COMMON")
+            .unwrap_or("".to_string())
+            .parse::<bool>()
+            .unwrap_or(true);
+        let common_passwords_list = env::var("PASSWORD_COMMON_LIST_PATH").ok().map(|path| {
+            fs::read_to_string(&path).unwrap_or_else(|err| {
+                panic!("Failed to read common passwords list {}: {}", path, err)
+            })
+        });
+        return PasswordPolicyConfig {
+            min_length,
+            require_lowercase,
+            require_uppercase,
+            require_digit,
+            require_special_character,
+            reject_common_passwords,
+            common_passwords_list,
+        };
+    }
+}
@@ -2,6 +2,7 @@ use std::env;
 
 pub struct ProfileConfig {
     pub email_verification_key_exp: u64,
+    pub password_reset_key_exp: u64,
 }
 
 impl ProfileConfig {
@@ -10,8 +11,13 @@ impl ProfileConfig {
             .unwrap_or("".to_string())
             .parse::<u64>()
             .unwrap_or(10_800);
+        let password_reset_key_exp = env::var("PASSWORD_RESET_KEY_EXP")
+            .unwrap_or("".to_string())
+            .parse::<u64>()
+            .unwrap_or(3_600);
         return ProfileConfig {
             email_verification_key_exp,
+            password_reset_key_exp,
         };
     }
 }
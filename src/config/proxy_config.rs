@@ -0,0 +1,18 @@
+use std::{env, net::IpAddr};
+
+pub struct ProxyConfig {
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl ProxyConfig {
+    // Reverse proxies are listed in `TRUSTED_PROXIES`, e.g. "10.0.0.1,10.0.0.2". Forwarded
+    // headers are ignored unless the request comes from one of them.
+    pub fn new() -> ProxyConfig {
+        let trusted_proxies = env::var("TRUSTED_PROXIES")
+            .unwrap_or("".to_string())
+            .split(',')
+            .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+            .collect();
+        return ProxyConfig { trusted_proxies };
+    }
+}
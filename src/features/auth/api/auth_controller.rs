@@ -1,41 +1,176 @@
 use actix_web::{
+    get,
+    http::StatusCode,
     post,
     web::{self, ServiceConfig},
-    HttpResponse, Responder,
+    HttpRequest, HttpResponse, Responder,
 };
+use actix_web_httpauth::middleware::HttpAuthentication;
 
 use crate::{
     common::failure::failure_handler::handle_failure,
-    config::auth_config::AuthConfig,
-    features::auth::{
-        infrastructure::{
-            auth_data_repository_impl::AuthDataRepositoryImpl,
-            jwt_token_provider::JwtTokenProvider, password_manager_impl::PasswordManagerImpl,
+    config::{auth_config::AuthConfig, client_config::ClientConfig},
+    features::{
+        auth::{
+            domain::login_result::LoginResult,
+            infrastructure::{
+                auth_data_repository_impl::AuthDataRepositoryImpl,
+                jwt_token_provider::JwtTokenProvider, login_throttle_impl::LoginThrottleImpl,
+                password_manager_impl::PasswordManagerImpl,
+                session_storage_impl::SessionStorageImpl,
+            },
+            interactors::auth_interactor::AuthInteractor,
         },
-        interactors::auth_interactor::AuthInteractor,
+        auth_event::infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
+        },
+        mfa::{
+            infrastructure::totp_repository_impl::TotpRepositoryImpl,
+            interactors::mfa_interactor::MfaInteractor,
+            utils::{
+                recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+            },
+        },
+        personal_access_token::{
+            infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+            interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+            utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+        },
+        profile::infrastructure::profile_repository_impl::ProfileRepositoryImpl,
     },
 };
 
-use super::dtos::{
-    login_dto::LoginDto, refresh_token_dto::RefreshTokenDto, tokens_pair_dto::TokensPairDto,
+use super::{
+    auth_middleware::{inject_current_user_data, verify_current_user},
+    current_user::CurrentUser,
+    dtos::{
+        auth_status_dto::AuthStatusDto, jwks_dto::JwksDto, login_dto::LoginDto,
+        mfa_login_dto::MfaLoginDto, mfa_required_dto::MfaRequiredDto,
+        refresh_token_dto::RefreshTokenDto, token_introspection_dto::TokenIntrospectionDto,
+        token_request_dto::TokenRequestDto, tokens_pair_dto::TokensPairDto,
+    },
+    request_info::get_device_info,
+    scoped_user::{ProfileWrite, ScopedUser},
 };
 
-type Interactor =
-    AuthInteractor<PasswordManagerImpl, JwtTokenProvider, AuthDataRepositoryImpl, AuthConfig>;
+type Interactor = AuthInteractor<
+    PasswordManagerImpl,
+    JwtTokenProvider,
+    AuthDataRepositoryImpl,
+    ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+    SessionStorageImpl,
+    MfaInteractor<
+        TotpRepositoryImpl,
+        TotpGenerator,
+        RandomRecoveryCodeGenerator,
+        ProfileRepositoryImpl,
+    >,
+    PersonalAccessTokenInteractor<
+        PersonalAccessTokenRepositoryImpl,
+        RandomPersonalAccessTokenGenerator,
+    >,
+    LoginThrottleImpl,
+    AuthConfig,
+    AuthEventRepositoryImpl,
+>;
 
 pub fn configure_auth_controller(config: &mut ServiceConfig) {
-    config.service(web::scope("/auth").service(login).service(refresh));
+    let auth_middleware = HttpAuthentication::bearer(verify_current_user);
+    config.service(
+        web::scope("/auth")
+            .service(
+                web::scope("/logout-all")
+                    .wrap(auth_middleware)
+                    .service(logout_all),
+            )
+            .service(
+                web::scope("/status")
+                    .wrap(HttpAuthentication::with_fn(inject_current_user_data))
+                    .service(get_status),
+            )
+            .service(login)
+            .service(login_with_second_factor)
+            .service(refresh)
+            .service(logout)
+            .service(introspect)
+            .service(revoke),
+    );
+}
+
+pub fn configure_well_known_controller(config: &mut ServiceConfig) {
+    config.service(web::scope("/.well-known").service(get_jwks));
+}
+
+#[get("/jwks.json")]
+async fn get_jwks(interactor: web::Data<Interactor>) -> impl Responder {
+    let keys = interactor.get_public_keys();
+
+    HttpResponse::Ok()
+        .header("Cache-Control", "public, max-age=300")
+        .json(JwksDto::from(keys))
+}
+
+// Open to anonymous callers, who are reported as unauthenticated rather than rejected.
+#[get("")]
+async fn get_status(current_user: Option<CurrentUser>) -> impl Responder {
+    HttpResponse::Ok()
+        .header("Cache-Control", "no-store")
+        .json(AuthStatusDto::from(
+            current_user.map(|current_user| current_user.0),
+        ))
 }
 
 #[post("/login")]
-async fn login(interactor: web::Data<Interactor>, dto: web::Form<LoginDto>) -> impl Responder {
-    let result = interactor.validate_client_secret(&dto.client_secret).await;
+async fn login(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<LoginDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
 
-    if let Err(failure) = result {
-        return handle_failure(failure);
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .login(&client, &dto.login, &dto.password, &device_info)
+        .await;
+
+    match result {
+        Ok(LoginResult::Authenticated(tokens)) => {
+            HttpResponse::Ok().json(TokensPairDto::from(tokens))
+        }
+        Ok(LoginResult::MfaRequired(mfa_token)) => {
+            HttpResponse::Ok().json(MfaRequiredDto { mfa_token })
+        }
+        Err(err) => handle_failure(err),
     }
+}
 
-    let result = interactor.login(&dto.login, &dto.password).await;
+#[post("/login/mfa")]
+async fn login_with_second_factor(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<MfaLoginDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .login_with_second_factor(&client, &dto.mfa_token, &dto.code, &device_info)
+        .await;
 
     match result {
         Ok(tokens) => HttpResponse::Ok().json(TokensPairDto::from(tokens)),
@@ -47,17 +182,103 @@ async fn login(interactor: web::Data<Interactor>, dto: web::Form<LoginDto>) -> i
 async fn refresh(
     interactor: web::Data<Interactor>,
     dto: web::Form<RefreshTokenDto>,
+    request: HttpRequest,
 ) -> impl Responder {
-    let result = interactor.validate_client_secret(&dto.client_secret).await;
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let device_info = get_device_info(&request);
+    let result = interactor.refresh(&client, &dto.token, &device_info).await;
 
-    if let Err(failure) = result {
+    match result {
+        Ok(tokens) => HttpResponse::Ok().json(TokensPairDto::from(tokens)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/logout")]
+async fn logout(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<RefreshTokenDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let device_info = get_device_info(&request);
+    let result = interactor.logout(&client, &dto.token, &device_info).await;
+
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/introspect")]
+async fn introspect(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<TokenRequestDto>,
+) -> impl Responder {
+    if let Err(failure) = interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
         return handle_failure(failure);
     }
 
-    let result = interactor.refresh(&dto.token).await;
+    let result = interactor.introspect(&dto.token).await;
 
     match result {
-        Ok(tokens) => HttpResponse::Ok().json(TokensPairDto::from(tokens)),
+        Ok(introspection) => HttpResponse::Ok()
+            .header("Cache-Control", "no-store")
+            .json(TokenIntrospectionDto::from(introspection)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/revoke")]
+async fn revoke(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<TokenRequestDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let device_info = get_device_info(&request);
+    let result = interactor.revoke(&client, &dto.token, &device_info).await;
+
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("")]
+async fn logout_all(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+    request: HttpRequest,
+) -> impl Responder {
+    let device_info = get_device_info(&request);
+    let result = interactor.logout_all(&current_user, &device_info).await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
         Err(err) => handle_failure(err),
     }
 }
@@ -1,43 +1,100 @@
+use std::future::Future;
+
 use actix_web::{dev::ServiceRequest, web::Data, Error, HttpMessage};
-use actix_web_httpauth::extractors::bearer::BearerAuth;
+use actix_web_httpauth::{extractors::bearer::BearerAuth, middleware::HttpAuthentication};
+use futures::future::{FutureExt, LocalBoxFuture};
 
 use crate::{
-    config::auth_config::AuthConfig,
-    features::auth::{
-        errors::auth_errors::get_unauthenticated_error,
-        infrastructure::{
-            auth_data_repository_impl::AuthDataRepositoryImpl,
-            jwt_token_provider::JwtTokenProvider, password_manager_impl::PasswordManagerImpl,
+    config::{auth_config::AuthConfig, client_config::ClientConfig},
+    features::{
+        auth::{
+            domain::{current_user_data::CurrentUserData, user_role::UserRole},
+            errors::auth_errors::{get_insufficient_role_error, get_unauthenticated_error},
+            infrastructure::{
+                auth_data_repository_impl::AuthDataRepositoryImpl,
+                jwt_token_provider::JwtTokenProvider, login_throttle_impl::LoginThrottleImpl,
+                password_manager_impl::PasswordManagerImpl,
+                session_storage_impl::SessionStorageImpl,
+            },
+            interactors::auth_interactor::AuthInteractor,
+        },
+        auth_event::infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
+        },
+        impersonation::{
+            domain::impersonated_request::ImpersonatedRequest,
+            infrastructure::impersonation_audit_log_impl::ImpersonationAuditLogImpl,
+            interactors::impersonation_interactor::ImpersonationInteractor,
+        },
+        mfa::{
+            infrastructure::totp_repository_impl::TotpRepositoryImpl,
+            interactors::mfa_interactor::MfaInteractor,
+            utils::{
+                recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+            },
         },
-        interactors::auth_interactor::AuthInteractor,
+        personal_access_token::{
+            infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+            interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+            utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+        },
+        profile::infrastructure::profile_repository_impl::ProfileRepositoryImpl,
     },
 };
 
-type Auth =
-    AuthInteractor<PasswordManagerImpl, JwtTokenProvider, AuthDataRepositoryImpl, AuthConfig>;
-
-// pub async fn inject_current_user_data(
-//     req: ServiceRequest,
-//     credentails: BearerAuth,
-// ) -> Result<ServiceRequest, Error> {
-//     let interactor = req.app_data::<Auth>();
-
-//     match interactor {
-//         None => Ok(req),
-//         Some(interacotor) => {
-//             let result = interacotor
-//                 .validate_access_token(&credentails.token().to_string())
-//                 .await;
-//             match result {
-//                 Err(_) => Ok(req),
-//                 Ok(data) => {
-//                     req.extensions_mut().insert(data);
-//                     Ok(req)
-//                 }
-//             }
-//         }
-//     }
-// }
+use super::optional_bearer_auth::OptionalBearerAuth;
+
+type Auth = AuthInteractor<
+    PasswordManagerImpl,
+    JwtTokenProvider,
+    AuthDataRepositoryImpl,
+    ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+    SessionStorageImpl,
+    MfaInteractor<
+        TotpRepositoryImpl,
+        TotpGenerator,
+        RandomRecoveryCodeGenerator,
+        ProfileRepositoryImpl,
+    >,
+    PersonalAccessTokenInteractor<
+        PersonalAccessTokenRepositoryImpl,
+        RandomPersonalAccessTokenGenerator,
+    >,
+    LoginThrottleImpl,
+    AuthConfig,
+    AuthEventRepositoryImpl,
+>;
+
+type Impersonation =
+    ImpersonationInteractor<ProfileRepositoryImpl, JwtTokenProvider, ImpersonationAuditLogImpl>;
+
+// Attaches the current user when the request carries a bearer token and lets anonymous
+// requests through, e.g. `.wrap(HttpAuthentication::with_fn(inject_current_user_data))`.
+// Invalid, expired or revoked tokens are still rejected instead of being treated as anonymous.
+pub async fn inject_current_user_data(
+    req: ServiceRequest,
+    credentials: OptionalBearerAuth,
+) -> Result<ServiceRequest, Error> {
+    return authenticate_optionally(req, credentials, verify_current_user).await;
+}
+
+async fn authenticate_optionally<F, Fut>(
+    req: ServiceRequest,
+    credentials: OptionalBearerAuth,
+    verify: F,
+) -> Result<ServiceRequest, Error>
+where
+    F: FnOnce(ServiceRequest, BearerAuth) -> Fut,
+    Fut: Future<Output = Result<ServiceRequest, Error>>,
+{
+    return match credentials.0 {
+        None => Ok(req),
+        Some(credentials) => verify(req, credentials).await,
+    };
+}
 
 pub async fn verify_current_user(
     req: ServiceRequest,
@@ -53,6 +110,9 @@ pub async fn verify_current_user(
             match result {
                 Err(_) => Err(Error::from(get_unauthenticated_error())),
                 Ok(data) => {
+                    if let Some(actor_id) = data.actor_id {
+                        record_impersonated_request(&req, actor_id, data.id).await?;
+                    }
                     req.extensions_mut().insert(data);
                     Ok(req)
                 }
@@ -60,3 +120,183 @@ pub async fn verify_current_user(
         }
     }
 }
+
+// Every request made with an impersonation token ends up in the audit log. Requests that
+// can not be recorded are rejected rather than served unaudited.
+async fn record_impersonated_request(
+    req: &ServiceRequest,
+    actor_id: i64,
+    user_id: i64,
+) -> Result<(), Error> {
+    let interactor = match req.app_data::<Data<Impersonation>>() {
+        Some(interactor) => interactor,
+        None => return Err(Error::from(get_unauthenticated_error())),
+    };
+    let request = ImpersonatedRequest {
+        actor_id,
+        user_id,
+        method: req.method().to_string(),
+        path: req.path().to_string(),
+    };
+
+    return interactor
+        .record_request(&request)
+        .await
+        .map_err(Error::from);
+}
+
+// Authenticates the request like `verify_current_user` and additionally requires the user
+// to have at least the given role, e.g. `.wrap(require_role(UserRole::MODERATOR))`.
+pub fn require_role(
+    role: UserRole,
+) -> HttpAuthentication<
+    BearerAuth,
+    impl Fn(ServiceRequest, BearerAuth) -> LocalBoxFuture<'static, Result<ServiceRequest, Error>>,
+> {
+    return HttpAuthentication::bearer(move |req, credentials| {
+        let role = role.clone();
+        async move {
+            let req = verify_current_user(req, credentials).await?;
+            check_role(&req, &role)?;
+            Ok(req)
+        }
+        .boxed_local()
+    });
+}
+
+fn check_role(req: &ServiceRequest, role: &UserRole) -> Result<(), Error> {
+    let ext = req.extensions();
+    return match ext.get::<CurrentUserData>() {
+        None => Err(Error::from(get_unauthenticated_error())),
+        Some(current_user) if current_user.role.includes(role) => Ok(()),
+        Some(_) => Err(Error::from(get_insufficient_role_error())),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::{
+        http::{header::AUTHORIZATION, StatusCode},
+        test::TestRequest,
+    };
+    use actix_web_httpauth::extractors::AuthExtractor;
+
+    use super::*;
+
+    fn get_request(role: Option<UserRole>) -> ServiceRequest {
+        let req = TestRequest::default().to_srv_request();
+        if let Some(role) = role {
+            req.extensions_mut().insert(CurrentUserData {
+                id: 1,
+                role,
+                session_id: None,
+                actor_id: None,
+                client_id: Some("client_id".to_string()),
+                scopes: vec![],
+            });
+        }
+
+        return req;
+    }
+
+    async fn verify_test_token(
+        req: ServiceRequest,
+        credentials: BearerAuth,
+    ) -> Result<ServiceRequest, Error> {
+        if credentials.token() != "valid_token" {
+            return Err(Error::from(get_unauthenticated_error()));
+        }
+        req.extensions_mut().insert(CurrentUserData {
+            id: 1,
+            role: UserRole::USER,
+            session_id: None,
+            actor_id: None,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![],
+        });
+
+        return Ok(req);
+    }
+
+    async fn authenticate_test_request(
+        authorization: Option<&str>,
+    ) -> Result<ServiceRequest, Error> {
+        let mut req = TestRequest::default();
+        if let Some(authorization) = authorization {
+            req = req.header(AUTHORIZATION, authorization);
+        }
+        let req = req.to_srv_request();
+        let credentials = OptionalBearerAuth::from_service_request(&req).await?;
+
+        return authenticate_optionally(req, credentials, verify_test_token).await;
+    }
+
+    #[actix_rt::test]
+    async fn should_let_request_without_token_through_anonymously() {
+        let result = authenticate_test_request(None).await;
+
+        let req = result.ok().unwrap();
+        assert!(req.extensions().get::<CurrentUserData>().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn should_attach_user_of_valid_token() {
+        let result = authenticate_test_request(Some("Bearer valid_token")).await;
+
+        let req = result.ok().unwrap();
+        let ext = req.extensions();
+        assert_eq!(ext.get::<CurrentUserData>().map(|user| user.id), Some(1));
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_invalid_token_instead_of_ignoring_it() {
+        let result = authenticate_test_request(Some("Bearer revoked_token")).await;
+
+        let status = result.err().unwrap().as_response_error().status_code();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_optional_request_without_auth_interactor() {
+        let req = TestRequest::default()
+            .header(AUTHORIZATION, "Bearer token")
+            .to_srv_request();
+        let credentials = OptionalBearerAuth::from_service_request(&req)
+            .await
+            .ok()
+            .unwrap();
+
+        let result = inject_current_user_data(req, credentials).await;
+
+        let status = result.err().unwrap().as_response_error().status_code();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn should_allow_user_with_sufficient_role() {
+        let admin = check_role(&get_request(Some(UserRole::ADMIN)), &UserRole::MODERATOR);
+        let moderator = check_role(
+            &get_request(Some(UserRole::MODERATOR)),
+            &UserRole::MODERATOR,
+        );
+
+        assert!(admin.is_ok());
+        assert!(moderator.is_ok());
+    }
+
+    #[test]
+    fn should_reject_user_with_insufficient_role() {
+        let result = check_role(&get_request(Some(UserRole::USER)), &UserRole::MODERATOR);
+
+        let status = result.err().unwrap().as_response_error().status_code();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn should_reject_unauthenticated_request() {
+        let result = check_role(&get_request(None), &UserRole::USER);
+
+        let status = result.err().unwrap().as_response_error().status_code();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,81 @@
+use std::ops::Deref;
+
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::auth::{
+        domain::current_user_data::CurrentUserData, errors::auth_errors::get_unauthenticated_error,
+    },
+};
+
+// The user attached by the authentication middleware. Handlers behind optional
+// authentication can take `Option<CurrentUser>` instead.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CurrentUser(pub CurrentUserData);
+
+impl Deref for CurrentUser {
+    type Target = CurrentUserData;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.0;
+    }
+}
+
+impl FromRequest for CurrentUser {
+    type Error = Failure;
+    type Future = Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let current_user = req.extensions().get::<CurrentUserData>().cloned();
+
+        return ready(
+            current_user
+                .map(CurrentUser)
+                .ok_or(get_unauthenticated_error()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::{http::StatusCode, test::TestRequest, ResponseError};
+
+    use crate::features::auth::domain::user_role::UserRole;
+
+    use super::*;
+
+    fn get_current_user_data() -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role: UserRole::USER,
+            session_id: None,
+            actor_id: None,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![],
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_extract_current_user() {
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+        req.extensions_mut().insert(get_current_user_data());
+
+        let result = CurrentUser::from_request(&req, &mut payload).await;
+
+        assert_eq!(result, Ok(CurrentUser(get_current_user_data())));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_unauthenticated_error_without_user() {
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+
+        let result = CurrentUser::from_request(&req, &mut payload).await;
+
+        let failure = result.err().unwrap();
+        assert_eq!(failure, get_unauthenticated_error());
+        assert_eq!(failure.status_code(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,74 @@
+use serde::Serialize;
+
+use crate::features::auth::domain::{current_user_data::CurrentUserData, scope::Scope};
+
+// Anonymous callers are reported with `authenticated` only.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct AuthStatusDto {
+    pub authenticated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl From<Option<CurrentUserData>> for AuthStatusDto {
+    fn from(current_user: Option<CurrentUserData>) -> Self {
+        return match current_user {
+            None => AuthStatusDto {
+                authenticated: false,
+                sub: None,
+                role: None,
+                scope: None,
+            },
+            Some(current_user) => AuthStatusDto {
+                authenticated: true,
+                sub: Some(current_user.id.to_string()),
+                role: Some(String::from(&current_user.role)),
+                scope: Some(Scope::join(&current_user.scopes)).filter(|scope| !scope.is_empty()),
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::features::auth::domain::user_role::UserRole;
+
+    use super::*;
+
+    #[test]
+    fn should_only_report_anonymous_caller_as_unauthenticated() {
+        let dto = AuthStatusDto::from(None);
+        let json = serde_json::to_value(&dto).unwrap();
+
+        assert_eq!(json, json!({ "authenticated": false }));
+    }
+
+    #[test]
+    fn should_map_current_user_to_dto() {
+        let dto = AuthStatusDto::from(Some(CurrentUserData {
+            id: 1,
+            role: UserRole::MODERATOR,
+            session_id: None,
+            actor_id: None,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![Scope::ProfileRead],
+        }));
+        let json = serde_json::to_value(&dto).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "authenticated": true,
+                "sub": "1",
+                "role": "MODERATOR",
+                "scope": "profile:read",
+            })
+        );
+    }
+}
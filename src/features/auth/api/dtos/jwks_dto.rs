@@ -0,0 +1,101 @@
+use serde::Serialize;
+
+use crate::features::auth::domain::public_key::{PublicKey, PublicKeyParams};
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct JwkDto {
+    pub kty: String,
+    pub kid: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct JwksDto {
+    pub keys: Vec<JwkDto>,
+}
+
+impl From<PublicKey> for JwkDto {
+    fn from(key: PublicKey) -> Self {
+        let (kty, n, e, crv, x) = match key.params {
+            PublicKeyParams::Rsa { modulus, exponent } => {
+                ("RSA", Some(modulus), Some(exponent), None, None)
+            }
+            PublicKeyParams::Ed25519 { x } => {
+                ("OKP", None, None, Some("Ed25519".to_string()), Some(x))
+            }
+        };
+
+        return JwkDto {
+            kty: kty.to_string(),
+            kid: key.id,
+            alg: key.algorithm,
+            key_use: "sig".to_string(),
+            n,
+            e,
+            crv,
+            x,
+        };
+    }
+}
+
+impl From<Vec<PublicKey>> for JwksDto {
+    fn from(keys: Vec<PublicKey>) -> Self {
+        return JwksDto {
+            keys: keys.into_iter().map(JwkDto::from).collect(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_map_rsa_key_to_jwk() {
+        let key = PublicKey {
+            id: "key_id".to_string(),
+            algorithm: "RS256".to_string(),
+            params: PublicKeyParams::Rsa {
+                modulus: "modulus".to_string(),
+                exponent: "AQAB".to_string(),
+            },
+        };
+
+        let result = JwkDto::from(key);
+
+        assert_eq!(result.kty, "RSA".to_string());
+        assert_eq!(result.kid, "key_id".to_string());
+        assert_eq!(result.alg, "RS256".to_string());
+        assert_eq!(result.n, Some("modulus".to_string()));
+        assert_eq!(result.e, Some("AQAB".to_string()));
+        assert_eq!(result.crv, None);
+    }
+
+    #[test]
+    fn should_map_ed25519_key_to_jwk() {
+        let key = PublicKey {
+            id: "key_id".to_string(),
+            algorithm: "EdDSA".to_string(),
+            params: PublicKeyParams::Ed25519 {
+                x: "public".to_string(),
+            },
+        };
+
+        let result = JwkDto::from(key);
+
+        assert_eq!(result.kty, "OKP".to_string());
+        assert_eq!(result.crv, Some("Ed25519".to_string()));
+        assert_eq!(result.x, Some("public".to_string()));
+        assert_eq!(result.n, None);
+    }
+}
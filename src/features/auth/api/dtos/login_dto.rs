@@ -5,5 +5,6 @@ use serde::Deserialize;
 pub struct LoginDto {
     pub login: String,
     pub password: String,
+    pub client_id: String,
     pub client_secret: String,
 }
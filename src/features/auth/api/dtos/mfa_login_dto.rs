@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaLoginDto {
+    pub mfa_token: String,
+    pub code: String,
+    pub client_id: String,
+    pub client_secret: String,
+}
@@ -0,0 +1,6 @@
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct MfaRequiredDto {
+    pub mfa_token: String,
+}
@@ -1,3 +1,9 @@
+pub mod auth_status_dto;
+pub mod jwks_dto;
 pub mod login_dto;
+pub mod mfa_login_dto;
+pub mod mfa_required_dto;
 pub mod refresh_token_dto;
+pub mod token_introspection_dto;
+pub mod token_request_dto;
 pub mod tokens_pair_dto;
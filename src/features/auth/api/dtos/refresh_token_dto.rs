@@ -4,5 +4,6 @@ use serde::Deserialize;
 #[serde(rename_all = "camelCase")]
 pub struct RefreshTokenDto {
     pub token: String,
+    pub client_id: String,
     pub client_secret: String,
 }
@@ -0,0 +1,114 @@
+use serde::Serialize;
+
+use crate::features::auth::domain::{scope::Scope, token_introspection::TokenIntrospection};
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct TokenActorDto {
+    pub sub: String,
+}
+
+// Inactive tokens are reported with `active` only, as required by RFC 7662.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct TokenIntrospectionDto {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub act: Option<TokenActorDto>,
+}
+
+impl From<Option<TokenIntrospection>> for TokenIntrospectionDto {
+    fn from(introspection: Option<TokenIntrospection>) -> Self {
+        let introspection = match introspection {
+            Some(introspection) => introspection,
+            None => {
+                return TokenIntrospectionDto {
+                    active: false,
+                    sub: None,
+                    role: None,
+                    token_type: None,
+                    client_id: None,
+                    scope: None,
+                    iat: None,
+                    exp: None,
+                    act: None,
+                }
+            }
+        };
+
+        return TokenIntrospectionDto {
+            active: true,
+            sub: Some(introspection.user_id.to_string()),
+            role: Some(String::from(&introspection.role)),
+            token_type: Some(String::from(&introspection.token_type)),
+            client_id: introspection.client_id,
+            scope: Some(Scope::join(&introspection.scopes)).filter(|scope| !scope.is_empty()),
+            iat: introspection.issued_at,
+            exp: introspection.expires_at,
+            act: introspection.actor_id.map(|actor_id| TokenActorDto {
+                sub: actor_id.to_string(),
+            }),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::features::auth::domain::{
+        introspected_token_type::IntrospectedTokenType, user_role::UserRole,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_only_report_inactive_token_as_inactive() {
+        let dto = TokenIntrospectionDto::from(None);
+        let json = serde_json::to_value(&dto).unwrap();
+
+        assert_eq!(json, json!({ "active": false }));
+    }
+
+    #[test]
+    fn should_map_active_token_to_dto() {
+        let dto = TokenIntrospectionDto::from(Some(TokenIntrospection {
+            user_id: 2,
+            role: UserRole::USER,
+            token_type: IntrospectedTokenType::Access,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![Scope::ProfileRead, Scope::PostsWrite],
+            actor_id: Some(1),
+            issued_at: Some(100),
+            expires_at: Some(160),
+        }));
+        let json = serde_json::to_value(&dto).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "active": true,
+                "sub": "2",
+                "role": "USER",
+                "token_type": "access_token",
+                "client_id": "client_id",
+                "scope": "profile:read posts:write",
+                "iat": 100,
+                "exp": 160,
+                "act": { "sub": "1" },
+            })
+        );
+    }
+}
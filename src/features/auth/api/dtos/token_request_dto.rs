@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+// Also accepts the client credential names of RFC 7662 and RFC 7009, so standard clients can
+// call the endpoints. `token_type_hint` is ignored, all token types are checked anyway.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRequestDto {
+    pub token: String,
+    #[serde(alias = "client_id")]
+    pub client_id: String,
+    #[serde(alias = "client_secret")]
+    pub client_secret: String,
+}
@@ -27,7 +27,7 @@ mod test {
 
     #[test]
     fn should_map_model_to_dto() {
-        let token = TokensPairDto {
+        let token = TokensPair {
             access_token: "access".to_string(),
             refresh_token: "refresh".to_string(),
             access_token_exp: 1,
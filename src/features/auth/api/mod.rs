@@ -1,3 +1,7 @@
 pub mod auth_controller;
 pub mod auth_middleware;
+pub mod current_user;
 pub mod dtos;
+pub mod optional_bearer_auth;
+pub mod request_info;
+pub mod scoped_user;
@@ -0,0 +1,66 @@
+use actix_web::{dev::ServiceRequest, http::header::AUTHORIZATION, Error};
+use actix_web_httpauth::extractors::{bearer::BearerAuth, AuthExtractor};
+use futures::future::{ok, FutureExt, LocalBoxFuture};
+
+// Bearer credentials for endpoints that are also open to anonymous callers. A missing
+// `Authorization` header is not an error, but a malformed one still is.
+pub struct OptionalBearerAuth(pub Option<BearerAuth>);
+
+impl AuthExtractor for OptionalBearerAuth {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_service_request(req: &ServiceRequest) -> Self::Future {
+        if !req.headers().contains_key(AUTHORIZATION) {
+            return ok(OptionalBearerAuth(None)).boxed_local();
+        }
+
+        return BearerAuth::from_service_request(req)
+            .map(|result| {
+                result
+                    .map(|credentials| OptionalBearerAuth(Some(credentials)))
+                    .map_err(Error::from)
+            })
+            .boxed_local();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::{http::StatusCode, test::TestRequest};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_extract_none_without_header() {
+        let req = TestRequest::default().to_srv_request();
+
+        let result = OptionalBearerAuth::from_service_request(&req).await;
+
+        assert!(matches!(result, Ok(OptionalBearerAuth(None))));
+    }
+
+    #[actix_rt::test]
+    async fn should_extract_bearer_token() {
+        let req = TestRequest::default()
+            .header(AUTHORIZATION, "Bearer token")
+            .to_srv_request();
+
+        let result = OptionalBearerAuth::from_service_request(&req).await;
+
+        let credentials = result.ok().unwrap().0.unwrap();
+        assert_eq!(credentials.token(), "token");
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_malformed_header() {
+        let req = TestRequest::default()
+            .header(AUTHORIZATION, "Basic dXNlcjpwYXNz")
+            .to_srv_request();
+
+        let result = OptionalBearerAuth::from_service_request(&req).await;
+
+        let status = result.err().unwrap().as_response_error().status_code();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,112 @@
+use std::net::{IpAddr, SocketAddr};
+
+use actix_web::{http::header::USER_AGENT, web::Data, HttpRequest};
+
+use crate::{config::proxy_config::ProxyConfig, features::auth::domain::device_info::DeviceInfo};
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+pub fn get_device_info(request: &HttpRequest) -> DeviceInfo {
+    let user_agent = request
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let trusted_proxies = match request.app_data::<Data<ProxyConfig>>() {
+        Some(config) => config.trusted_proxies.clone(),
+        None => vec![],
+    };
+    let ip = get_client_ip(request, &trusted_proxies).map(|ip| ip.to_string());
+
+    return DeviceInfo { user_agent, ip };
+}
+
+// Every proxy appends the address it got the request from, so the forwarded addresses are
+// walked back from the end while the hop is trusted. Anything before the first untrusted hop
+// can be made up by the client.
+fn get_client_ip(request: &HttpRequest, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let mut ip = request.peer_addr()?.ip();
+    let forwarded = request
+        .headers()
+        .get_all(X_FORWARDED_FOR)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect::<Vec<&str>>();
+
+    for address in forwarded.iter().rev() {
+        if !trusted_proxies.contains(&ip) {
+            break;
+        }
+        match parse_ip(address.trim()) {
+            Some(forwarded_ip) => ip = forwarded_ip,
+            None => break,
+        }
+    }
+
+    return Some(ip);
+}
+
+// Some proxies forward the client port as well, which is noise for an IP.
+fn parse_ip(address: &str) -> Option<IpAddr> {
+    if let Ok(address) = address.parse::<SocketAddr>() {
+        return Some(address.ip());
+    }
+    return address.parse::<IpAddr>().ok();
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn get_proxy_config() -> ProxyConfig {
+        return ProxyConfig {
+            trusted_proxies: vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()],
+        };
+    }
+
+    #[test]
+    fn should_read_user_agent_and_peer_ip() {
+        let request = TestRequest::default()
+            .peer_addr("127.0.0.1:54321".parse().unwrap())
+            .header(USER_AGENT, "test-agent")
+            .to_http_request();
+
+        let result = get_device_info(&request);
+
+        assert_eq!(
+            result,
+            DeviceInfo {
+                user_agent: Some("test-agent".to_string()),
+                ip: Some("127.0.0.1".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn should_ignore_forwarded_ip_from_untrusted_peer() {
+        let request = TestRequest::default()
+            .peer_addr("127.0.0.1:54321".parse().unwrap())
+            .header(X_FORWARDED_FOR, "1.1.1.1")
+            .data(get_proxy_config())
+            .to_http_request();
+
+        let result = get_device_info(&request);
+
+        assert_eq!(result.ip, Some("127.0.0.1".to_string()));
+    }
+
+    #[test]
+    fn should_read_forwarded_ip_behind_trusted_proxies() {
+        let request = TestRequest::default()
+            .peer_addr("10.0.0.1:54321".parse().unwrap())
+            .header(X_FORWARDED_FOR, "6.6.6.6, 1.1.1.1:1234, 10.0.0.2")
+            .data(get_proxy_config())
+            .to_http_request();
+
+        let result = get_device_info(&request);
+
+        assert_eq!(result.ip, Some("1.1.1.1".to_string()));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{marker::PhantomData, ops::Deref};
+
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::auth::{
+        domain::{current_user_data::CurrentUserData, scope::Scope},
+        errors::auth_errors::{get_insufficient_scope_error, get_unauthenticated_error},
+    },
+};
+
+pub trait RequiredScope {
+    const SCOPE: Scope;
+}
+
+pub struct ProfileRead;
+
+impl RequiredScope for ProfileRead {
+    const SCOPE: Scope = Scope::ProfileRead;
+}
+
+pub struct ProfileWrite;
+
+impl RequiredScope for ProfileWrite {
+    const SCOPE: Scope = Scope::ProfileWrite;
+}
+
+pub struct AdminRead;
+
+impl RequiredScope for AdminRead {
+    const SCOPE: Scope = Scope::AdminRead;
+}
+
+pub struct AdminWrite;
+
+impl RequiredScope for AdminWrite {
+    const SCOPE: Scope = Scope::AdminWrite;
+}
+
+// The current user, provided the token was granted the scope, so handlers declare what
+// they need in their signature, e.g. `current_user: ScopedUser<ProfileWrite>`.
+pub struct ScopedUser<S> {
+    user: CurrentUserData,
+    scope: PhantomData<S>,
+}
+
+impl<S> Deref for ScopedUser<S> {
+    type Target = CurrentUserData;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.user;
+    }
+}
+
+impl<S> FromRequest for ScopedUser<S>
+where
+    S: RequiredScope,
+{
+    type Error = Failure;
+    type Future = Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let current_user = req.extensions().get::<CurrentUserData>().cloned();
+
+        return ready(match current_user {
+            None => Err(get_unauthenticated_error()),
+            Some(user) if user.scopes.contains(&S::SCOPE) => Ok(ScopedUser {
+                user,
+                scope: PhantomData,
+            }),
+            Some(_) => Err(get_insufficient_scope_error(String::from(&S::SCOPE))),
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use actix_web::{http::StatusCode, test::TestRequest, ResponseError};
+
+    use crate::features::auth::domain::user_role::UserRole;
+
+    use super::*;
+
+    fn get_current_user_data(scopes: Vec<Scope>) -> CurrentUserData {
+        return get_user_data(UserRole::USER, scopes);
+    }
+
+    fn get_user_data(role: UserRole, scopes: Vec<Scope>) -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role,
+            session_id: None,
+            actor_id: None,
+            client_id: Some("client_id".to_string()),
+            scopes,
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_extract_user_with_scope() {
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+        req.extensions_mut()
+            .insert(get_current_user_data(vec![Scope::ProfileRead]));
+
+        let result = ScopedUser::<ProfileRead>::from_request(&req, &mut payload).await;
+
+        assert_eq!(result.unwrap().id, 1);
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_user_without_scope() {
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+        req.extensions_mut()
+            .insert(get_current_user_data(vec![Scope::ProfileRead]));
+
+        let result = ScopedUser::<ProfileWrite>::from_request(&req, &mut payload).await;
+
+        let failure = result.err().unwrap();
+        assert_eq!(
+            failure,
+            get_insufficient_scope_error("profile:write".to_string())
+        );
+        assert_eq!(failure.status_code(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_admin_token_without_admin_scope() {
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+        req.extensions_mut().insert(get_user_data(
+            UserRole::ADMIN,
+            vec![Scope::ProfileRead, Scope::AdminRead],
+        ));
+
+        let read = ScopedUser::<AdminRead>::from_request(&req, &mut payload).await;
+        let write = ScopedUser::<AdminWrite>::from_request(&req, &mut payload).await;
+
+        assert!(read.is_ok());
+        let failure = write.err().unwrap();
+        assert_eq!(
+            failure,
+            get_insufficient_scope_error("admin:write".to_string())
+        );
+        assert_eq!(failure.status_code(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_rt::test]
+    async fn should_return_unauthenticated_error_without_user() {
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+
+        let result = ScopedUser::<ProfileRead>::from_request(&req, &mut payload).await;
+
+        assert_eq!(result.err(), Some(get_unauthenticated_error()));
+    }
+}
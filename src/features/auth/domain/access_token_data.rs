@@ -0,0 +1,14 @@
+use super::{scope::Scope, user_role::UserRole};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AccessTokenData {
+    pub user_id: i64,
+    pub role: UserRole,
+    pub session_id: Option<String>,
+    // The admin acting as the user, set only on impersonation tokens.
+    pub actor_id: Option<i64>,
+    pub client_id: String,
+    pub scopes: Vec<Scope>,
+    pub issued_at: i64,
+    pub expires_at: i64,
+}
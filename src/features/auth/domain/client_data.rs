@@ -0,0 +1,11 @@
+use super::{grant_type::GrantType, scope::Scope};
+
+// Token lifetimes of the client, `None` falls back to the configured defaults.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClientData {
+    pub id: String,
+    pub grant_types: Vec<GrantType>,
+    pub scopes: Vec<Scope>,
+    pub access_token_exp: Option<u64>,
+    pub refresh_token_exp: Option<u64>,
+}
@@ -1,7 +1,13 @@
-use super::user_role::UserRole;
+use super::{scope::Scope, user_role::UserRole};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct CurrentUserData {
     pub id: i64,
     pub role: UserRole,
+    pub session_id: Option<String>,
+    // The admin acting as the user, set only on impersonation tokens.
+    pub actor_id: Option<i64>,
+    // Personal access tokens are not issued through a client.
+    pub client_id: Option<String>,
+    pub scopes: Vec<Scope>,
 }
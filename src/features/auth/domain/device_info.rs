@@ -0,0 +1,5 @@
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DeviceInfo {
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
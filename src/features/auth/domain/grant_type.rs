@@ -0,0 +1,49 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum GrantType {
+    Password,
+    RefreshToken,
+}
+
+impl GrantType {
+    pub fn parse(str: &str) -> Option<GrantType> {
+        return match str {
+            "password" => Some(GrantType::Password),
+            "refresh_token" => Some(GrantType::RefreshToken),
+            _ => None,
+        };
+    }
+}
+
+impl From<&GrantType> for String {
+    fn from(grant_type: &GrantType) -> Self {
+        return match grant_type {
+            GrantType::Password => "password".to_string(),
+            GrantType::RefreshToken => "refresh_token".to_string(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_grant_type() {
+        let password = GrantType::parse("password");
+        let refresh_token = GrantType::parse("refresh_token");
+        let unknown = GrantType::parse("implicit");
+
+        assert_eq!(password, Some(GrantType::Password));
+        assert_eq!(refresh_token, Some(GrantType::RefreshToken));
+        assert_eq!(unknown, None);
+    }
+
+    #[test]
+    fn should_get_string_from_grant_type() {
+        let password: String = String::from(&GrantType::Password);
+        let refresh_token: String = String::from(&GrantType::RefreshToken);
+
+        assert_eq!(password, "password");
+        assert_eq!(refresh_token, "refresh_token");
+    }
+}
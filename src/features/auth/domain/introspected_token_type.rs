@@ -0,0 +1,15 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum IntrospectedTokenType {
+    Access,
+    Refresh,
+}
+
+// Named after the `token_type_hint` values of RFC 7009.
+impl From<&IntrospectedTokenType> for String {
+    fn from(token_type: &IntrospectedTokenType) -> Self {
+        return match token_type {
+            IntrospectedTokenType::Access => "access_token".to_string(),
+            IntrospectedTokenType::Refresh => "refresh_token".to_string(),
+        };
+    }
+}
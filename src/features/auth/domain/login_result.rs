@@ -0,0 +1,7 @@
+use super::tokens_pair::TokensPair;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LoginResult {
+    Authenticated(TokensPair),
+    MfaRequired(String),
+}
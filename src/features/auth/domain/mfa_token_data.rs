@@ -0,0 +1,9 @@
+use super::user_role::UserRole;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MfaTokenData {
+    pub user_id: i64,
+    pub role: UserRole,
+    pub client_id: String,
+    pub token_id: String,
+}
@@ -1,4 +1,16 @@
+pub mod access_token_data;
 pub mod auth_data;
+pub mod client_data;
 pub mod current_user_data;
+pub mod device_info;
+pub mod grant_type;
+pub mod introspected_token_type;
+pub mod login_result;
+pub mod mfa_token_data;
+pub mod public_key;
+pub mod refresh_token_data;
+pub mod scope;
+pub mod session;
+pub mod token_introspection;
 pub mod tokens_pair;
 pub mod user_role;
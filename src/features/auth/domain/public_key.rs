@@ -0,0 +1,12 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum PublicKeyParams {
+    Rsa { modulus: String, exponent: String },
+    Ed25519 { x: String },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PublicKey {
+    pub id: String,
+    pub algorithm: String,
+    pub params: PublicKeyParams,
+}
@@ -0,0 +1,12 @@
+use super::user_role::UserRole;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RefreshTokenData {
+    pub user_id: i64,
+    pub role: UserRole,
+    pub session_id: String,
+    pub client_id: String,
+    pub token_id: String,
+    pub issued_at: i64,
+    pub expires_at: i64,
+}
@@ -0,0 +1,76 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum Scope {
+    ProfileRead,
+    ProfileWrite,
+    PostsWrite,
+    // Required on top of the admin role, so a narrowed admin token can not manage the service.
+    AdminRead,
+    AdminWrite,
+}
+
+impl Scope {
+    pub fn parse(str: &str) -> Option<Scope> {
+        return match str {
+            "profile:read" => Some(Scope::ProfileRead),
+            "profile:write" => Some(Scope::ProfileWrite),
+            "posts:write" => Some(Scope::PostsWrite),
+            "admin:read" => Some(Scope::AdminRead),
+            "admin:write" => Some(Scope::AdminWrite),
+            _ => None,
+        };
+    }
+
+    // Scopes are space separated in tokens, as in the OAuth2 `scope` parameter.
+    pub fn parse_list(str: &str) -> Vec<Scope> {
+        return str.split_whitespace().filter_map(Scope::parse).collect();
+    }
+
+    pub fn join(scopes: &[Scope]) -> String {
+        return scopes
+            .iter()
+            .map(String::from)
+            .collect::<Vec<String>>()
+            .join(" ");
+    }
+}
+
+impl From<&Scope> for String {
+    fn from(scope: &Scope) -> Self {
+        return match scope {
+            Scope::ProfileRead => "profile:read".to_string(),
+            Scope::ProfileWrite => "profile:write".to_string(),
+            Scope::PostsWrite => "posts:write".to_string(),
+            Scope::AdminRead => "admin:read".to_string(),
+            Scope::AdminWrite => "admin:write".to_string(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_scope() {
+        let profile_read = Scope::parse("profile:read");
+        let posts_write = Scope::parse("posts:write");
+        let admin_write = Scope::parse("admin:write");
+        let unknown = Scope::parse("posts:delete");
+
+        assert_eq!(profile_read, Some(Scope::ProfileRead));
+        assert_eq!(posts_write, Some(Scope::PostsWrite));
+        assert_eq!(admin_write, Some(Scope::AdminWrite));
+        assert_eq!(unknown, None);
+    }
+
+    #[test]
+    fn should_join_and_parse_scope_list() {
+        let scopes = vec![Scope::ProfileRead, Scope::PostsWrite];
+
+        let joined = Scope::join(&scopes);
+        let parsed = Scope::parse_list("profile:read  unknown posts:write");
+
+        assert_eq!(joined, "profile:read posts:write");
+        assert_eq!(parsed, scopes);
+    }
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Session {
+    pub id: String,
+    pub user_id: i64,
+    pub refresh_token_id: String,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub refreshed_at: Option<DateTime<Utc>>,
+}
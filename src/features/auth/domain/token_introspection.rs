@@ -0,0 +1,17 @@
+use super::{introspected_token_type::IntrospectedTokenType, scope::Scope, user_role::UserRole};
+
+// Details of an active token, as reported by RFC 7662 token introspection.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TokenIntrospection {
+    pub user_id: i64,
+    pub role: UserRole,
+    pub token_type: IntrospectedTokenType,
+    // Personal access tokens are not issued through a client.
+    pub client_id: Option<String>,
+    // Refresh tokens do not carry scopes.
+    pub scopes: Vec<Scope>,
+    pub actor_id: Option<i64>,
+    pub issued_at: Option<i64>,
+    // Personal access tokens can be created without an expiration.
+    pub expires_at: Option<i64>,
+}
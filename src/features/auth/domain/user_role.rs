@@ -1,3 +1,5 @@
+// Spelled like the role names stored in the database.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum UserRole {
     USER,
@@ -5,6 +7,31 @@ pub enum UserRole {
     ADMIN,
 }
 
+impl UserRole {
+    // Unlike `from`, does not fall back to `USER` for unknown roles.
+    pub fn parse(str: &str) -> Option<UserRole> {
+        return match str {
+            "USER" => Some(UserRole::USER),
+            "MODERATOR" => Some(UserRole::MODERATOR),
+            "ADMIN" => Some(UserRole::ADMIN),
+            _ => None,
+        };
+    }
+
+    // Every role is allowed everything the roles below it are allowed.
+    pub fn includes(&self, role: &UserRole) -> bool {
+        return self.level() >= role.level();
+    }
+
+    fn level(&self) -> u8 {
+        return match self {
+            UserRole::USER => 0,
+            UserRole::MODERATOR => 1,
+            UserRole::ADMIN => 2,
+        };
+    }
+}
+
 impl From<&str> for UserRole {
     fn from(str: &str) -> Self {
         return match str {
@@ -51,4 +78,19 @@ mod test {
         assert_eq!(moderator, "MODERATOR");
         assert_eq!(admin, "ADMIN");
     }
+
+    #[test]
+    fn should_not_parse_unknown_role() {
+        assert_eq!(UserRole::parse("MODERATOR"), Some(UserRole::MODERATOR));
+        assert_eq!(UserRole::parse("OWNER"), None);
+    }
+
+    #[test]
+    fn should_include_lower_roles() {
+        assert!(UserRole::ADMIN.includes(&UserRole::MODERATOR));
+        assert!(UserRole::MODERATOR.includes(&UserRole::MODERATOR));
+        assert!(UserRole::MODERATOR.includes(&UserRole::USER));
+        assert!(!UserRole::MODERATOR.includes(&UserRole::ADMIN));
+        assert!(!UserRole::USER.includes(&UserRole::MODERATOR));
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::common::failure::domain::failure::{Failure, FailureType};
 
 pub fn get_invalid_credentials_error() -> Failure {
@@ -27,11 +29,80 @@ pub fn get_db_connection_error() -> Failure {
     };
 }
 
-pub fn get_client_secret_error() -> Failure {
+pub fn get_unauthorized_client_error() -> Failure {
+    return Failure {
+        args: None,
+        error_type: FailureType::Forbidden,
+        code: "error.unauthorized_client".to_string(),
+        message: "Client is not allowed to use this grant type".to_string(),
+    };
+}
+
+pub fn get_redis_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to redis".to_string(),
+    };
+}
+
+pub fn get_session_not_found_error() -> Failure {
+    return Failure {
+        error_type: FailureType::NotFound,
+        args: None,
+        code: "error.session_not_found".to_string(),
+        message: "Session not found".to_string(),
+    };
+}
+
+pub fn get_invalid_second_factor_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.invalid_mfa_code".to_string(),
+        message: "Invalid two-factor authentication code".to_string(),
+    };
+}
+
+pub fn get_insufficient_role_error() -> Failure {
     return Failure {
+        error_type: FailureType::Forbidden,
         args: None,
+        code: "error.insufficient_role".to_string(),
+        message: "Insufficient role".to_string(),
+    };
+}
+
+pub fn get_insufficient_scope_error(scope: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("scope".to_string(), scope.clone());
+
+    return Failure {
         error_type: FailureType::Forbidden,
-        code: "error.invalid_client_secret".to_string(),
-        message: "Invalid client secret".to_string(),
+        code: "error.insufficient_scope".to_string(),
+        message: format!("Token is missing the '{}' scope", scope),
+        args: Some(args),
+    };
+}
+
+pub fn get_too_many_login_attempts_error(retry_after: u64) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("retry_after".to_string(), retry_after.to_string());
+
+    return Failure {
+        error_type: FailureType::TooManyRequests,
+        code: "error.too_many_login_attempts".to_string(),
+        message: format!("Too many login attempts, retry in {} seconds", retry_after),
+        args: Some(args),
+    };
+}
+
+pub fn get_email_not_verified_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.email_not_verified".to_string(),
+        message: "Email has to be verified before logging in".to_string(),
     };
 }
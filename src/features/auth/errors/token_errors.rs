@@ -26,3 +26,30 @@ pub fn get_invalid_refresh_token_error() -> Failure {
         message: "Invalid refresh token".to_string(),
     };
 }
+
+pub fn get_refresh_token_reused_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.refresh_token_reused".to_string(),
+        message: "Refresh token has already been used".to_string(),
+    };
+}
+
+pub fn get_invalid_mfa_token_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.invalid_mfa_token".to_string(),
+        message: "Invalid mfa token".to_string(),
+    };
+}
+
+pub fn get_invalid_signing_key_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.invalid_signing_key".to_string(),
+        message: "Invalid token signing key".to_string(),
+    };
+}
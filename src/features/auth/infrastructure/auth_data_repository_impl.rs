@@ -46,4 +46,20 @@ impl AuthDataRepository for AuthDataRepositoryImpl {
             Err(_) => Err(get_invalid_credentials_error()),
         };
     }
+
+    async fn update_password_hash(
+        &self,
+        user_id: &i64,
+        password_hash: &String,
+    ) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::update(profile.find(user_id))
+            .set(password.eq(password_hash))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
 }
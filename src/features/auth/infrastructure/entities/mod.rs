@@ -1 +1,3 @@
+pub mod token_actor;
 pub mod token_claims;
+pub mod token_type;
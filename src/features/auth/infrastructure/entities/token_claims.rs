@@ -1,9 +1,25 @@
 use serde::{Deserialize, Serialize};
 
+use super::{token_actor::TokenActor, token_type::TokenType};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenClaims {
+    pub typ: TokenType,
+    pub iss: String,
+    pub aud: String,
+    pub sub: String,
+    pub iat: usize,
+    pub nbf: usize,
     pub exp: usize,
-    pub user_id: i64,
+    pub jti: String,
+    pub client_id: String,
     pub user_role: String,
-    pub authorized: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    // Only access tokens carry scopes, space separated as described in RFC 9068.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    // The admin acting as the subject on impersonation tokens, as described in RFC 8693.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<TokenActor>,
 }
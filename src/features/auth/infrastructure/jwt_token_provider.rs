@@ -1,110 +1,634 @@
+use std::{collections::HashMap, fs};
+
 use chrono::Utc;
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, decode_header, encode, errors::Error, Header, Validation};
+use nanoid::nanoid;
 
 use crate::{
     common::failure::domain::failure::Failure,
-    config::auth_config::AuthConfig,
-    features::auth::{
-        domain::{tokens_pair::TokensPair, user_role::UserRole},
-        errors::token_errors::{
-            get_invalid_access_token_error, get_invalid_refresh_token_error,
-            get_token_generating_error,
+    config::{auth_config::AuthConfig, jwt_key_set::JwtKeyConfig},
+    features::{
+        auth::{
+            domain::{
+                access_token_data::AccessTokenData, client_data::ClientData,
+                mfa_token_data::MfaTokenData, public_key::PublicKey,
+                refresh_token_data::RefreshTokenData, scope::Scope, session::Session,
+                tokens_pair::TokensPair, user_role::UserRole,
+            },
+            errors::token_errors::{
+                get_invalid_access_token_error, get_invalid_mfa_token_error,
+                get_invalid_refresh_token_error, get_invalid_signing_key_error,
+                get_token_generating_error,
+            },
+            infrastructure::{
+                entities::{
+                    token_actor::TokenActor, token_claims::TokenClaims, token_type::TokenType,
+                },
+                jwt_key::JwtKey,
+            },
+            interactors::auth_interactor::TokenProvider,
+        },
+        impersonation::{
+            domain::impersonation_token::ImpersonationToken,
+            interactors::impersonation_interactor::ImpersonationTokenProvider,
         },
-        infrastructure::entities::token_claims::TokenClaims,
-        interactors::auth_interactor::TokenProvider,
     },
 };
 
 pub struct JwtTokenProvider {
     auth_config: AuthConfig,
+    keys: HashMap<String, JwtKey>,
 }
 
 impl JwtTokenProvider {
     pub fn new(auth_config: AuthConfig) -> JwtTokenProvider {
-        return JwtTokenProvider { auth_config };
+        let mut keys = HashMap::new();
+        for key_config in auth_config.jwt_key_set.keys.iter() {
+            let key = JwtTokenProvider::load_key(&auth_config, key_config)
+                .unwrap_or_else(|failure| panic!("{}: {}", failure.message, key_config.id));
+            keys.insert(key.id.clone(), key);
+        }
+        return JwtTokenProvider { auth_config, keys };
+    }
+
+    fn load_key(auth_config: &AuthConfig, key_config: &JwtKeyConfig) -> Result<JwtKey, Failure> {
+        if key_config.algorithm == "HS256" {
+            // The key file holds the secret, `PRIVATE_KEY` is only used by a single key set.
+            let secret = match &key_config.private_key_path {
+                Some(path) => fs::read_to_string(path)
+                    .map_err(|_| get_invalid_signing_key_error())?
+                    .trim()
+                    .to_string(),
+                None => auth_config.private_key.clone(),
+            };
+            if secret.is_empty() {
+                return Err(get_invalid_signing_key_error());
+            }
+            return Ok(JwtKey::from_secret(&key_config.id, &secret));
+        }
+        let path = key_config
+            .private_key_path
+            .as_ref()
+            .ok_or(get_invalid_signing_key_error())?;
+        let pem = fs::read(path).map_err(|_| get_invalid_signing_key_error())?;
+
+        return JwtKey::from_pem(&key_config.id, &key_config.algorithm, &pem);
+    }
+
+    fn get_signing_key(&self) -> &JwtKey {
+        return &self.keys[&self.auth_config.jwt_key_set.current];
+    }
+
+    fn get_claims(
+        &self,
+        token_type: TokenType,
+        id: &i64,
+        role: &UserRole,
+        client_id: &String,
+        exp: u64,
+        session_id: Option<String>,
+    ) -> TokenClaims {
+        let now = Utc::now().timestamp().unsigned_abs();
+
+        return TokenClaims {
+            typ: token_type,
+            iss: self.auth_config.jwt_issuer.clone(),
+            aud: self.auth_config.jwt_audience.clone(),
+            sub: id.to_string(),
+            iat: now as usize,
+            nbf: now as usize,
+            exp: (now + exp) as usize,
+            jti: nanoid!(),
+            client_id: client_id.to_owned(),
+            user_role: String::from(role),
+            sid: session_id,
+            scope: None,
+            act: None,
+        };
+    }
+
+    fn encode_claims(&self, claims: &TokenClaims) -> Result<String, Error> {
+        let signing_key = self.get_signing_key();
+        let mut header = Header::new(signing_key.algorithm);
+        header.kid = Some(signing_key.id.clone());
+
+        return encode(&header, claims, &signing_key.encoding_key);
+    }
+
+    // Tokens are verified with the key they name, so tokens signed before a rotation stay
+    // valid for as long as their key is kept in the key set.
+    fn decode_claims(&self, token: &String, token_type: TokenType) -> Option<(i64, TokenClaims)> {
+        let header = decode_header(token).ok()?;
+        let key = match header.kid {
+            Some(kid) => self.keys.get(&kid)?,
+            None => self.get_signing_key(),
+        };
+        let mut validation = Validation::new(key.algorithm);
+        validation.validate_nbf = true;
+        validation.set_issuer(&[&self.auth_config.jwt_issuer]);
+        validation.set_audience(&[&self.auth_config.jwt_audience]);
+        validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud", "sub"]);
+
+        let claims = decode::<TokenClaims>(token, &key.decoding_key, &validation)
+            .ok()?
+            .claims;
+        if claims.typ != token_type {
+            return None;
+        }
+        let user_id = claims.sub.parse::<i64>().ok()?;
+        // Checked here so a malformed actor cannot pass as a regular, unaudited token.
+        if let Some(actor) = &claims.act {
+            actor.sub.parse::<i64>().ok()?;
+        }
+
+        return Some((user_id, claims));
     }
 }
 
 impl TokenProvider for JwtTokenProvider {
-    fn generate_token(&self, id: &i64, role: &UserRole) -> Result<TokensPair, Failure> {
+    fn generate_token(
+        &self,
+        id: &i64,
+        role: &UserRole,
+        session: &Session,
+        client: &ClientData,
+    ) -> Result<TokensPair, Failure> {
+        let access_token_exp = client
+            .access_token_exp
+            .unwrap_or(self.auth_config.access_token_exp);
+        let refresh_token_exp = client
+            .refresh_token_exp
+            .unwrap_or(self.auth_config.refresh_token_exp);
         let access_token_claims = TokenClaims {
-            authorized: true,
-            user_role: String::from(role),
-            user_id: id.to_owned(),
-            exp: (Utc::now().timestamp().unsigned_abs() + self.auth_config.access_token_exp)
-                as usize,
+            scope: Some(Scope::join(&client.scopes)),
+            ..self.get_claims(
+                TokenType::Access,
+                id,
+                role,
+                &client.id,
+                access_token_exp,
+                Some(session.id.clone()),
+            )
         };
+        // The refresh token id is tracked by the session storage to detect token reuse.
         let refresh_token_claims = TokenClaims {
-            authorized: true,
-            user_role: String::from(role),
-            user_id: id.to_owned(),
-            exp: (Utc::now().timestamp().unsigned_abs() + self.auth_config.refresh_token_exp)
-                as usize,
+            jti: session.refresh_token_id.clone(),
+            ..self.get_claims(
+                TokenType::Refresh,
+                id,
+                role,
+                &client.id,
+                refresh_token_exp,
+                Some(session.id.clone()),
+            )
         };
 
-        let access_token = encode(
-            &Header::default(),
-            &access_token_claims,
-            &EncodingKey::from_secret(self.auth_config.private_key.as_ref()),
-        );
-        let refresh_token = encode(
-            &Header::default(),
-            &refresh_token_claims,
-            &EncodingKey::from_secret(self.auth_config.private_key.as_ref()),
-        );
+        let access_token = self.encode_claims(&access_token_claims);
+        let refresh_token = self.encode_claims(&refresh_token_claims);
 
         return match (access_token, refresh_token) {
             (Ok(access), Ok(refresh)) => Ok(TokensPair {
                 access_token: access,
                 refresh_token: refresh,
-                access_token_exp: self.auth_config.access_token_exp,
-                refresh_token_exp: self.auth_config.refresh_token_exp,
+                access_token_exp,
+                refresh_token_exp,
             }),
             _ => Err(get_token_generating_error()),
         };
     }
 
-    fn validate_access_token(&self, access_token: &String) -> Result<(i64, UserRole), Failure> {
-        let token = decode::<TokenClaims>(
-            access_token,
-            &DecodingKey::from_secret(self.auth_config.private_key.as_ref()),
-            &Validation::default(),
-        );
+    fn validate_access_token(&self, access_token: &String) -> Result<AccessTokenData, Failure> {
+        let token = self.decode_claims(access_token, TokenType::Access);
 
         return match token {
-            Ok(token) => {
-                return if token.claims.authorized {
-                    Ok((
-                        token.claims.user_id,
-                        UserRole::from(token.claims.user_role.as_str()),
-                    ))
-                } else {
-                    Err(get_invalid_access_token_error())
-                }
-            }
-            _ => Err(get_invalid_access_token_error()),
+            Some((user_id, claims)) => Ok(AccessTokenData {
+                user_id,
+                role: UserRole::from(claims.user_role.as_str()),
+                session_id: claims.sid,
+                actor_id: claims.act.and_then(|actor| actor.sub.parse::<i64>().ok()),
+                client_id: claims.client_id,
+                scopes: Scope::parse_list(&claims.scope.unwrap_or_default()),
+                issued_at: claims.iat as i64,
+                expires_at: claims.exp as i64,
+            }),
+            None => Err(get_invalid_access_token_error()),
         };
     }
 
-    fn validate_refresh_token(&self, refresh_token: &String) -> Result<(i64, UserRole), Failure> {
-        let token = decode::<TokenClaims>(
-            refresh_token,
-            &DecodingKey::from_secret(self.auth_config.private_key.as_ref()),
-            &Validation::default(),
-        );
+    fn validate_refresh_token(&self, refresh_token: &String) -> Result<RefreshTokenData, Failure> {
+        let token = self.decode_claims(refresh_token, TokenType::Refresh);
+
+        return match token {
+            Some((user_id, claims)) => match claims.sid {
+                Some(session_id) => Ok(RefreshTokenData {
+                    user_id,
+                    role: UserRole::from(claims.user_role.as_str()),
+                    session_id,
+                    client_id: claims.client_id,
+                    token_id: claims.jti,
+                    issued_at: claims.iat as i64,
+                    expires_at: claims.exp as i64,
+                }),
+                None => Err(get_invalid_refresh_token_error()),
+            },
+            None => Err(get_invalid_refresh_token_error()),
+        };
+    }
+
+    fn generate_mfa_token(
+        &self,
+        id: &i64,
+        role: &UserRole,
+        client_id: &String,
+        token_id: &String,
+    ) -> Result<String, Failure> {
+        // The token id names the challenge tracked by the session storage.
+        let claims = TokenClaims {
+            jti: token_id.clone(),
+            ..self.get_claims(
+                TokenType::Mfa,
+                id,
+                role,
+                client_id,
+                self.auth_config.mfa_token_exp,
+                None,
+            )
+        };
+
+        let token = self.encode_claims(&claims);
+
+        return match token {
+            Ok(token) => Ok(token),
+            Err(_) => Err(get_token_generating_error()),
+        };
+    }
+
+    fn validate_mfa_token(&self, mfa_token: &String) -> Result<MfaTokenData, Failure> {
+        let token = self.decode_claims(mfa_token, TokenType::Mfa);
 
         return match token {
-            Ok(token) => {
-                return if !token.claims.authorized {
-                    Ok((
-                        token.claims.user_id,
-                        UserRole::from(token.claims.user_role.as_str()),
-                    ))
-                } else {
-                    Err(get_invalid_refresh_token_error())
-                }
+            Some((user_id, claims)) => Ok(MfaTokenData {
+                user_id,
+                role: UserRole::from(claims.user_role.as_str()),
+                client_id: claims.client_id,
+                token_id: claims.jti,
+            }),
+            None => Err(get_invalid_mfa_token_error()),
+        };
+    }
+
+    fn get_public_keys(&self) -> Vec<PublicKey> {
+        let mut public_keys = self
+            .keys
+            .values()
+            .filter_map(|key| key.public_key.clone())
+            .collect::<Vec<PublicKey>>();
+        public_keys.sort_by(|first, second| first.id.cmp(&second.id));
+
+        return public_keys;
+    }
+}
+
+impl ImpersonationTokenProvider for JwtTokenProvider {
+    fn generate_impersonation_token(
+        &self,
+        user_id: &i64,
+        role: &UserRole,
+        actor_id: &i64,
+        client_id: &String,
+        scopes: &[Scope],
+    ) -> Result<ImpersonationToken, Failure> {
+        let access_token_exp = self.auth_config.impersonation_token_exp;
+        let claims = TokenClaims {
+            scope: Some(Scope::join(scopes)),
+            act: Some(TokenActor {
+                sub: actor_id.to_string(),
+            }),
+            ..self.get_claims(
+                TokenType::Access,
+                user_id,
+                role,
+                client_id,
+                access_token_exp,
+                None,
+            )
+        };
+
+        return match self.encode_claims(&claims) {
+            Ok(access_token) => Ok(ImpersonationToken {
+                access_token,
+                access_token_exp,
+            }),
+            Err(_) => Err(get_token_generating_error()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use crate::config::jwt_key_set::JwtKeySet;
+
+    use super::*;
+
+    fn get_config(private_key: &str, key_ids: Vec<&str>, current: &str) -> AuthConfig {
+        return AuthConfig {
+            private_key: private_key.to_string(),
+            access_token_exp: 60,
+            refresh_token_exp: 120,
+            mfa_token_exp: 30,
+            jwt_issuer: "issuer".to_string(),
+            jwt_audience: "audience".to_string(),
+            jwt_key_set: JwtKeySet {
+                current: current.to_string(),
+                keys: key_ids
+                    .into_iter()
+                    .map(|id| JwtKeyConfig {
+                        id: id.to_string(),
+                        algorithm: "HS256".to_string(),
+                        private_key_path: None,
+                        retired_at: None,
+                    })
+                    .collect(),
+            },
+            require_verified_email: false,
+            impersonation_token_exp: 15,
+        };
+    }
+
+    fn get_provider() -> JwtTokenProvider {
+        return JwtTokenProvider::new(get_config("private_key", vec!["key_id"], "key_id"));
+    }
+
+    fn get_client() -> ClientData {
+        return ClientData {
+            id: "client_id".to_string(),
+            grant_types: vec![],
+            scopes: vec![Scope::ProfileRead, Scope::PostsWrite],
+            access_token_exp: None,
+            refresh_token_exp: None,
+        };
+    }
+
+    fn get_session() -> Session {
+        return Session {
+            id: "session_id".to_string(),
+            user_id: 1,
+            refresh_token_id: "refresh_token_id".to_string(),
+            user_agent: None,
+            ip: None,
+            created_at: Utc::now(),
+            refreshed_at: None,
+        };
+    }
+
+    #[test]
+    fn should_embed_session_in_refresh_token() {
+        let provider = get_provider();
+        let tokens = provider
+            .generate_token(&1, &UserRole::MODERATOR, &get_session(), &get_client())
+            .unwrap();
+
+        let result = provider
+            .validate_refresh_token(&tokens.refresh_token)
+            .unwrap();
+
+        assert_eq!(
+            result,
+            RefreshTokenData {
+                user_id: 1,
+                role: UserRole::MODERATOR,
+                session_id: "session_id".to_string(),
+                client_id: "client_id".to_string(),
+                token_id: "refresh_token_id".to_string(),
+                issued_at: result.issued_at,
+                expires_at: result.issued_at + 120,
             }
-            _ => Err(get_invalid_refresh_token_error()),
+        );
+    }
+
+    #[test]
+    fn should_embed_session_in_access_token() {
+        let provider = get_provider();
+        let tokens = provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let result = provider
+            .validate_access_token(&tokens.access_token)
+            .unwrap();
+
+        assert_eq!(result.user_id, 1);
+        assert_eq!(result.role, UserRole::USER);
+        assert_eq!(result.session_id, Some("session_id".to_string()));
+        assert_eq!(result.scopes, vec![Scope::ProfileRead, Scope::PostsWrite]);
+        assert_eq!(result.expires_at - result.issued_at, 60);
+    }
+
+    #[test]
+    fn should_not_accept_tokens_of_other_type() {
+        let provider = get_provider();
+        let tokens = provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let access_as_refresh = provider.validate_refresh_token(&tokens.access_token);
+        let refresh_as_access = provider.validate_access_token(&tokens.refresh_token);
+
+        assert_eq!(access_as_refresh, Err(get_invalid_refresh_token_error()));
+        assert_eq!(refresh_as_access, Err(get_invalid_access_token_error()));
+    }
+
+    #[test]
+    fn should_embed_user_in_mfa_token() {
+        let provider = get_provider();
+        let token = provider
+            .generate_mfa_token(
+                &1,
+                &UserRole::ADMIN,
+                &"client_id".to_string(),
+                &"challenge_id".to_string(),
+            )
+            .unwrap();
+
+        let result = provider.validate_mfa_token(&token);
+
+        assert_eq!(
+            result,
+            Ok(MfaTokenData {
+                user_id: 1,
+                role: UserRole::ADMIN,
+                client_id: "client_id".to_string(),
+                token_id: "challenge_id".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_not_accept_mfa_token_as_access_token() {
+        let provider = get_provider();
+        let mfa_token = provider
+            .generate_mfa_token(
+                &1,
+                &UserRole::ADMIN,
+                &"client_id".to_string(),
+                &"challenge_id".to_string(),
+            )
+            .unwrap();
+        let tokens = provider
+            .generate_token(&1, &UserRole::ADMIN, &get_session(), &get_client())
+            .unwrap();
+
+        let mfa_as_access = provider.validate_access_token(&mfa_token);
+        let mfa_as_refresh = provider.validate_refresh_token(&mfa_token);
+        let access_as_mfa = provider.validate_mfa_token(&tokens.access_token);
+
+        assert_eq!(mfa_as_access, Err(get_invalid_access_token_error()));
+        assert_eq!(mfa_as_refresh, Err(get_invalid_refresh_token_error()));
+        assert_eq!(access_as_mfa, Err(get_invalid_mfa_token_error()));
+    }
+
+    #[test]
+    fn should_embed_actor_in_impersonation_token() {
+        let provider = get_provider();
+        let token = provider
+            .generate_impersonation_token(
+                &2,
+                &UserRole::USER,
+                &1,
+                &"client_id".to_string(),
+                &[Scope::ProfileRead],
+            )
+            .unwrap();
+
+        let result = provider.validate_access_token(&token.access_token).unwrap();
+        let as_refresh = provider.validate_refresh_token(&token.access_token);
+
+        assert_eq!(token.access_token_exp, 15);
+        assert_eq!(result.user_id, 2);
+        assert_eq!(result.actor_id, Some(1));
+        assert_eq!(result.session_id, None);
+        assert_eq!(result.scopes, vec![Scope::ProfileRead]);
+        assert_eq!(as_refresh, Err(get_invalid_refresh_token_error()));
+    }
+
+    #[test]
+    fn should_sign_tokens_with_key_id() {
+        let provider = get_provider();
+        let tokens = provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let header = jsonwebtoken::decode_header(&tokens.access_token).unwrap();
+
+        assert_eq!(header.kid, Some("key_id".to_string()));
+        assert_eq!(provider.get_public_keys(), vec![]);
+    }
+
+    fn write_secret(secret: &str) -> String {
+        let path = env::temp_dir().join(format!("{}.key", nanoid!()));
+        fs::write(&path, secret).unwrap();
+
+        return path.to_string_lossy().to_string();
+    }
+
+    #[test]
+    fn should_verify_tokens_signed_with_previous_key() {
+        let old_provider = JwtTokenProvider::new(get_config("secret", vec!["old"], "old"));
+        let mut new_config = get_config("", vec!["old", "new"], "new");
+        new_config.jwt_key_set.keys[0].private_key_path = Some(write_secret("secret"));
+        new_config.jwt_key_set.keys[1].private_key_path = Some(write_secret("new_secret"));
+        let new_provider = JwtTokenProvider::new(new_config);
+        let old_tokens = old_provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+        let new_tokens = new_provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let old_token_result = new_provider.validate_access_token(&old_tokens.access_token);
+        let header = jsonwebtoken::decode_header(&new_tokens.access_token).unwrap();
+
+        assert!(old_token_result.is_ok());
+        assert_eq!(header.kid, Some("new".to_string()));
+    }
+
+    #[test]
+    fn should_reject_tokens_signed_with_pruned_key() {
+        let old_provider = JwtTokenProvider::new(get_config("secret", vec!["old"], "old"));
+        let new_provider = JwtTokenProvider::new(get_config("secret", vec!["new"], "new"));
+        let old_tokens = old_provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let result = new_provider.validate_access_token(&old_tokens.access_token);
+
+        assert_eq!(result, Err(get_invalid_access_token_error()));
+    }
+
+    #[test]
+    fn should_reject_tokens_of_other_issuer_or_audience() {
+        let provider = get_provider();
+        let mut other_issuer_config = get_config("private_key", vec!["key_id"], "key_id");
+        other_issuer_config.jwt_issuer = "other_issuer".to_string();
+        let mut other_audience_config = get_config("private_key", vec!["key_id"], "key_id");
+        other_audience_config.jwt_audience = "other_audience".to_string();
+        let other_issuer_tokens = JwtTokenProvider::new(other_issuer_config)
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+        let other_audience_tokens = JwtTokenProvider::new(other_audience_config)
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let other_issuer_result = provider.validate_access_token(&other_issuer_tokens.access_token);
+        let other_audience_result =
+            provider.validate_refresh_token(&other_audience_tokens.refresh_token);
+
+        assert_eq!(other_issuer_result, Err(get_invalid_access_token_error()));
+        assert_eq!(
+            other_audience_result,
+            Err(get_invalid_refresh_token_error())
+        );
+    }
+
+    #[test]
+    fn should_embed_standard_claims() {
+        let provider = get_provider();
+        let tokens = provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &get_client())
+            .unwrap();
+
+        let (_, claims) = provider
+            .decode_claims(&tokens.access_token, TokenType::Access)
+            .unwrap();
+
+        assert_eq!(claims.typ, TokenType::Access);
+        assert_eq!(claims.iss, "issuer".to_string());
+        assert_eq!(claims.aud, "audience".to_string());
+        assert_eq!(claims.sub, "1".to_string());
+        assert_eq!(claims.nbf, claims.iat);
+        assert!(!claims.jti.is_empty());
+    }
+
+    #[test]
+    fn should_use_token_lifetimes_of_client() {
+        let provider = get_provider();
+        let client = ClientData {
+            access_token_exp: Some(10),
+            ..get_client()
         };
+        let tokens = provider
+            .generate_token(&1, &UserRole::USER, &get_session(), &client)
+            .unwrap();
+
+        let (_, claims) = provider
+            .decode_claims(&tokens.access_token, TokenType::Access)
+            .unwrap();
+
+        assert_eq!(tokens.access_token_exp, 10);
+        assert_eq!(tokens.refresh_token_exp, 120);
+        assert_eq!(claims.exp - claims.iat, 10);
+        assert_eq!(claims.client_id, "client_id".to_string());
     }
 }
@@ -0,0 +1,266 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    config::login_throttle_config::LoginThrottleConfig,
+    features::auth::{
+        errors::auth_errors::get_redis_connection_error,
+        interactors::auth_interactor::LoginThrottle,
+    },
+};
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, RedisError, Script};
+
+// Counts an attempt on every attempts key unless one of the lock keys is set. Keys come in
+// attempts and lock pairs. Returns the longest remaining lock followed by the new counts, so
+// the count and the check can not race.
+const REGISTER_ATTEMPT_SCRIPT: &str = r"
+local lock = 0
+for i = 2, #KEYS, 2 do
+    lock = math.max(lock, redis.call('TTL', KEYS[i]))
+end
+if lock > 0 then
+    return {lock}
+end
+local result = {0}
+for i = 1, #KEYS, 2 do
+    table.insert(result, redis.call('INCR', KEYS[i]))
+    redis.call('EXPIRE', KEYS[i], ARGV[1])
+end
+return result
+";
+
+// Drops the login counter and takes the successful attempt back from the IP counter, if it
+// is still counted. Logins of many users can share an IP, only failed ones should add up.
+const RESET_SCRIPT: &str = r"
+redis.call('DEL', KEYS[1], KEYS[2])
+if KEYS[3] and tonumber(redis.call('GET', KEYS[3]) or '0') > 0 then
+    redis.call('DECR', KEYS[3])
+end
+return 1
+";
+
+pub struct LoginThrottleImpl {
+    redis_connection: MultiplexedConnection,
+    config: LoginThrottleConfig,
+}
+
+impl LoginThrottleImpl {
+    pub fn new(redis: MultiplexedConnection, config: LoginThrottleConfig) -> LoginThrottleImpl {
+        return LoginThrottleImpl {
+            redis_connection: redis,
+            config,
+        };
+    }
+
+    fn get_login_attempts_key(login: &String) -> String {
+        return format!("login_attempts:{}", login.to_lowercase());
+    }
+
+    fn get_login_lock_key(login: &String) -> String {
+        return format!("login_lock:{}", login.to_lowercase());
+    }
+
+    fn get_ip_attempts_key(ip: &String) -> String {
+        return format!("login_attempts_ip:{}", ip);
+    }
+
+    fn get_ip_lock_key(ip: &String) -> String {
+        return format!("login_lock_ip:{}", ip);
+    }
+
+    fn get_second_factor_attempts_key(user_id: &i64) -> String {
+        return format!("mfa_attempts:{}", user_id);
+    }
+
+    fn get_second_factor_lock_key(user_id: &i64) -> String {
+        return format!("mfa_lock:{}", user_id);
+    }
+
+    // Registers the attempt on every counter and locks the counters that ran out of free
+    // attempts. `limits` holds the highest allowed count of each counter.
+    async fn register_attempts(
+        &self,
+        counters: &[(String, String)],
+        limits: &[u64],
+        get_delay: impl Fn(usize, u64) -> u64,
+    ) -> Result<Option<u64>, Failure> {
+        let mut connection = self.redis_connection.clone();
+        let script = Script::new(REGISTER_ATTEMPT_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        for (attempts_key, lock_key) in counters.iter() {
+            invocation.key(attempts_key).key(lock_key);
+        }
+
+        let result: Result<Vec<u64>, RedisError> = invocation
+            .arg(self.config.lockout_duration)
+            .invoke_async(&mut connection)
+            .await;
+        let result = match result {
+            Ok(result) => result,
+            Err(_) => return Err(get_redis_connection_error()),
+        };
+        if result[0] > 0 {
+            return Ok(Some(result[0]));
+        }
+        let attempts = &result[1..];
+
+        // The attempt is let through, the delay applies to the next one.
+        let mut pipe = redis::pipe();
+        for (index, ((_, lock_key), attempts)) in counters.iter().zip(attempts).enumerate() {
+            let delay = get_delay(index, *attempts);
+            if delay > 0 {
+                pipe.set_ex(lock_key, 1, delay as usize).ignore();
+            }
+        }
+        let result: Result<(), RedisError> = pipe.query_async(&mut connection).await;
+        if result.is_err() {
+            return Err(get_redis_connection_error());
+        }
+        // Parallel attempts can all pass the lock check before the lock is set.
+        let is_over_limit = attempts
+            .iter()
+            .zip(limits)
+            .any(|(attempts, limit)| attempts > limit);
+        if is_over_limit {
+            return Ok(Some(self.config.lockout_duration));
+        }
+
+        return Ok(None);
+    }
+}
+
+#[async_trait]
+impl LoginThrottle for LoginThrottleImpl {
+    async fn register_attempt(
+        &self,
+        login: &String,
+        ip: &Option<String>,
+    ) -> Result<Option<u64>, Failure> {
+        let mut counters = vec![(
+            LoginThrottleImpl::get_login_attempts_key(login),
+            LoginThrottleImpl::get_login_lock_key(login),
+        )];
+        if let Some(ip) = ip {
+            counters.push((
+                LoginThrottleImpl::get_ip_attempts_key(ip),
+                LoginThrottleImpl::get_ip_lock_key(ip),
+            ));
+        }
+        let limits = [self.config.max_attempts, self.config.max_ip_attempts];
+
+        self.register_attempts(&counters, &limits, |index, attempts| match index {
+            0 => get_login_delay(&self.config, attempts),
+            _ => get_ip_delay(&self.config, attempts),
+        })
+        .await
+    }
+
+    async fn reset(&self, login: &String, ip: &Option<String>) -> Result<(), Failure> {
+        let mut connection = self.redis_connection.clone();
+        let script = Script::new(RESET_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        invocation
+            .key(LoginThrottleImpl::get_login_attempts_key(login))
+            .key(LoginThrottleImpl::get_login_lock_key(login));
+        if let Some(ip) = ip {
+            invocation.key(LoginThrottleImpl::get_ip_attempts_key(ip));
+        }
+
+        let result: Result<(), RedisError> = invocation.invoke_async(&mut connection).await;
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    // Keyed by user, every attempt needs a new password login which resets the login counter.
+    async fn register_second_factor_attempt(&self, user_id: &i64) -> Result<Option<u64>, Failure> {
+        let counters = [(
+            LoginThrottleImpl::get_second_factor_attempts_key(user_id),
+            LoginThrottleImpl::get_second_factor_lock_key(user_id),
+        )];
+
+        self.register_attempts(&counters, &[self.config.max_attempts], |_, attempts| {
+            get_login_delay(&self.config, attempts)
+        })
+        .await
+    }
+
+    async fn reset_second_factor(&self, user_id: &i64) -> Result<(), Failure> {
+        let mut connection = self.redis_connection.clone();
+
+        let result: Result<(), RedisError> = connection
+            .del(&[
+                LoginThrottleImpl::get_second_factor_attempts_key(user_id),
+                LoginThrottleImpl::get_second_factor_lock_key(user_id),
+            ])
+            .await;
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+}
+
+// The first few failures are free, after that every failure doubles the wait until the
+// login is locked out completely.
+fn get_login_delay(config: &LoginThrottleConfig, failures: u64) -> u64 {
+    if failures >= config.max_attempts {
+        return config.lockout_duration;
+    }
+    if failures <= config.free_attempts {
+        return 0;
+    }
+    let exponent = (failures - config.free_attempts - 1).min(32) as u32;
+
+    return config
+        .backoff_base
+        .saturating_mul(2_u64.pow(exponent))
+        .min(config.lockout_duration);
+}
+
+// Many users can share an IP, so it is only locked out after a lot more failures.
+fn get_ip_delay(config: &LoginThrottleConfig, failures: u64) -> u64 {
+    if failures >= config.max_ip_attempts {
+        return config.lockout_duration;
+    }
+    return 0;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_config() -> LoginThrottleConfig {
+        return LoginThrottleConfig {
+            free_attempts: 3,
+            max_attempts: 10,
+            max_ip_attempts: 100,
+            backoff_base: 2,
+            lockout_duration: 900,
+        };
+    }
+
+    #[test]
+    fn should_back_off_exponentially_after_free_attempts() {
+        let config = get_config();
+
+        let delays = (1..=9)
+            .map(|failures| get_login_delay(&config, failures))
+            .collect::<Vec<u64>>();
+
+        assert_eq!(delays, vec![0, 0, 0, 2, 4, 8, 16, 32, 64]);
+    }
+
+    #[test]
+    fn should_lock_out_after_max_attempts() {
+        let config = get_config();
+
+        assert_eq!(get_login_delay(&config, 10), 900);
+        assert_eq!(get_login_delay(&config, 1000), 900);
+        assert_eq!(get_ip_delay(&config, 99), 0);
+        assert_eq!(get_ip_delay(&config, 100), 900);
+    }
+}
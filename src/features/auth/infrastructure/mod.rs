@@ -1,4 +1,7 @@
 pub mod auth_data_repository_impl;
 pub mod entities;
+pub mod jwt_key;
 pub mod jwt_token_provider;
+pub mod login_throttle_impl;
 pub mod password_manager_impl;
+pub mod session_storage_impl;
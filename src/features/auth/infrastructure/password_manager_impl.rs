@@ -1,5 +1,6 @@
 use crate::{
     common::failure::domain::failure::Failure,
+    config::password_config::PasswordConfig,
     features::{
         auth::{
             errors::password_errors::{
@@ -11,26 +12,114 @@ use crate::{
     },
 };
 
+use argon2::{
+    password_hash::{PasswordHash, Salt, SaltString},
+    Algorithm, Argon2, Params, PasswordHasher as _, PasswordVerifier as _, Version,
+};
 use async_trait::async_trait;
+use futures::{
+    executor::ThreadPool,
+    task::{SpawnError, SpawnExt},
+};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::convert::TryFrom;
 
-use bcrypt::{hash, verify, DEFAULT_COST};
+const BCRYPT_PREFIXES: [&str; 4] = ["$2a$", "$2b$", "$2x$", "$2y$"];
 
-pub struct PasswordManagerImpl;
+// Hashing is CPU bound and takes tens of milliseconds, so it runs on a dedicated pool instead
+// of blocking the actix worker that handles the request. Clones share the same pool.
+#[derive(Clone)]
+pub struct PasswordManagerImpl {
+    config: PasswordConfig,
+    pool: ThreadPool,
+}
 
 impl PasswordManagerImpl {
-    pub fn new() -> PasswordManagerImpl {
-        return PasswordManagerImpl {};
+    pub fn new(config: PasswordConfig) -> PasswordManagerImpl {
+        let pool = ThreadPool::builder()
+            .pool_size(config.hashing_threads)
+            .name_prefix("password-hashing-")
+            .create()
+            .unwrap();
+        return PasswordManagerImpl { config, pool };
+    }
+
+    async fn run_blocking<F, R>(&self, job: F) -> Result<R, SpawnError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let handle = self.pool.spawn_with_handle(async move { job() })?;
+
+        return Ok(handle.await);
+    }
+
+    fn get_argon2(&self) -> Result<Argon2<'static>, Failure> {
+        let params = Params::new(
+            self.config.memory_cost,
+            self.config.iterations,
+            self.config.parallelism,
+            None,
+        )
+        .map_err(|_| get_password_hashing_error())?;
+
+        return Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params));
+    }
+
+    fn generate_salt(&self) -> Result<SaltString, Failure> {
+        let mut salt = [0u8; Salt::RECOMMENDED_LENGTH];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| get_password_hashing_error())?;
+
+        return SaltString::encode_b64(&salt).map_err(|_| get_password_hashing_error());
     }
 }
 
+fn is_bcrypt_hash(hash: &String) -> bool {
+    return BCRYPT_PREFIXES
+        .iter()
+        .any(|prefix| hash.starts_with(prefix));
+}
+
+fn verify_hash(password: &String, hash: &String) -> Result<bool, Failure> {
+    // Accounts created through an identity provider have no password.
+    if hash.is_empty() {
+        return Ok(false);
+    }
+    if is_bcrypt_hash(hash) {
+        return bcrypt::verify(password, hash).map_err(|_| get_password_verification_error());
+    }
+
+    let parsed = PasswordHash::new(hash).map_err(|_| get_password_verification_error())?;
+    // Parameters are taken from the hash itself, so hashes created with an older
+    // configuration keep verifying.
+    let result = Argon2::default().verify_password(password.as_bytes(), &parsed);
+
+    return match result {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(_) => Err(get_password_verification_error()),
+    };
+}
+
 #[async_trait]
 impl PasswordHasher for PasswordManagerImpl {
     async fn hash_password(&self, password: &String) -> Result<String, Failure> {
-        let hashed = hash(password, DEFAULT_COST);
+        let salt = self.generate_salt()?;
+        let argon2 = self.get_argon2()?;
+        let password = password.clone();
+        let hashed = self
+            .run_blocking(move || {
+                argon2
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hashed| hashed.to_string())
+            })
+            .await;
 
         return match hashed {
-            Ok(hashed) => Ok(hashed),
-            Err(_) => Err(get_password_hashing_error()),
+            Ok(Ok(hashed)) => Ok(hashed),
+            _ => Err(get_password_hashing_error()),
         };
     }
 }
@@ -38,11 +127,112 @@ impl PasswordHasher for PasswordManagerImpl {
 #[async_trait]
 impl PasswordVerifier for PasswordManagerImpl {
     async fn verify_password(&self, password: &String, hash: &String) -> Result<bool, Failure> {
-        let result = verify(password, hash);
+        let password = password.clone();
+        let hash = hash.clone();
+        let result = self
+            .run_blocking(move || verify_hash(&password, &hash))
+            .await;
 
         return match result {
-            Ok(result) => Ok(result),
+            Ok(result) => result,
             Err(_) => Err(get_password_verification_error()),
         };
     }
+
+    fn needs_rehash(&self, hash: &String) -> bool {
+        let parsed = match PasswordHash::new(hash) {
+            Ok(parsed) => parsed,
+            Err(_) => return true,
+        };
+        if parsed.algorithm != Algorithm::Argon2id.ident()
+            || parsed.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        return match Params::try_from(&parsed) {
+            Ok(params) => {
+                params.m_cost() != self.config.memory_cost
+                    || params.t_cost() != self.config.iterations
+                    || params.p_cost() != self.config.parallelism
+            }
+            Err(_) => true,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_config() -> PasswordConfig {
+        PasswordConfig {
+            memory_cost: 1024,
+            iterations: 1,
+            parallelism: 1,
+            hashing_threads: 1,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn should_run_hashing_on_dedicated_pool() {
+        let password_manager = PasswordManagerImpl::new(get_config());
+
+        let thread_name = password_manager
+            .run_blocking(|| std::thread::current().name().map(String::from))
+            .await
+            .unwrap();
+
+        assert!(thread_name.unwrap().starts_with("password-hashing-"));
+    }
+
+    #[actix_rt::test]
+    async fn should_hash_with_argon2id() {
+        let password_manager = PasswordManagerImpl::new(get_config());
+        let password = "Password1!".to_string();
+
+        let hash = password_manager.hash_password(&password).await.unwrap();
+
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(!password_manager.needs_rehash(&hash));
+        assert!(password_manager
+            .verify_password(&password, &hash)
+            .await
+            .unwrap());
+        assert!(!password_manager
+            .verify_password(&"Password2!".to_string(), &hash)
+            .await
+            .unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn should_verify_and_rehash_bcrypt_hashes() {
+        let password_manager = PasswordManagerImpl::new(get_config());
+        let password = "Password1!".to_string();
+        let hash = bcrypt::hash(&password, 4).unwrap();
+
+        assert!(password_manager.needs_rehash(&hash));
+        assert!(password_manager
+            .verify_password(&password, &hash)
+            .await
+            .unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn should_rehash_when_parameters_change() {
+        let old_password_manager = PasswordManagerImpl::new(get_config());
+        let password_manager = PasswordManagerImpl::new(PasswordConfig {
+            iterations: 2,
+            ..get_config()
+        });
+        let password = "Password1!".to_string();
+
+        let hash = old_password_manager.hash_password(&password).await.unwrap();
+
+        assert!(password_manager.needs_rehash(&hash));
+        assert!(password_manager
+            .verify_password(&password, &hash)
+            .await
+            .unwrap());
+    }
 }
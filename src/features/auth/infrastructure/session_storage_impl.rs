@@ -0,0 +1,376 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{
+    common::failure::domain::failure::Failure,
+    config::auth_config::AuthConfig,
+    features::{
+        auth::{
+            domain::{client_data::ClientData, device_info::DeviceInfo, session::Session},
+            errors::{
+                auth_errors::get_redis_connection_error,
+                token_errors::{get_invalid_refresh_token_error, get_refresh_token_reused_error},
+            },
+            interactors::auth_interactor::SessionStorage,
+        },
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientRepository,
+        },
+        profile::interactors::profile_interactor::SessionsInvalidator,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use nanoid::nanoid;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, FromRedisValue, RedisError, Script, Value};
+
+// Swaps the refresh token id stored for the session only if the presented one is still
+// the current one. Presenting an already rotated id means the token leaked, so the whole
+// session is dropped. On success the updated session hash is returned.
+const ROTATE_REFRESH_TOKEN_SCRIPT: &str = r"
+local current = redis.call('HGET', KEYS[1], 'refresh_token_id')
+if not current then
+    return 0
+end
+if current ~= ARGV[1] then
+    redis.call('DEL', KEYS[1])
+    redis.call('SREM', KEYS[2], ARGV[4])
+    return -1
+end
+redis.call('HSET', KEYS[1], 'refresh_token_id', ARGV[2], 'refreshed_at', ARGV[5])
+redis.call('EXPIRE', KEYS[1], ARGV[3])
+if redis.call('TTL', KEYS[2]) < tonumber(ARGV[3]) then
+    redis.call('EXPIRE', KEYS[2], ARGV[3])
+end
+return redis.call('HGETALL', KEYS[1])
+";
+
+// Sessions of other clients can live longer, so the user's session index only ever has its
+// expiration extended.
+const EXTEND_EXPIRATION_SCRIPT: &str = r"
+if redis.call('TTL', KEYS[1]) < tonumber(ARGV[1]) then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return 1
+";
+
+// Deletes the session only when it belongs to the given user.
+const REVOKE_SESSION_SCRIPT: &str = r"
+if redis.call('HGET', KEYS[1], 'user_id') ~= ARGV[1] then
+    return 0
+end
+redis.call('DEL', KEYS[1])
+redis.call('SREM', KEYS[2], ARGV[2])
+return 1
+";
+
+pub struct SessionStorageImpl {
+    redis_connection: MultiplexedConnection,
+    config: AuthConfig,
+    client_repository: ClientRepositoryImpl,
+}
+
+impl SessionStorageImpl {
+    pub fn new(
+        redis: MultiplexedConnection,
+        config: AuthConfig,
+        client_repository: ClientRepositoryImpl,
+    ) -> SessionStorageImpl {
+        return SessionStorageImpl {
+            redis_connection: redis,
+            config,
+            client_repository,
+        };
+    }
+
+    fn get_refresh_token_exp(&self, client: &ClientData) -> u64 {
+        return client
+            .refresh_token_exp
+            .unwrap_or(self.config.refresh_token_exp);
+    }
+
+    // Access tokens of any client, including impersonation tokens, are issued with the user
+    // id, so a cut-off for the user has to outlive the longest of them.
+    async fn get_max_access_token_exp(&self) -> Result<u64, Failure> {
+        let lifetimes = self.client_repository.get_max_token_lifetimes().await?;
+
+        return Ok(self
+            .config
+            .access_token_exp
+            .max(self.config.impersonation_token_exp)
+            .max(lifetimes.access_token_exp.unwrap_or(0)));
+    }
+
+    fn get_session_key(session_id: &String) -> String {
+        return format!("session:{}", session_id);
+    }
+
+    fn get_user_sessions_key(user_id: &i64) -> String {
+        return format!("user_sessions:{}", user_id);
+    }
+
+    fn get_tokens_valid_after_key(user_id: &i64) -> String {
+        return format!("tokens_valid_after:{}", user_id);
+    }
+
+    fn get_mfa_challenge_key(challenge_id: &String) -> String {
+        return format!("mfa_challenge:{}", challenge_id);
+    }
+
+    fn parse_session(session_id: &String, fields: HashMap<String, String>) -> Option<Session> {
+        let parse_date = |field: &str| -> Option<DateTime<Utc>> {
+            let timestamp = fields.get(field)?.parse::<i64>().ok()?;
+            return Some(Utc.timestamp(timestamp, 0));
+        };
+
+        return Some(Session {
+            id: session_id.to_owned(),
+            user_id: fields.get("user_id")?.parse::<i64>().ok()?,
+            refresh_token_id: fields.get("refresh_token_id")?.to_owned(),
+            user_agent: fields.get("user_agent").cloned(),
+            ip: fields.get("ip").cloned(),
+            created_at: parse_date("created_at")?,
+            refreshed_at: parse_date("refreshed_at"),
+        });
+    }
+}
+
+#[async_trait]
+impl SessionStorage for SessionStorageImpl {
+    async fn create_session(
+        &self,
+        user_id: &i64,
+        client: &ClientData,
+        device_info: &DeviceInfo,
+    ) -> Result<Session, Failure> {
+        let session = Session {
+            id: nanoid!(),
+            user_id: user_id.to_owned(),
+            refresh_token_id: nanoid!(),
+            user_agent: device_info.user_agent.clone(),
+            ip: device_info.ip.clone(),
+            created_at: Utc.timestamp(Utc::now().timestamp(), 0),
+            refreshed_at: None,
+        };
+        let key = SessionStorageImpl::get_session_key(&session.id);
+        let user_sessions_key = SessionStorageImpl::get_user_sessions_key(user_id);
+        let exp = self.get_refresh_token_exp(client);
+        let mut connection = self.redis_connection.clone();
+
+        let mut fields = vec![
+            ("user_id", session.user_id.to_string()),
+            ("refresh_token_id", session.refresh_token_id.clone()),
+            ("created_at", session.created_at.timestamp().to_string()),
+        ];
+        if let Some(user_agent) = &session.user_agent {
+            fields.push(("user_agent", user_agent.clone()));
+        }
+        if let Some(ip) = &session.ip {
+            fields.push(("ip", ip.clone()));
+        }
+
+        let result: Result<(), RedisError> = redis::pipe()
+            .atomic()
+            .hset_multiple(&key, &fields)
+            .ignore()
+            .expire(&key, exp as usize)
+            .ignore()
+            .sadd(&user_sessions_key, &session.id)
+            .ignore()
+            .query_async(&mut connection)
+            .await;
+        if result.is_err() {
+            return Err(get_redis_connection_error());
+        }
+
+        let result: Result<(), RedisError> = Script::new(EXTEND_EXPIRATION_SCRIPT)
+            .key(&user_sessions_key)
+            .arg(exp)
+            .invoke_async(&mut connection)
+            .await;
+
+        return match result {
+            Ok(_) => Ok(session),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        user_id: &i64,
+        session_id: &String,
+        refresh_token_id: &String,
+        client: &ClientData,
+    ) -> Result<Session, Failure> {
+        let new_refresh_token_id = nanoid!();
+        let mut connection = self.redis_connection.clone();
+
+        let result: Result<Value, RedisError> = Script::new(ROTATE_REFRESH_TOKEN_SCRIPT)
+            .key(SessionStorageImpl::get_session_key(session_id))
+            .key(SessionStorageImpl::get_user_sessions_key(user_id))
+            .arg(refresh_token_id)
+            .arg(&new_refresh_token_id)
+            .arg(self.get_refresh_token_exp(client))
+            .arg(session_id)
+            .arg(Utc::now().timestamp())
+            .invoke_async(&mut connection)
+            .await;
+
+        return match result {
+            Ok(Value::Int(0)) => Err(get_invalid_refresh_token_error()),
+            Ok(Value::Int(_)) => Err(get_refresh_token_reused_error()),
+            Ok(value) => HashMap::<String, String>::from_redis_value(&value)
+                .ok()
+                .and_then(|fields| SessionStorageImpl::parse_session(session_id, fields))
+                .ok_or(get_redis_connection_error()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn get_user_sessions(&self, user_id: &i64) -> Result<Vec<Session>, Failure> {
+        let user_sessions_key = SessionStorageImpl::get_user_sessions_key(user_id);
+        let mut connection = self.redis_connection.clone();
+
+        let session_ids: Result<Vec<String>, RedisError> =
+            connection.smembers(&user_sessions_key).await;
+        let session_ids = match session_ids {
+            Ok(session_ids) => session_ids,
+            Err(_) => return Err(get_redis_connection_error()),
+        };
+
+        let mut pipe = redis::pipe();
+        for session_id in session_ids.iter() {
+            pipe.hgetall(SessionStorageImpl::get_session_key(session_id));
+        }
+        let result: Result<Vec<HashMap<String, String>>, RedisError> =
+            pipe.query_async(&mut connection).await;
+        let fields = match result {
+            Ok(fields) => fields,
+            Err(_) => return Err(get_redis_connection_error()),
+        };
+
+        let mut sessions = Vec::new();
+        let mut expired_session_ids = Vec::new();
+        for (session_id, fields) in session_ids.iter().zip(fields) {
+            match SessionStorageImpl::parse_session(session_id, fields) {
+                Some(session) => sessions.push(session),
+                None => expired_session_ids.push(session_id),
+            }
+        }
+        if !expired_session_ids.is_empty() {
+            let _: Result<(), RedisError> = connection
+                .srem(&user_sessions_key, expired_session_ids)
+                .await;
+        }
+        sessions.sort_by_key(|session| Reverse(session.created_at));
+
+        return Ok(sessions);
+    }
+
+    async fn revoke_session(&self, user_id: &i64, session_id: &String) -> Result<bool, Failure> {
+        let mut connection = self.redis_connection.clone();
+
+        let result: Result<i64, RedisError> = Script::new(REVOKE_SESSION_SCRIPT)
+            .key(SessionStorageImpl::get_session_key(session_id))
+            .key(SessionStorageImpl::get_user_sessions_key(user_id))
+            .arg(user_id.to_owned())
+            .arg(session_id)
+            .invoke_async(&mut connection)
+            .await;
+
+        return match result {
+            Ok(revoked) => Ok(revoked == 1),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn revoke_user_sessions(&self, user_id: &i64) -> Result<(), Failure> {
+        let valid_after_exp = self.get_max_access_token_exp().await?;
+        let user_sessions_key = SessionStorageImpl::get_user_sessions_key(user_id);
+        let mut connection = self.redis_connection.clone();
+
+        let session_ids: Result<Vec<String>, RedisError> =
+            connection.smembers(&user_sessions_key).await;
+        let session_ids = match session_ids {
+            Ok(session_ids) => session_ids,
+            Err(_) => return Err(get_redis_connection_error()),
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for session_id in session_ids.iter() {
+            pipe.del(SessionStorageImpl::get_session_key(session_id))
+                .ignore()
+                .srem(&user_sessions_key, session_id)
+                .ignore();
+        }
+        // The cut-off can expire together with the longest living access token.
+        pipe.set_ex(
+            SessionStorageImpl::get_tokens_valid_after_key(user_id),
+            Utc::now().timestamp(),
+            valid_after_exp as usize,
+        )
+        .ignore();
+
+        let result: Result<(), RedisError> = pipe.query_async(&mut connection).await;
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn get_tokens_valid_after(&self, user_id: &i64) -> Result<Option<i64>, Failure> {
+        let result: Result<Option<i64>, RedisError> = self
+            .redis_connection
+            .clone()
+            .get(SessionStorageImpl::get_tokens_valid_after_key(user_id))
+            .await;
+
+        return match result {
+            Ok(timestamp) => Ok(timestamp),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn create_mfa_challenge(&self, user_id: &i64) -> Result<String, Failure> {
+        let challenge_id = nanoid!();
+
+        let result: Result<(), RedisError> = self
+            .redis_connection
+            .clone()
+            .set_ex(
+                SessionStorageImpl::get_mfa_challenge_key(&challenge_id),
+                user_id.to_owned(),
+                self.config.mfa_token_exp as usize,
+            )
+            .await;
+
+        return match result {
+            Ok(_) => Ok(challenge_id),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn take_mfa_challenge(&self, challenge_id: &String) -> Result<bool, Failure> {
+        // DEL reports whether the key existed, so only one of parallel attempts gets it.
+        let result: Result<i64, RedisError> = self
+            .redis_connection
+            .clone()
+            .del(SessionStorageImpl::get_mfa_challenge_key(challenge_id))
+            .await;
+
+        return match result {
+            Ok(deleted) => Ok(deleted == 1),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl SessionsInvalidator for SessionStorageImpl {
+    async fn invalidate_sessions(&self, user_id: &i64) -> Result<(), Failure> {
+        self.revoke_user_sessions(user_id).await
+    }
+}
@@ -1,115 +1,799 @@
 use crate::{
     common::failure::domain::failure::Failure,
-    features::auth::{
-        domain::{
-            auth_data::AuthData, current_user_data::CurrentUserData, tokens_pair::TokensPair,
-            user_role::UserRole,
+    features::{
+        auth::{
+            domain::{
+                access_token_data::AccessTokenData, auth_data::AuthData, client_data::ClientData,
+                current_user_data::CurrentUserData, device_info::DeviceInfo, grant_type::GrantType,
+                introspected_token_type::IntrospectedTokenType, login_result::LoginResult,
+                mfa_token_data::MfaTokenData, public_key::PublicKey,
+                refresh_token_data::RefreshTokenData, session::Session,
+                token_introspection::TokenIntrospection, tokens_pair::TokensPair,
+                user_role::UserRole,
+            },
+            errors::{
+                auth_errors::{
+                    get_email_not_verified_error, get_invalid_credentials_error,
+                    get_invalid_second_factor_error, get_session_not_found_error,
+                    get_too_many_login_attempts_error, get_unauthorized_client_error,
+                },
+                token_errors::{
+                    get_invalid_access_token_error, get_invalid_mfa_token_error,
+                    get_invalid_refresh_token_error,
+                },
+            },
         },
-        errors::auth_errors::{get_client_secret_error, get_invalid_credentials_error},
+        auth_event::{
+            domain::{
+                auth_event_outcome::AuthEventOutcome, auth_event_type::AuthEventType,
+                new_auth_event::NewAuthEvent,
+            },
+            interactors::auth_event_interactor::AuthEventRecorder,
+        },
+        impersonation::errors::impersonation_errors::get_not_allowed_while_impersonating_error,
+        oidc::interactors::oidc_interactor::IdentityAuthenticator,
+        personal_access_token::interactors::personal_access_token_interactor::PersonalAccessTokenRevoker,
+        profile::interactors::profile_interactor::PasswordHasher,
     },
 };
 
 use async_trait::async_trait;
 
 pub trait TokenProvider {
-    fn generate_token(&self, id: &i64, role: &UserRole) -> Result<TokensPair, Failure>;
+    fn generate_token(
+        &self,
+        id: &i64,
+        role: &UserRole,
+        session: &Session,
+        client: &ClientData,
+    ) -> Result<TokensPair, Failure>;
+
+    fn validate_access_token(&self, access_token: &String) -> Result<AccessTokenData, Failure>;
+
+    fn validate_refresh_token(&self, refresh_token: &String) -> Result<RefreshTokenData, Failure>;
+
+    fn generate_mfa_token(
+        &self,
+        id: &i64,
+        role: &UserRole,
+        client_id: &String,
+        token_id: &String,
+    ) -> Result<String, Failure>;
+
+    fn validate_mfa_token(&self, mfa_token: &String) -> Result<MfaTokenData, Failure>;
+
+    fn get_public_keys(&self) -> Vec<PublicKey>;
+}
+
+#[async_trait]
+pub trait ClientVerifier {
+    async fn verify_client(
+        &self,
+        client_id: &String,
+        client_secret: &String,
+    ) -> Result<ClientData, Failure>;
+
+    async fn is_client_active(&self, client_id: &String) -> Result<bool, Failure>;
+}
+
+// Resolves tokens that are not JWTs issued by the `TokenProvider`. Returns `None` when the
+// token is not a personal access token at all, so it can be validated as a JWT instead.
+#[async_trait]
+pub trait PersonalAccessTokenVerifier {
+    async fn verify_personal_access_token(
+        &self,
+        token: &String,
+    ) -> Result<Option<CurrentUserData>, Failure>;
+
+    // Unlike a verification this is not a use of the token. `None` when the token is not an
+    // active personal access token.
+    async fn introspect_personal_access_token(
+        &self,
+        token: &String,
+    ) -> Result<Option<TokenIntrospection>, Failure>;
+}
+
+// Counts logins per login and per IP. Unknown logins are counted as well, so the throttling
+// does not reveal which accounts exist.
+#[async_trait]
+pub trait LoginThrottle {
+    // Counted before the password is checked, so parallel attempts can not all pass. Returns
+    // the seconds until the next attempt is allowed, `None` when the attempt may go on.
+    async fn register_attempt(
+        &self,
+        login: &String,
+        ip: &Option<String>,
+    ) -> Result<Option<u64>, Failure>;
+
+    // Forgets the attempts of the login and takes the successful one back from the IP.
+    async fn reset(&self, login: &String, ip: &Option<String>) -> Result<(), Failure>;
 
-    fn validate_access_token(&self, access_token: &String) -> Result<(i64, UserRole), Failure>;
+    // Counted before the code is checked, so parallel attempts can not all pass. Returns the
+    // seconds to wait when the attempt is over the limit.
+    async fn register_second_factor_attempt(&self, user_id: &i64) -> Result<Option<u64>, Failure>;
 
-    fn validate_refresh_token(&self, refresh_token: &String) -> Result<(i64, UserRole), Failure>;
+    async fn reset_second_factor(&self, user_id: &i64) -> Result<(), Failure>;
 }
 
 pub trait AuthConfigProvider {
-    fn get_client_secret(&self) -> String;
+    fn is_verified_email_required(&self) -> bool;
 }
 
 #[async_trait]
 pub trait AuthDataRepository {
     async fn get_auth_data(&self, login: &String) -> Result<AuthData, Failure>;
+
+    async fn update_password_hash(
+        &self,
+        user_id: &i64,
+        password_hash: &String,
+    ) -> Result<(), Failure>;
+}
+
+#[async_trait]
+pub trait SessionStorage {
+    // Sessions expire together with the refresh tokens of the client.
+    async fn create_session(
+        &self,
+        user_id: &i64,
+        client: &ClientData,
+        device_info: &DeviceInfo,
+    ) -> Result<Session, Failure>;
+
+    async fn rotate_refresh_token(
+        &self,
+        user_id: &i64,
+        session_id: &String,
+        refresh_token_id: &String,
+        client: &ClientData,
+    ) -> Result<Session, Failure>;
+
+    async fn get_user_sessions(&self, user_id: &i64) -> Result<Vec<Session>, Failure>;
+
+    async fn revoke_session(&self, user_id: &i64, session_id: &String) -> Result<bool, Failure>;
+
+    async fn revoke_user_sessions(&self, user_id: &i64) -> Result<(), Failure>;
+
+    async fn get_tokens_valid_after(&self, user_id: &i64) -> Result<Option<i64>, Failure>;
+
+    // Mfa tokens name a challenge that can be taken only once, so every second factor attempt
+    // needs a new password login.
+    async fn create_mfa_challenge(&self, user_id: &i64) -> Result<String, Failure>;
+
+    async fn take_mfa_challenge(&self, challenge_id: &String) -> Result<bool, Failure>;
 }
 
 #[async_trait]
 pub trait PasswordVerifier {
     async fn verify_password(&self, password: &String, hash: &String) -> Result<bool, Failure>;
+
+    // True when the hash uses an outdated algorithm or parameters.
+    fn needs_rehash(&self, hash: &String) -> bool;
+}
+
+#[async_trait]
+pub trait SecondFactorVerifier {
+    async fn is_second_factor_enabled(&self, user_id: &i64) -> Result<bool, Failure>;
+
+    async fn verify_second_factor(&self, user_id: &i64, code: &String) -> Result<bool, Failure>;
 }
 
-pub struct AuthInteractor<T, Y, U, I> {
+pub struct AuthInteractor<T, Y, U, I, J, K, L, M, N, O> {
     password_manager: T,
     token_provider: Y,
     auth_data_repository: U,
-    auth_config_provider: I,
+    client_verifier: I,
+    session_storage: J,
+    second_factor_verifier: K,
+    personal_access_token_verifier: L,
+    login_throttle: M,
+    auth_config_provider: N,
+    auth_event_recorder: O,
 }
 
-impl<T, Y, U, I> AuthInteractor<T, Y, U, I>
+impl<T, Y, U, I, J, K, L, M, N, O> AuthInteractor<T, Y, U, I, J, K, L, M, N, O>
 where
-    T: PasswordVerifier,
+    T: PasswordVerifier + PasswordHasher,
     Y: TokenProvider,
     U: AuthDataRepository,
-    I: AuthConfigProvider,
+    I: ClientVerifier,
+    J: SessionStorage,
+    K: SecondFactorVerifier,
+    L: PersonalAccessTokenVerifier + PersonalAccessTokenRevoker,
+    M: LoginThrottle,
+    N: AuthConfigProvider,
+    O: AuthEventRecorder,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         password_manager: T,
         token_provider: Y,
         auth_data_repository: U,
-        auth_config_provider: I,
-    ) -> AuthInteractor<T, Y, U, I> {
+        client_verifier: I,
+        session_storage: J,
+        second_factor_verifier: K,
+        personal_access_token_verifier: L,
+        login_throttle: M,
+        auth_config_provider: N,
+        auth_event_recorder: O,
+    ) -> AuthInteractor<T, Y, U, I, J, K, L, M, N, O> {
         return AuthInteractor {
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         };
     }
 
-    pub async fn validate_client_secret(&self, client_secret: &String) -> Result<(), Failure> {
-        if client_secret == &self.auth_config_provider.get_client_secret() {
-            Ok(())
-        } else {
-            Err(get_client_secret_error())
+    pub async fn verify_client(
+        &self,
+        client_id: &String,
+        client_secret: &String,
+    ) -> Result<ClientData, Failure> {
+        self.client_verifier
+            .verify_client(client_id, client_secret)
+            .await
+    }
+
+    pub async fn login(
+        &self,
+        client: &ClientData,
+        login: &String,
+        password: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<LoginResult, Failure> {
+        check_grant_type(client, GrantType::Password)?;
+        let retry_after = self
+            .login_throttle
+            .register_attempt(login, &device_info.ip)
+            .await?;
+        if let Some(retry_after) = retry_after {
+            return Err(get_too_many_login_attempts_error(retry_after));
+        }
+        let profile = self
+            .verify_credentials(client, login, password, device_info)
+            .await?;
+        self.login_throttle.reset(login, &device_info.ip).await?;
+        // Checked only after the password, so the response does not leak the verification
+        // status of other people's accounts.
+        if self.auth_config_provider.is_verified_email_required() && profile.verified_at.is_none() {
+            self.record_event(
+                AuthEventType::Login,
+                AuthEventOutcome::Failure,
+                Some(profile.id),
+                Some(client.id.clone()),
+                device_info,
+            )
+            .await;
+            return Err(get_email_not_verified_error());
+        }
+
+        self.authenticate(client, &profile, device_info).await
+    }
+
+    // Finishes a login of an already identified user, asking for the second factor if the user
+    // has it enabled.
+    async fn authenticate(
+        &self,
+        client: &ClientData,
+        profile: &AuthData,
+        device_info: &DeviceInfo,
+    ) -> Result<LoginResult, Failure> {
+        let is_second_factor_enabled = self
+            .second_factor_verifier
+            .is_second_factor_enabled(&profile.id)
+            .await?;
+        if is_second_factor_enabled {
+            let challenge_id = self
+                .session_storage
+                .create_mfa_challenge(&profile.id)
+                .await?;
+            let mfa_token = self.token_provider.generate_mfa_token(
+                &profile.id,
+                &profile.user_role,
+                &client.id,
+                &challenge_id,
+            )?;
+
+            return Ok(LoginResult::MfaRequired(mfa_token));
         }
+
+        let tokens = self
+            .create_session(client, &profile.id, &profile.user_role, device_info)
+            .await?;
+
+        Ok(LoginResult::Authenticated(tokens))
     }
 
-    pub async fn login(&self, login: &String, password: &String) -> Result<TokensPair, Failure> {
-        let profile = self.auth_data_repository.get_auth_data(&login).await?;
+    async fn verify_credentials(
+        &self,
+        client: &ClientData,
+        login: &String,
+        password: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<AuthData, Failure> {
+        let profile = match self.auth_data_repository.get_auth_data(login).await {
+            Err(failure) if failure == get_invalid_credentials_error() => {
+                self.record_event(
+                    AuthEventType::Login,
+                    AuthEventOutcome::Failure,
+                    None,
+                    Some(client.id.clone()),
+                    device_info,
+                )
+                .await;
+                return Err(failure);
+            }
+            result => result?,
+        };
         let is_password_valid = self
             .password_manager
-            .verify_password(&password, &profile.password)
+            .verify_password(password, &profile.password)
+            .await?;
+        if !is_password_valid {
+            self.record_event(
+                AuthEventType::Login,
+                AuthEventOutcome::Failure,
+                Some(profile.id),
+                Some(client.id.clone()),
+                device_info,
+            )
+            .await;
+            return Err(get_invalid_credentials_error());
+        }
+        if self.password_manager.needs_rehash(&profile.password) {
+            // A failed rehash is retried on the next login, so it must not fail this one.
+            let _ = self.rehash_password(&profile.id, password).await;
+        }
+
+        Ok(profile)
+    }
+
+    async fn rehash_password(&self, user_id: &i64, password: &String) -> Result<(), Failure> {
+        let password_hash = self.password_manager.hash_password(password).await?;
+
+        self.auth_data_repository
+            .update_password_hash(user_id, &password_hash)
+            .await
+    }
+
+    pub async fn login_with_second_factor(
+        &self,
+        client: &ClientData,
+        mfa_token: &String,
+        code: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<TokensPair, Failure> {
+        check_grant_type(client, GrantType::Password)?;
+        let token_data = self.token_provider.validate_mfa_token(mfa_token)?;
+        if token_data.client_id != client.id {
+            return Err(get_invalid_mfa_token_error());
+        }
+        let is_challenge_open = self
+            .session_storage
+            .take_mfa_challenge(&token_data.token_id)
+            .await?;
+        if !is_challenge_open {
+            return Err(get_invalid_mfa_token_error());
+        }
+        let retry_after = self
+            .login_throttle
+            .register_second_factor_attempt(&token_data.user_id)
+            .await?;
+        if let Some(retry_after) = retry_after {
+            return Err(get_too_many_login_attempts_error(retry_after));
+        }
+        let is_code_valid = self
+            .second_factor_verifier
+            .verify_second_factor(&token_data.user_id, code)
+            .await?;
+        if !is_code_valid {
+            self.record_event(
+                AuthEventType::Login,
+                AuthEventOutcome::Failure,
+                Some(token_data.user_id),
+                Some(client.id.clone()),
+                device_info,
+            )
+            .await;
+            return Err(get_invalid_second_factor_error());
+        }
+        self.login_throttle
+            .reset_second_factor(&token_data.user_id)
+            .await?;
+
+        self.create_session(client, &token_data.user_id, &token_data.role, device_info)
+            .await
+    }
+
+    pub async fn refresh(
+        &self,
+        client: &ClientData,
+        refresh_token: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<TokensPair, Failure> {
+        check_grant_type(client, GrantType::RefreshToken)?;
+        let token_data = self.token_provider.validate_refresh_token(refresh_token)?;
+        if token_data.client_id != client.id {
+            return Err(get_invalid_refresh_token_error());
+        }
+        let session = self
+            .session_storage
+            .rotate_refresh_token(
+                &token_data.user_id,
+                &token_data.session_id,
+                &token_data.token_id,
+                client,
+            )
+            .await;
+        let outcome = match session {
+            Ok(_) => AuthEventOutcome::Success,
+            // Includes reuse of an already rotated refresh token.
+            Err(_) => AuthEventOutcome::Failure,
+        };
+        self.record_event(
+            AuthEventType::Refresh,
+            outcome,
+            Some(token_data.user_id),
+            Some(client.id.clone()),
+            device_info,
+        )
+        .await;
+
+        let tokens = self.token_provider.generate_token(
+            &token_data.user_id,
+            &token_data.role,
+            &session?,
+            client,
+        )?;
+
+        Ok(tokens)
+    }
+
+    pub async fn logout(
+        &self,
+        client: &ClientData,
+        refresh_token: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
+        let token_data = self.token_provider.validate_refresh_token(refresh_token)?;
+        if token_data.client_id != client.id {
+            return Err(get_invalid_refresh_token_error());
+        }
+
+        self.session_storage
+            .revoke_session(&token_data.user_id, &token_data.session_id)
+            .await?;
+        self.record_event(
+            AuthEventType::Logout,
+            AuthEventOutcome::Success,
+            Some(token_data.user_id),
+            Some(client.id.clone()),
+            device_info,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    // Follows RFC 7662, tokens that can not be used are reported as inactive with `None`
+    // instead of failing.
+    pub async fn introspect(&self, token: &String) -> Result<Option<TokenIntrospection>, Failure> {
+        let personal_access_token = self
+            .personal_access_token_verifier
+            .introspect_personal_access_token(token)
+            .await?;
+        if personal_access_token.is_some() {
+            return Ok(personal_access_token);
+        }
+        if let Ok(token_data) = self.token_provider.validate_access_token(token) {
+            if !self.is_access_token_active(&token_data).await? {
+                return Ok(None);
+            }
+            return Ok(Some(TokenIntrospection {
+                user_id: token_data.user_id,
+                role: token_data.role,
+                token_type: IntrospectedTokenType::Access,
+                client_id: Some(token_data.client_id),
+                scopes: token_data.scopes,
+                actor_id: token_data.actor_id,
+                issued_at: Some(token_data.issued_at),
+                expires_at: Some(token_data.expires_at),
+            }));
+        }
+        if let Ok(token_data) = self.token_provider.validate_refresh_token(token) {
+            if !self.is_refresh_token_active(&token_data).await? {
+                return Ok(None);
+            }
+            return Ok(Some(TokenIntrospection {
+                user_id: token_data.user_id,
+                role: token_data.role,
+                token_type: IntrospectedTokenType::Refresh,
+                client_id: Some(token_data.client_id),
+                scopes: vec![],
+                actor_id: None,
+                issued_at: Some(token_data.issued_at),
+                expires_at: Some(token_data.expires_at),
+            }));
+        }
+
+        Ok(None)
+    }
+
+    // Follows RFC 7009, invalid tokens and tokens of other clients are ignored. Access tokens
+    // revoke the session they belong to, the access token itself stays valid until it expires.
+    pub async fn revoke(
+        &self,
+        client: &ClientData,
+        token: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
+        let session = match self.token_provider.validate_refresh_token(token) {
+            Ok(token_data) => Some((
+                token_data.user_id,
+                token_data.session_id,
+                token_data.client_id,
+            )),
+            Err(_) => match self.token_provider.validate_access_token(token) {
+                // Impersonation tokens have no session to revoke.
+                Ok(AccessTokenData {
+                    user_id,
+                    session_id: Some(session_id),
+                    client_id,
+                    ..
+                }) => Some((user_id, session_id, client_id)),
+                _ => None,
+            },
+        };
+        let (user_id, session_id) = match session {
+            Some((user_id, session_id, client_id)) if client_id == client.id => {
+                (user_id, session_id)
+            }
+            _ => return Ok(()),
+        };
+
+        let revoked = self
+            .session_storage
+            .revoke_session(&user_id, &session_id)
+            .await?;
+        if revoked {
+            self.record_event(
+                AuthEventType::Logout,
+                AuthEventOutcome::Success,
+                Some(user_id),
+                Some(client.id.clone()),
+                device_info,
+            )
+            .await;
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_sessions(&self, user_id: &i64) -> Result<Vec<Session>, Failure> {
+        self.session_storage.get_user_sessions(user_id).await
+    }
+
+    pub async fn revoke_session(
+        &self,
+        current_user: &CurrentUserData,
+        session_id: &String,
+    ) -> Result<(), Failure> {
+        // Would let an admin sign the user out of their devices.
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        let revoked = self
+            .session_storage
+            .revoke_session(&current_user.id, session_id)
             .await?;
-        if is_password_valid {
-            let tokens = self
-                .token_provider
-                .generate_token(&profile.id, &profile.user_role)?;
 
-            Ok(tokens)
+        if revoked {
+            Ok(())
         } else {
-            Err(get_invalid_credentials_error())
+            Err(get_session_not_found_error())
         }
     }
 
-    pub async fn refresh(&self, refresh_token: &String) -> Result<TokensPair, Failure> {
-        let (id, role) = self.token_provider.validate_refresh_token(&refresh_token)?;
+    pub async fn logout_all(
+        &self,
+        current_user: &CurrentUserData,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
+        // Would sign the user out of everything on an admin's behalf.
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        self.session_storage
+            .revoke_user_sessions(&current_user.id)
+            .await?;
+        self.personal_access_token_verifier
+            .revoke_user_tokens(&current_user.id)
+            .await?;
+        self.record_event(
+            AuthEventType::Logout,
+            AuthEventOutcome::Success,
+            Some(current_user.id),
+            current_user.client_id.clone(),
+            device_info,
+        )
+        .await;
+
+        Ok(())
+    }
 
-        let tokens = self.token_provider.generate_token(&id, &role)?;
+    async fn create_session(
+        &self,
+        client: &ClientData,
+        user_id: &i64,
+        role: &UserRole,
+        device_info: &DeviceInfo,
+    ) -> Result<TokensPair, Failure> {
+        let session = self
+            .session_storage
+            .create_session(user_id, client, device_info)
+            .await?;
+        let tokens = self
+            .token_provider
+            .generate_token(user_id, role, &session, client)?;
+        self.record_event(
+            AuthEventType::Login,
+            AuthEventOutcome::Success,
+            Some(*user_id),
+            Some(client.id.clone()),
+            device_info,
+        )
+        .await;
 
         Ok(tokens)
     }
 
+    async fn is_access_token_active(&self, token_data: &AccessTokenData) -> Result<bool, Failure> {
+        // Revoking a client invalidates its access tokens right away instead of at expiry.
+        let is_client_active = self
+            .client_verifier
+            .is_client_active(&token_data.client_id)
+            .await?;
+        if !is_client_active {
+            return Ok(false);
+        }
+        let valid_after = self
+            .session_storage
+            .get_tokens_valid_after(&token_data.user_id)
+            .await?;
+
+        // `iat` only has whole seconds, so a token issued in the same second as the cut-off may
+        // predate it. It is rejected too, a client that just logged in again has to refresh.
+        Ok(!matches!(valid_after, Some(valid_after) if token_data.issued_at <= valid_after))
+    }
+
+    // Only the latest refresh token of a session can be used, older ones were already rotated.
+    async fn is_refresh_token_active(
+        &self,
+        token_data: &RefreshTokenData,
+    ) -> Result<bool, Failure> {
+        let is_client_active = self
+            .client_verifier
+            .is_client_active(&token_data.client_id)
+            .await?;
+        if !is_client_active {
+            return Ok(false);
+        }
+        let sessions = self
+            .session_storage
+            .get_user_sessions(&token_data.user_id)
+            .await?;
+
+        Ok(sessions.iter().any(|session| {
+            session.id == token_data.session_id && session.refresh_token_id == token_data.token_id
+        }))
+    }
+
+    async fn record_event(
+        &self,
+        event_type: AuthEventType,
+        outcome: AuthEventOutcome,
+        user_id: Option<i64>,
+        client_id: Option<String>,
+        device_info: &DeviceInfo,
+    ) {
+        let event = NewAuthEvent::new(event_type, outcome, user_id, client_id, device_info);
+        let _ = self.auth_event_recorder.record_event(&event).await;
+    }
+
+    pub fn get_public_keys(&self) -> Vec<PublicKey> {
+        self.token_provider.get_public_keys()
+    }
+
     pub async fn validate_access_token(
         &self,
         access_token: &String,
     ) -> Result<CurrentUserData, Failure> {
-        let (id, role) = self.token_provider.validate_access_token(access_token)?;
-        return Ok(CurrentUserData { id, role });
+        let personal_access_token = self
+            .personal_access_token_verifier
+            .verify_personal_access_token(access_token)
+            .await?;
+        if let Some(current_user) = personal_access_token {
+            return Ok(current_user);
+        }
+        let token_data = self.token_provider.validate_access_token(access_token)?;
+        if !self.is_access_token_active(&token_data).await? {
+            return Err(get_invalid_access_token_error());
+        }
+
+        return Ok(CurrentUserData {
+            id: token_data.user_id,
+            role: token_data.role,
+            session_id: token_data.session_id,
+            actor_id: token_data.actor_id,
+            client_id: Some(token_data.client_id),
+            scopes: token_data.scopes,
+        });
+    }
+}
+
+#[async_trait]
+impl<T, Y, U, I, J, K, L, M, N, O> IdentityAuthenticator
+    for AuthInteractor<T, Y, U, I, J, K, L, M, N, O>
+where
+    T: PasswordVerifier + PasswordHasher + Send + Sync,
+    Y: TokenProvider + Send + Sync,
+    U: AuthDataRepository + Send + Sync,
+    I: ClientVerifier + Send + Sync,
+    J: SessionStorage + Send + Sync,
+    K: SecondFactorVerifier + Send + Sync,
+    L: PersonalAccessTokenVerifier + PersonalAccessTokenRevoker + Send + Sync,
+    M: LoginThrottle + Send + Sync,
+    N: AuthConfigProvider + Send + Sync,
+    O: AuthEventRecorder + Send + Sync,
+{
+    // Logins through an identity provider replace the password, so they are allowed for the
+    // same clients.
+    async fn login_with_identity(
+        &self,
+        client: &ClientData,
+        user: &AuthData,
+        device_info: &DeviceInfo,
+    ) -> Result<LoginResult, Failure> {
+        check_grant_type(client, GrantType::Password)?;
+
+        self.authenticate(client, user, device_info).await
+    }
+}
+
+fn check_grant_type(client: &ClientData, grant_type: GrantType) -> Result<(), Failure> {
+    if client.grant_types.contains(&grant_type) {
+        Ok(())
+    } else {
+        Err(get_unauthorized_client_error())
     }
 }
 
 #[cfg(test)]
 mod test {
     use async_trait::async_trait;
-    use chrono::Utc;
+    use chrono::{TimeZone, Utc};
     use mockall::{mock, predicate};
 
-    use crate::common::failure::domain::failure::FailureType;
+    use crate::{
+        common::failure::domain::failure::FailureType,
+        features::{
+            auth::{
+                domain::scope::Scope,
+                errors::{
+                    auth_errors::get_db_connection_error,
+                    token_errors::get_refresh_token_reused_error,
+                },
+            },
+            client::errors::client_errors::get_invalid_client_error,
+        },
+    };
 
     use super::*;
 
@@ -119,6 +803,13 @@ mod test {
         #[async_trait]
         impl PasswordVerifier for PasswordManager {
             async fn verify_password(&self, password: &String, hash: &String) -> Result<bool, Failure>;
+
+            fn needs_rehash(&self, hash: &String) -> bool;
+        }
+
+        #[async_trait]
+        impl PasswordHasher for PasswordManager {
+            async fn hash_password(&self, password: &String) -> Result<String, Failure>;
         }
     }
 
@@ -126,11 +817,29 @@ mod test {
         TokenProvider {}
 
         impl TokenProvider for TokenProvider {
-            fn generate_token(&self, id: &i64, role: &UserRole) -> Result<TokensPair, Failure>;
+            fn generate_token(
+                &self,
+                id: &i64,
+                role: &UserRole,
+                session: &Session,
+                client: &ClientData,
+            ) -> Result<TokensPair, Failure>;
 
-            fn validate_access_token(&self, access_token: &String) -> Result<(i64, UserRole), Failure>;
+            fn validate_access_token(&self, access_token: &String) -> Result<AccessTokenData, Failure>;
 
-            fn validate_refresh_token(&self, refresh_token: &String) -> Result<(i64, UserRole), Failure>;
+            fn validate_refresh_token(&self, refresh_token: &String) -> Result<RefreshTokenData, Failure>;
+
+            fn generate_mfa_token(
+                &self,
+                id: &i64,
+                role: &UserRole,
+                client_id: &String,
+                token_id: &String,
+            ) -> Result<String, Failure>;
+
+            fn validate_mfa_token(&self, mfa_token: &String) -> Result<MfaTokenData, Failure>;
+
+            fn get_public_keys(&self) -> Vec<PublicKey>;
         }
     }
 
@@ -140,6 +849,110 @@ mod test {
         #[async_trait]
         impl AuthDataRepository for AuthDataRepository {
             async fn get_auth_data(&self, login: &String) -> Result<AuthData, Failure>;
+
+            async fn update_password_hash(&self, user_id: &i64, password_hash: &String)
+                -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        ClientVerifier {}
+
+        #[async_trait]
+        impl ClientVerifier for ClientVerifier {
+            async fn verify_client(
+                &self,
+                client_id: &String,
+                client_secret: &String,
+            ) -> Result<ClientData, Failure>;
+
+            async fn is_client_active(&self, client_id: &String) -> Result<bool, Failure>;
+        }
+    }
+
+    mock! {
+        SessionStorage {}
+
+        #[async_trait]
+        impl SessionStorage for SessionStorage {
+            async fn create_session(
+                &self,
+                user_id: &i64,
+                client: &ClientData,
+                device_info: &DeviceInfo,
+            ) -> Result<Session, Failure>;
+
+            async fn rotate_refresh_token(
+                &self,
+                user_id: &i64,
+                session_id: &String,
+                refresh_token_id: &String,
+                client: &ClientData,
+            ) -> Result<Session, Failure>;
+
+            async fn get_user_sessions(&self, user_id: &i64) -> Result<Vec<Session>, Failure>;
+
+            async fn revoke_session(&self, user_id: &i64, session_id: &String) -> Result<bool, Failure>;
+
+            async fn revoke_user_sessions(&self, user_id: &i64) -> Result<(), Failure>;
+
+            async fn get_tokens_valid_after(&self, user_id: &i64) -> Result<Option<i64>, Failure>;
+
+            async fn create_mfa_challenge(&self, user_id: &i64) -> Result<String, Failure>;
+
+            async fn take_mfa_challenge(&self, challenge_id: &String) -> Result<bool, Failure>;
+        }
+    }
+
+    mock! {
+        SecondFactorVerifier {}
+
+        #[async_trait]
+        impl SecondFactorVerifier for SecondFactorVerifier {
+            async fn is_second_factor_enabled(&self, user_id: &i64) -> Result<bool, Failure>;
+
+            async fn verify_second_factor(&self, user_id: &i64, code: &String) -> Result<bool, Failure>;
+        }
+    }
+
+    mock! {
+        PersonalAccessTokenVerifier {}
+
+        #[async_trait]
+        impl PersonalAccessTokenVerifier for PersonalAccessTokenVerifier {
+            async fn verify_personal_access_token(
+                &self,
+                token: &String,
+            ) -> Result<Option<CurrentUserData>, Failure>;
+
+            async fn introspect_personal_access_token(
+                &self,
+                token: &String,
+            ) -> Result<Option<TokenIntrospection>, Failure>;
+        }
+
+        #[async_trait]
+        impl PersonalAccessTokenRevoker for PersonalAccessTokenVerifier {
+            async fn revoke_user_tokens(&self, user_id: &i64) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        LoginThrottle {}
+
+        #[async_trait]
+        impl LoginThrottle for LoginThrottle {
+            async fn register_attempt(
+                &self,
+                login: &String,
+                ip: &Option<String>,
+            ) -> Result<Option<u64>, Failure>;
+
+            async fn reset(&self, login: &String, ip: &Option<String>) -> Result<(), Failure>;
+
+            async fn register_second_factor_attempt(&self, user_id: &i64) -> Result<Option<u64>, Failure>;
+
+            async fn reset_second_factor(&self, user_id: &i64) -> Result<(), Failure>;
         }
     }
 
@@ -147,54 +960,377 @@ mod test {
         AuthConfigProvider {}
 
         impl AuthConfigProvider for AuthConfigProvider {
-            fn get_client_secret(&self) -> String;
+            fn is_verified_email_required(&self) -> bool;
         }
     }
 
-    #[actix_rt::test]
-    async fn should_return_error_if_client_secret_invalid() {
-        let password_manager = MockPasswordManager::new();
-        let auth_data_repository = MockAuthDataRepository::new();
-        let mut auth_config_provider = MockAuthConfigProvider::new();
-        let token_provider = MockTokenProvider::new();
-        auth_config_provider
-            .expect_get_client_secret()
-            .return_once(|| "test_client_secret".to_string());
-        let interactor = AuthInteractor::new(
-            password_manager,
-            token_provider,
-            auth_data_repository,
-            auth_config_provider,
-        );
-
-        let result = interactor
-            .validate_client_secret(&"invalid_client_secret".to_string())
-            .await;
+    mock! {
+        AuthEventRecorder {}
 
-        assert_eq!(result, Err(get_client_secret_error()));
+        #[async_trait]
+        impl AuthEventRecorder for AuthEventRecorder {
+            async fn record_event(&self, event: &NewAuthEvent) -> Result<(), Failure>;
+        }
     }
 
-    #[actix_rt::test]
-    async fn should_return_ok_when_secret_is_valid() {
-        let password_manager = MockPasswordManager::new();
+    fn get_dependencies() -> (
+        MockPasswordManager,
+        MockTokenProvider,
+        MockAuthDataRepository,
+        MockClientVerifier,
+        MockSessionStorage,
+        MockSecondFactorVerifier,
+        MockPersonalAccessTokenVerifier,
+        MockLoginThrottle,
+        MockAuthConfigProvider,
+        MockAuthEventRecorder,
+    ) {
+        let mut password_manager = MockPasswordManager::new();
+        password_manager.expect_needs_rehash().returning(|_| false);
+        let token_provider = MockTokenProvider::new();
         let auth_data_repository = MockAuthDataRepository::new();
+        let client_verifier = MockClientVerifier::new();
+        let session_storage = MockSessionStorage::new();
+        let second_factor_verifier = MockSecondFactorVerifier::new();
+        let mut personal_access_token_verifier = MockPersonalAccessTokenVerifier::new();
+        personal_access_token_verifier
+            .expect_verify_personal_access_token()
+            .returning(|_| Ok(None));
+        personal_access_token_verifier
+            .expect_introspect_personal_access_token()
+            .returning(|_| Ok(None));
+        let mut login_throttle = MockLoginThrottle::new();
+        login_throttle
+            .expect_register_attempt()
+            .returning(|_, __| Ok(None));
+        login_throttle.expect_reset().returning(|_, __| Ok(()));
+        login_throttle
+            .expect_register_second_factor_attempt()
+            .returning(|_| Ok(None));
+        login_throttle
+            .expect_reset_second_factor()
+            .returning(|_| Ok(()));
         let mut auth_config_provider = MockAuthConfigProvider::new();
-        let token_provider = MockTokenProvider::new();
         auth_config_provider
-            .expect_get_client_secret()
-            .return_once(|| "test_client_secret".to_string());
-        let interactor = AuthInteractor::new(
+            .expect_is_verified_email_required()
+            .returning(|| false);
+        let mut auth_event_recorder = MockAuthEventRecorder::new();
+        auth_event_recorder
+            .expect_record_event()
+            .returning(|_| Ok(()));
+
+        return (
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
+    }
 
-        let result = interactor
-            .validate_client_secret(&"test_client_secret".to_string())
-            .await;
-
-        assert_eq!(result, Ok(()));
+    fn get_session() -> Session {
+        return Session {
+            id: "session_id".to_string(),
+            user_id: 1,
+            refresh_token_id: "refresh_token_id".to_string(),
+            user_agent: Some("user_agent".to_string()),
+            ip: Some("127.0.0.1".to_string()),
+            created_at: Utc.timestamp(100, 0),
+            refreshed_at: None,
+        };
+    }
+
+    fn get_client() -> ClientData {
+        return ClientData {
+            id: "client_id".to_string(),
+            grant_types: vec![GrantType::Password, GrantType::RefreshToken],
+            scopes: vec![Scope::ProfileRead],
+            access_token_exp: None,
+            refresh_token_exp: None,
+        };
+    }
+
+    fn get_device_info() -> DeviceInfo {
+        return DeviceInfo {
+            user_agent: Some("user_agent".to_string()),
+            ip: Some("127.0.0.1".to_string()),
+        };
+    }
+
+    fn get_current_user(actor_id: Option<i64>) -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role: UserRole::USER,
+            session_id: Some("session_id".to_string()),
+            actor_id,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![Scope::ProfileWrite],
+        };
+    }
+
+    fn get_access_token_data() -> AccessTokenData {
+        return AccessTokenData {
+            user_id: 1,
+            role: UserRole::USER,
+            session_id: Some("session_id".to_string()),
+            actor_id: None,
+            client_id: "client_id".to_string(),
+            scopes: vec![Scope::ProfileRead],
+            issued_at: 100,
+            expires_at: 160,
+        };
+    }
+
+    fn get_refresh_token_data() -> RefreshTokenData {
+        return RefreshTokenData {
+            user_id: 1,
+            role: UserRole::USER,
+            session_id: "session_id".to_string(),
+            client_id: "client_id".to_string(),
+            token_id: "old_refresh_token_id".to_string(),
+            issued_at: 100,
+            expires_at: 220,
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_client_is_invalid() {
+        let (
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        client_verifier
+            .expect_verify_client()
+            .with(
+                predicate::eq("client_id".to_string()),
+                predicate::eq("invalid_client_secret".to_string()),
+            )
+            .return_once(|_, __| Err(get_invalid_client_error()));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .verify_client(
+                &"client_id".to_string(),
+                &"invalid_client_secret".to_string(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_client_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_login_with_client_without_password_grant() {
+        let (
+            password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository.expect_get_auth_data().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+        let client = ClientData {
+            grant_types: vec![GrantType::RefreshToken],
+            ..get_client()
+        };
+
+        let result = interactor
+            .login(
+                &client,
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_unauthorized_client_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_require_second_factor_for_identity_login() {
+        let user = AuthData {
+            email: "email".to_string(),
+            password: "".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(true));
+        token_provider
+            .expect_generate_mfa_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq(UserRole::USER),
+                predicate::eq("client_id".to_string()),
+                predicate::eq("challenge_id".to_string()),
+            )
+            .return_once(|_, __, ___, ____| Ok("mfa_token".to_string()));
+        session_storage
+            .expect_create_mfa_challenge()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok("challenge_id".to_string()));
+        session_storage.expect_create_session().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login_with_identity(&get_client(), &user, &get_device_info())
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(LoginResult::MfaRequired("mfa_token".to_string()))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_not_refresh_token_issued_to_other_client() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage.expect_rotate_refresh_token().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+        let client = ClientData {
+            id: "other_client_id".to_string(),
+            ..get_client()
+        };
+
+        let result = interactor
+            .refresh(&client, &"refresh_token".to_string(), &get_device_info())
+            .await;
+
+        assert_eq!(result, Err(get_invalid_refresh_token_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_access_token_of_revoked_client() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_access_token()
+            .return_once(|_| Ok(get_access_token_data()));
+        client_verifier
+            .expect_is_client_active()
+            .with(predicate::eq("client_id".to_string()))
+            .return_once(|_| Ok(false));
+        session_storage.expect_get_tokens_valid_after().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .validate_access_token(&"access_token".to_string())
+            .await;
+
+        assert_eq!(result, Err(get_invalid_access_token_error()));
     }
 
     #[actix_rt::test]
@@ -207,10 +1343,18 @@ mod test {
         };
         let failure_clone = failure.clone();
 
-        let password_manager = MockPasswordManager::new();
-        let mut auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let token_provider = MockTokenProvider::new();
+        let (
+            password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
         auth_data_repository
             .expect_get_auth_data()
             .with(predicate::eq("login".to_string()))
@@ -219,330 +1363,2301 @@ mod test {
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_fail_to_verify_password() {
+        let failure = Failure {
+            args: None,
+            code: "Test".to_string(),
+            message: "Test".to_string(),
+            error_type: FailureType::Authentication,
+        };
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let failure_clone = failure.clone();
+
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .with(predicate::eq("login".to_string()))
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .with(
+                predicate::eq("password".to_string()),
+                predicate::eq("hash".to_string()),
+            )
+            .return_once(move |_, __| Err(failure_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_password_invalid() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .with(predicate::eq("login".to_string()))
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .with(
+                predicate::eq("password".to_string()),
+                predicate::eq("hash".to_string()),
+            )
+            .return_once(move |_, __| Ok(false));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_credentials_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_record_failed_login_without_user_if_login_is_unknown() {
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            _,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .return_once(|_| Err(get_invalid_credentials_error()));
+        password_manager.expect_verify_password().never();
+        let mut auth_event_recorder = MockAuthEventRecorder::new();
+        auth_event_recorder
+            .expect_record_event()
+            .with(predicate::eq(NewAuthEvent::new(
+                AuthEventType::Login,
+                AuthEventOutcome::Failure,
+                None,
+                Some(get_client().id),
+                &get_device_info(),
+            )))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"unknown".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_credentials_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_login_unverified_user_when_verification_required() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: None,
+        };
+
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            _,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .return_once(move |_, __| Ok(true));
+        session_storage.expect_create_session().never();
+        let mut auth_config_provider = MockAuthConfigProvider::new();
+        auth_config_provider
+            .expect_is_verified_email_required()
+            .return_once(|| true);
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_email_not_verified_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_login_unverified_user_when_verification_not_required() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: None,
+        };
+
+        let (
+            mut password_manager,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .return_once(move |_, __| Ok(true));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .return_once(|_| Ok(false));
+        session_storage
+            .expect_create_session()
+            .return_once(|_, __, ___| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .return_once(|_, __, ___, ____| {
+                Ok(TokensPair {
+                    access_token: "access_token".to_string(),
+                    refresh_token: "refresh_token".to_string(),
+                    access_token_exp: 60,
+                    refresh_token_exp: 120,
+                })
+            });
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert!(matches!(result, Ok(LoginResult::Authenticated(_))));
+    }
+
+    #[actix_rt::test]
+    async fn should_count_login_attempt_before_checking_password() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            _,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .return_once(move |_, __| Ok(false));
+        let mut login_throttle = MockLoginThrottle::new();
+        login_throttle
+            .expect_register_attempt()
+            .with(
+                predicate::eq("login".to_string()),
+                predicate::eq(Some("127.0.0.1".to_string())),
+            )
+            .times(1)
+            .return_once(|_, __| Ok(None));
+        login_throttle.expect_reset().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_credentials_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_login_while_throttled() {
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            _,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository.expect_get_auth_data().never();
+        password_manager.expect_verify_password().never();
+        let mut login_throttle = MockLoginThrottle::new();
+        login_throttle
+            .expect_register_attempt()
+            .with(
+                predicate::eq("login".to_string()),
+                predicate::eq(Some("127.0.0.1".to_string())),
+            )
+            .return_once(|_, __| Ok(Some(30)));
+        login_throttle.expect_reset().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        let failure = result.err().unwrap();
+        assert_eq!(failure, get_too_many_login_attempts_error(30));
+        assert_eq!(failure.args.unwrap()["retry_after"], "30");
+    }
+
+    #[actix_rt::test]
+    async fn should_reset_login_throttle_after_valid_password() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+
+        let (
+            mut password_manager,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            _,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .return_once(move |_, __| Ok(true));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .return_once(|_| Ok(true));
+        session_storage
+            .expect_create_mfa_challenge()
+            .return_once(|_| Ok("challenge_id".to_string()));
+        token_provider
+            .expect_generate_mfa_token()
+            .return_once(|_, __, ___, ____| Ok("mfa_token".to_string()));
+        let mut login_throttle = MockLoginThrottle::new();
+        login_throttle
+            .expect_register_attempt()
+            .return_once(|_, __| Ok(None));
+        login_throttle
+            .expect_reset()
+            .with(
+                predicate::eq("login".to_string()),
+                predicate::eq(Some("127.0.0.1".to_string())),
+            )
+            .times(1)
+            .return_once(|_, __| Ok(()));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(LoginResult::MfaRequired("mfa_token".to_string()))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_fail_to_generate_token() {
+        let failure = Failure {
+            args: None,
+            code: "Test".to_string(),
+            message: "Test".to_string(),
+            error_type: FailureType::Authentication,
+        };
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let failure_clone = failure.clone();
+
+        let (
+            mut password_manager,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .with(predicate::eq("login".to_string()))
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .with(
+                predicate::eq("password".to_string()),
+                predicate::eq("hash".to_string()),
+            )
+            .return_once(move |_, __| Ok(true));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(false));
+        session_storage
+            .expect_create_session()
+            .with(
+                predicate::eq(1),
+                predicate::eq(get_client()),
+                predicate::eq(get_device_info()),
+            )
+            .return_once(|_, __, ___| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq(UserRole::USER),
+                predicate::eq(get_session()),
+                predicate::eq(get_client()),
+            )
+            .return_once(move |_, __, ___, ____| Err(failure_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_fail_to_create_session() {
+        let failure = Failure {
+            args: None,
+            code: "Test".to_string(),
+            message: "Test".to_string(),
+            error_type: FailureType::Unknown,
+        };
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let failure_clone = failure.clone();
+
+        let (
+            mut password_manager,
+            token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .with(predicate::eq("login".to_string()))
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .with(
+                predicate::eq("password".to_string()),
+                predicate::eq("hash".to_string()),
+            )
+            .return_once(move |_, __| Ok(true));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(false));
+        session_storage
+            .expect_create_session()
+            .with(
+                predicate::eq(1),
+                predicate::eq(get_client()),
+                predicate::eq(get_device_info()),
+            )
+            .return_once(move |_, __, ___| Err(failure_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_return_tokens_pair() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let tokens_pair = TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
+        };
+        let tokens_pair_clone = tokens_pair.clone();
+
+        let (
+            mut password_manager,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        auth_data_repository
+            .expect_get_auth_data()
+            .with(predicate::eq("login".to_string()))
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .with(
+                predicate::eq("password".to_string()),
+                predicate::eq("hash".to_string()),
+            )
+            .return_once(move |_, __| Ok(true));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(false));
+        session_storage
+            .expect_create_session()
+            .with(
+                predicate::eq(1),
+                predicate::eq(get_client()),
+                predicate::eq(get_device_info()),
+            )
+            .return_once(|_, __, ___| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq(UserRole::USER),
+                predicate::eq(get_session()),
+                predicate::eq(get_client()),
+            )
+            .return_once(move |_, __, ___, ____| Ok(tokens_pair_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(LoginResult::Authenticated(tokens_pair)));
+    }
+
+    #[actix_rt::test]
+    async fn should_rehash_outdated_password_hash_on_login() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "old_hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let tokens_pair = TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
+        };
+        let tokens_pair_clone = tokens_pair.clone();
+
+        let (
+            _,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        let mut password_manager = MockPasswordManager::new();
+        auth_data_repository
+            .expect_get_auth_data()
+            .with(predicate::eq("login".to_string()))
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .with(
+                predicate::eq("password".to_string()),
+                predicate::eq("old_hash".to_string()),
+            )
+            .return_once(move |_, __| Ok(true));
+        password_manager
+            .expect_needs_rehash()
+            .with(predicate::eq("old_hash".to_string()))
+            .return_once(|_| true);
+        password_manager
+            .expect_hash_password()
+            .with(predicate::eq("password".to_string()))
+            .return_once(|_| Ok("new_hash".to_string()));
+        auth_data_repository
+            .expect_update_password_hash()
+            .with(predicate::eq(1), predicate::eq("new_hash".to_string()))
+            .times(1)
+            .return_once(|_, __| Ok(()));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .return_once(|_| Ok(false));
+        session_storage
+            .expect_create_session()
+            .return_once(|_, __, ___| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .return_once(move |_, __, ___, ____| Ok(tokens_pair_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(LoginResult::Authenticated(tokens_pair)));
+    }
+
+    #[actix_rt::test]
+    async fn should_login_if_rehash_fails() {
+        let profile = AuthData {
+            email: "email".to_string(),
+            password: "old_hash".to_string(),
+            user_role: UserRole::USER,
+            username: "username".to_string(),
+            id: 1,
+            verified_at: Some(Utc::now()),
+        };
+        let tokens_pair = TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
+        };
+        let tokens_pair_clone = tokens_pair.clone();
+
+        let (
+            _,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        let mut password_manager = MockPasswordManager::new();
+        auth_data_repository
+            .expect_get_auth_data()
+            .return_once(move |_| Ok(profile));
+        password_manager
+            .expect_verify_password()
+            .return_once(move |_, __| Ok(true));
+        password_manager.expect_needs_rehash().return_once(|_| true);
+        password_manager
+            .expect_hash_password()
+            .return_once(|_| Ok("new_hash".to_string()));
+        auth_data_repository
+            .expect_update_password_hash()
+            .return_once(|_, __| Err(get_db_connection_error()));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .return_once(|_| Ok(false));
+        session_storage
+            .expect_create_session()
+            .return_once(|_, __, ___| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .return_once(move |_, __, ___, ____| Ok(tokens_pair_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(LoginResult::Authenticated(tokens_pair)));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_refresh_token_is_invalid() {
+        let failure = Failure {
+            args: None,
+            code: "Test".to_string(),
+            message: "Test".to_string(),
+            error_type: FailureType::Authentication,
+        };
+        let failure_clone = failure.clone();
+
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .with(predicate::eq("refresh_token".to_string()))
+            .return_once(move |_| Err(failure_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .refresh(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_refresh_token_was_already_used() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .with(predicate::eq("refresh_token".to_string()))
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage
+            .expect_rotate_refresh_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq("session_id".to_string()),
+                predicate::eq("old_refresh_token_id".to_string()),
+                predicate::eq(get_client()),
+            )
+            .return_once(|_, __, ___, ____| Err(get_refresh_token_reused_error()));
+        token_provider.expect_generate_token().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .refresh(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_refresh_token_reused_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_record_failed_refresh_if_refresh_token_was_already_used() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            _,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage
+            .expect_rotate_refresh_token()
+            .return_once(|_, __, ___, ____| Err(get_refresh_token_reused_error()));
+        let mut auth_event_recorder = MockAuthEventRecorder::new();
+        auth_event_recorder
+            .expect_record_event()
+            .with(predicate::eq(NewAuthEvent::new(
+                AuthEventType::Refresh,
+                AuthEventOutcome::Failure,
+                Some(1),
+                Some(get_client().id),
+                &get_device_info(),
+            )))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .refresh(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_refresh_token_reused_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_when_failed_to_generate_new_tokens() {
+        let failure = Failure {
+            args: None,
+            code: "Test".to_string(),
+            message: "Test".to_string(),
+            error_type: FailureType::Authentication,
+        };
+        let failure_clone = failure.clone();
+
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .with(predicate::eq("refresh_token".to_string()))
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage
+            .expect_rotate_refresh_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq("session_id".to_string()),
+                predicate::eq("old_refresh_token_id".to_string()),
+                predicate::eq(get_client()),
+            )
+            .return_once(|_, __, ___, ____| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq(UserRole::USER),
+                predicate::eq(get_session()),
+                predicate::eq(get_client()),
+            )
+            .return_once(move |_, __, ___, ____| Err(failure_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .refresh(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_new_token_pair_if_refresh_token_is_valid() {
+        let tokens_pair = TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
+        };
+        let tokens_pair_clone = tokens_pair.clone();
+
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .with(predicate::eq("refresh_token".to_string()))
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage
+            .expect_rotate_refresh_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq("session_id".to_string()),
+                predicate::eq("old_refresh_token_id".to_string()),
+                predicate::eq(get_client()),
+            )
+            .return_once(|_, __, ___, ____| Ok(get_session()));
+        token_provider
+            .expect_generate_token()
+            .with(
+                predicate::eq(1),
+                predicate::eq(UserRole::USER),
+                predicate::eq(get_session()),
+                predicate::eq(get_client()),
+            )
+            .return_once(move |_, __, ___, ____| Ok(tokens_pair_clone));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .refresh(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(tokens_pair));
+    }
+
+    #[actix_rt::test]
+    async fn should_revoke_session_of_refresh_token_on_logout() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .with(predicate::eq("refresh_token".to_string()))
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage
+            .expect_revoke_session()
+            .with(predicate::eq(1), predicate::eq("session_id".to_string()))
+            .return_once(|_, __| Ok(true));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .logout(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_revoke_session_if_refresh_token_is_invalid() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .with(predicate::eq("refresh_token".to_string()))
+            .return_once(|_| Err(get_invalid_refresh_token_error()));
+        session_storage.expect_revoke_session().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .logout(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_refresh_token_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_user_sessions() {
+        let (
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        session_storage
+            .expect_get_user_sessions()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(vec![get_session()]));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor.get_sessions(&1).await;
+
+        assert_eq!(result, Ok(vec![get_session()]));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_revoked_session_not_found() {
+        let (
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        session_storage
+            .expect_revoke_session()
+            .with(predicate::eq(1), predicate::eq("session_id".to_string()))
+            .return_once(|_, __| Ok(false));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .revoke_session(&get_current_user(None), &"session_id".to_string())
+            .await;
+
+        assert_eq!(result, Err(get_session_not_found_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_revoke_session_while_impersonating() {
+        let (
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        session_storage.expect_revoke_session().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .revoke_session(&get_current_user(Some(2)), &"session_id".to_string())
+            .await;
+
+        assert_eq!(result, Err(get_not_allowed_while_impersonating_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_revoke_all_user_sessions() {
+        let (
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            mut personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        session_storage
+            .expect_revoke_user_sessions()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(()));
+        // Personal access tokens are not tied to a session and have to go as well.
+        personal_access_token_verifier
+            .expect_revoke_user_tokens()
+            .with(predicate::eq(1))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .logout_all(&get_current_user(None), &get_device_info())
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_revoke_all_user_sessions_while_impersonating() {
+        let (
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            mut personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        session_storage.expect_revoke_user_sessions().never();
+        personal_access_token_verifier
+            .expect_revoke_user_tokens()
+            .never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .logout_all(&get_current_user(Some(2)), &get_device_info())
+            .await;
+
+        assert_eq!(result, Err(get_not_allowed_while_impersonating_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_validate_access_token() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_access_token()
+            .with(predicate::eq("access_token".to_string()))
+            .return_once(|_| Ok(get_access_token_data()));
+        client_verifier
+            .expect_is_client_active()
+            .with(predicate::eq("client_id".to_string()))
+            .return_once(|_| Ok(true));
+        session_storage
+            .expect_get_tokens_valid_after()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(Some(50)));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .validate_access_token(&"access_token".to_string())
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(CurrentUserData {
+                id: 1,
+                role: UserRole::USER,
+                session_id: Some("session_id".to_string()),
+                actor_id: None,
+                client_id: Some("client_id".to_string()),
+                scopes: vec![Scope::ProfileRead],
+            })
+        )
+    }
+
+    #[actix_rt::test]
+    async fn should_validate_personal_access_token() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            _,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        let mut personal_access_token_verifier = MockPersonalAccessTokenVerifier::new();
+        personal_access_token_verifier
+            .expect_verify_personal_access_token()
+            .with(predicate::eq("vvp_token".to_string()))
+            .return_once(|_| {
+                Ok(Some(CurrentUserData {
+                    id: 1,
+                    role: UserRole::USER,
+                    session_id: None,
+                    actor_id: None,
+                    client_id: None,
+                    scopes: vec![Scope::ProfileRead],
+                }))
+            });
+        token_provider.expect_validate_access_token().never();
+        client_verifier.expect_is_client_active().never();
+        session_storage.expect_get_tokens_valid_after().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
+
+        let result = interactor
+            .validate_access_token(&"vvp_token".to_string())
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(CurrentUserData {
+                id: 1,
+                role: UserRole::USER,
+                session_id: None,
+                actor_id: None,
+                client_id: None,
+                scopes: vec![Scope::ProfileRead],
+            })
+        )
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_access_token_issued_before_logout_all() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_access_token()
+            .with(predicate::eq("access_token".to_string()))
+            .return_once(|_| Ok(get_access_token_data()));
+        client_verifier
+            .expect_is_client_active()
+            .with(predicate::eq("client_id".to_string()))
+            .return_once(|_| Ok(true));
+        session_storage
+            .expect_get_tokens_valid_after()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(Some(150)));
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
         let result = interactor
-            .login(&"login".to_string(), &"password".to_string())
+            .validate_access_token(&"access_token".to_string())
             .await;
 
-        assert_eq!(result, Err(failure));
+        assert_eq!(result, Err(get_invalid_access_token_error()))
     }
 
     #[actix_rt::test]
-    async fn should_return_error_if_fail_to_verify_password() {
-        let failure = Failure {
-            args: None,
-            code: "Test".to_string(),
-            message: "Test".to_string(),
-            error_type: FailureType::Authentication,
-        };
-        let profile = AuthData {
-            email: "email".to_string(),
-            password: "hash".to_string(),
-            user_role: UserRole::USER,
-            username: "username".to_string(),
-            id: 1,
-            verified_at: Some(Utc::now()),
-        };
-        let failure_clone = failure.clone();
-
-        let mut password_manager = MockPasswordManager::new();
-        let mut auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let token_provider = MockTokenProvider::new();
-        auth_data_repository
-            .expect_get_auth_data()
-            .with(predicate::eq("login".to_string()))
-            .return_once(move |_| Ok(profile));
-        password_manager
-            .expect_verify_password()
-            .with(
-                predicate::eq("password".to_string()),
-                predicate::eq("hash".to_string()),
-            )
-            .return_once(move |_, __| Err(failure_clone));
+    async fn should_reject_access_token_issued_in_same_second_as_logout_all() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_access_token()
+            .return_once(|_| Ok(get_access_token_data()));
+        client_verifier
+            .expect_is_client_active()
+            .return_once(|_| Ok(true));
+        session_storage
+            .expect_get_tokens_valid_after()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(Some(100)));
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
         let result = interactor
-            .login(&"login".to_string(), &"password".to_string())
+            .validate_access_token(&"access_token".to_string())
             .await;
 
-        assert_eq!(result, Err(failure));
+        assert_eq!(result, Err(get_invalid_access_token_error()))
     }
 
     #[actix_rt::test]
-    async fn should_return_error_if_password_invalid() {
+    async fn should_require_second_factor_when_enabled() {
         let profile = AuthData {
             email: "email".to_string(),
             password: "hash".to_string(),
-            user_role: UserRole::USER,
+            user_role: UserRole::ADMIN,
             username: "username".to_string(),
             id: 1,
             verified_at: Some(Utc::now()),
         };
 
-        let mut password_manager = MockPasswordManager::new();
-        let mut auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let token_provider = MockTokenProvider::new();
+        let (
+            mut password_manager,
+            mut token_provider,
+            mut auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
         auth_data_repository
             .expect_get_auth_data()
-            .with(predicate::eq("login".to_string()))
             .return_once(move |_| Ok(profile));
         password_manager
             .expect_verify_password()
+            .return_once(|_, __| Ok(true));
+        second_factor_verifier
+            .expect_is_second_factor_enabled()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(true));
+        token_provider
+            .expect_generate_mfa_token()
             .with(
-                predicate::eq("password".to_string()),
-                predicate::eq("hash".to_string()),
+                predicate::eq(1),
+                predicate::eq(UserRole::ADMIN),
+                predicate::eq("client_id".to_string()),
+                predicate::eq("challenge_id".to_string()),
             )
-            .return_once(move |_, __| Ok(false));
+            .return_once(|_, __, ___, ____| Ok("mfa_token".to_string()));
+        session_storage
+            .expect_create_mfa_challenge()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok("challenge_id".to_string()));
+        session_storage.expect_create_session().never();
+        token_provider.expect_generate_token().never();
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
         let result = interactor
-            .login(&"login".to_string(), &"password".to_string())
+            .login(
+                &get_client(),
+                &"login".to_string(),
+                &"password".to_string(),
+                &get_device_info(),
+            )
             .await;
 
-        assert_eq!(result, Err(get_invalid_credentials_error()));
+        assert_eq!(
+            result,
+            Ok(LoginResult::MfaRequired("mfa_token".to_string()))
+        );
     }
 
     #[actix_rt::test]
-    async fn should_return_error_if_fail_to_generate_token() {
-        let failure = Failure {
-            args: None,
-            code: "Test".to_string(),
-            message: "Test".to_string(),
-            error_type: FailureType::Authentication,
-        };
-        let profile = AuthData {
-            email: "email".to_string(),
-            password: "hash".to_string(),
-            user_role: UserRole::USER,
-            username: "username".to_string(),
-            id: 1,
-            verified_at: Some(Utc::now()),
+    async fn should_return_tokens_pair_after_second_factor() {
+        let tokens_pair = TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
         };
-        let failure_clone = failure.clone();
+        let tokens_pair_clone = tokens_pair.clone();
 
-        let mut password_manager = MockPasswordManager::new();
-        let mut auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let mut token_provider = MockTokenProvider::new();
-        auth_data_repository
-            .expect_get_auth_data()
-            .with(predicate::eq("login".to_string()))
-            .return_once(move |_| Ok(profile));
-        password_manager
-            .expect_verify_password()
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_mfa_token()
+            .with(predicate::eq("mfa_token".to_string()))
+            .return_once(|_| {
+                Ok(MfaTokenData {
+                    user_id: 1,
+                    role: UserRole::ADMIN,
+                    client_id: "client_id".to_string(),
+                    token_id: "challenge_id".to_string(),
+                })
+            });
+        session_storage
+            .expect_take_mfa_challenge()
+            .with(predicate::eq("challenge_id".to_string()))
+            .return_once(|_| Ok(true));
+        second_factor_verifier
+            .expect_verify_second_factor()
+            .with(predicate::eq(1), predicate::eq("123456".to_string()))
+            .return_once(|_, __| Ok(true));
+        session_storage
+            .expect_create_session()
             .with(
-                predicate::eq("password".to_string()),
-                predicate::eq("hash".to_string()),
+                predicate::eq(1),
+                predicate::eq(get_client()),
+                predicate::eq(get_device_info()),
             )
-            .return_once(move |_, __| Ok(true));
+            .return_once(|_, __, ___| Ok(get_session()));
         token_provider
             .expect_generate_token()
-            .with(predicate::eq(1), predicate::eq(UserRole::USER))
-            .return_once(move |_, __| Err(failure_clone));
+            .with(
+                predicate::eq(1),
+                predicate::eq(UserRole::ADMIN),
+                predicate::eq(get_session()),
+                predicate::eq(get_client()),
+            )
+            .return_once(move |_, __, ___, ____| Ok(tokens_pair_clone));
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
         let result = interactor
-            .login(&"login".to_string(), &"password".to_string())
+            .login_with_second_factor(
+                &get_client(),
+                &"mfa_token".to_string(),
+                &"123456".to_string(),
+                &get_device_info(),
+            )
             .await;
 
-        assert_eq!(result, Err(failure));
+        assert_eq!(result, Ok(tokens_pair));
     }
 
     #[actix_rt::test]
-    async fn should_return_return_tokens_pair() {
-        let profile = AuthData {
-            email: "email".to_string(),
-            password: "hash".to_string(),
-            user_role: UserRole::USER,
-            username: "username".to_string(),
-            id: 1,
-            verified_at: Some(Utc::now()),
-        };
-        let tokens_pair = TokensPair {
-            access_token: "access_token".to_string(),
-            refresh_token: "refresh_token".to_string(),
-            access_token_exp: 1,
-            refresh_token_exp: 2,
-        };
-        let tokens_pair_clone = tokens_pair.clone();
+    async fn should_return_error_if_second_factor_is_invalid() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider.expect_validate_mfa_token().return_once(|_| {
+            Ok(MfaTokenData {
+                user_id: 1,
+                role: UserRole::ADMIN,
+                client_id: "client_id".to_string(),
+                token_id: "challenge_id".to_string(),
+            })
+        });
+        session_storage
+            .expect_take_mfa_challenge()
+            .return_once(|_| Ok(true));
+        second_factor_verifier
+            .expect_verify_second_factor()
+            .return_once(|_, __| Ok(false));
+        session_storage.expect_create_session().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
 
-        let mut password_manager = MockPasswordManager::new();
-        let mut auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let mut token_provider = MockTokenProvider::new();
-        auth_data_repository
-            .expect_get_auth_data()
-            .with(predicate::eq("login".to_string()))
-            .return_once(move |_| Ok(profile));
-        password_manager
-            .expect_verify_password()
-            .with(
-                predicate::eq("password".to_string()),
-                predicate::eq("hash".to_string()),
+        let result = interactor
+            .login_with_second_factor(
+                &get_client(),
+                &"mfa_token".to_string(),
+                &"123456".to_string(),
+                &get_device_info(),
             )
-            .return_once(move |_, __| Ok(true));
-        token_provider
-            .expect_generate_token()
-            .with(predicate::eq(1), predicate::eq(UserRole::USER))
-            .return_once(move |_, __| Ok(tokens_pair_clone));
+            .await;
+
+        assert_eq!(result, Err(get_invalid_second_factor_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_accept_used_mfa_token() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider.expect_validate_mfa_token().return_once(|_| {
+            Ok(MfaTokenData {
+                user_id: 1,
+                role: UserRole::ADMIN,
+                client_id: "client_id".to_string(),
+                token_id: "challenge_id".to_string(),
+            })
+        });
+        session_storage
+            .expect_take_mfa_challenge()
+            .with(predicate::eq("challenge_id".to_string()))
+            .return_once(|_| Ok(false));
+        second_factor_verifier.expect_verify_second_factor().never();
+        session_storage.expect_create_session().never();
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
         let result = interactor
-            .login(&"login".to_string(), &"password".to_string())
+            .login_with_second_factor(
+                &get_client(),
+                &"mfa_token".to_string(),
+                &"123456".to_string(),
+                &get_device_info(),
+            )
             .await;
 
-        assert_eq!(result, Ok(tokens_pair));
+        assert_eq!(result, Err(get_invalid_mfa_token_error()));
     }
 
     #[actix_rt::test]
-    async fn should_return_error_if_refresh_token_is_invalid() {
-        let failure = Failure {
-            args: None,
-            code: "Test".to_string(),
-            message: "Test".to_string(),
-            error_type: FailureType::Authentication,
-        };
-        let failure_clone = failure.clone();
+    async fn should_throttle_second_factor_attempts() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            mut second_factor_verifier,
+            personal_access_token_verifier,
+            _,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider.expect_validate_mfa_token().return_once(|_| {
+            Ok(MfaTokenData {
+                user_id: 1,
+                role: UserRole::ADMIN,
+                client_id: "client_id".to_string(),
+                token_id: "challenge_id".to_string(),
+            })
+        });
+        session_storage
+            .expect_take_mfa_challenge()
+            .return_once(|_| Ok(true));
+        let mut login_throttle = MockLoginThrottle::new();
+        login_throttle
+            .expect_register_second_factor_attempt()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(Some(30)));
+        login_throttle.expect_reset_second_factor().never();
+        second_factor_verifier.expect_verify_second_factor().never();
+        session_storage.expect_create_session().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
 
-        let password_manager = MockPasswordManager::new();
-        let auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let mut token_provider = MockTokenProvider::new();
+        let result = interactor
+            .login_with_second_factor(
+                &get_client(),
+                &"mfa_token".to_string(),
+                &"123456".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_too_many_login_attempts_error(30)));
+    }
+
+    #[actix_rt::test]
+    async fn should_introspect_active_access_token() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
         token_provider
-            .expect_validate_refresh_token()
-            .with(predicate::eq("refresh_token".to_string()))
-            .return_once(move |_| Err(failure_clone));
+            .expect_validate_access_token()
+            .with(predicate::eq("access_token".to_string()))
+            .return_once(|_| Ok(get_access_token_data()));
+        client_verifier
+            .expect_is_client_active()
+            .with(predicate::eq("client_id".to_string()))
+            .return_once(|_| Ok(true));
+        session_storage
+            .expect_get_tokens_valid_after()
+            .return_once(|_| Ok(None));
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
-        let result = interactor.refresh(&"refresh_token".to_string()).await;
+        let result = interactor.introspect(&"access_token".to_string()).await;
 
-        assert_eq!(result, Err(failure));
+        assert_eq!(
+            result,
+            Ok(Some(TokenIntrospection {
+                user_id: 1,
+                role: UserRole::USER,
+                token_type: IntrospectedTokenType::Access,
+                client_id: Some("client_id".to_string()),
+                scopes: vec![Scope::ProfileRead],
+                actor_id: None,
+                issued_at: Some(100),
+                expires_at: Some(160),
+            }))
+        );
     }
 
     #[actix_rt::test]
-    async fn should_return_error_when_failed_to_generate_new_tokens() {
-        let failure = Failure {
-            args: None,
-            code: "Test".to_string(),
-            message: "Test".to_string(),
-            error_type: FailureType::Authentication,
+    async fn should_introspect_personal_access_token() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            _,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        let introspection = TokenIntrospection {
+            user_id: 1,
+            role: UserRole::USER,
+            token_type: IntrospectedTokenType::Access,
+            client_id: None,
+            scopes: vec![Scope::ProfileRead],
+            actor_id: None,
+            issued_at: Some(100),
+            expires_at: Some(2_000),
         };
-        let failure_clone = failure.clone();
+        let introspection_clone = introspection.clone();
+        let mut personal_access_token_verifier = MockPersonalAccessTokenVerifier::new();
+        personal_access_token_verifier
+            .expect_introspect_personal_access_token()
+            .with(predicate::eq("vvp_token".to_string()))
+            .return_once(move |_| Ok(Some(introspection_clone)));
+        personal_access_token_verifier
+            .expect_verify_personal_access_token()
+            .never();
+        token_provider.expect_validate_access_token().never();
+        let interactor = AuthInteractor::new(
+            password_manager,
+            token_provider,
+            auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        );
 
-        let password_manager = MockPasswordManager::new();
-        let auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let mut token_provider = MockTokenProvider::new();
+        let result = interactor.introspect(&"vvp_token".to_string()).await;
+
+        assert_eq!(result, Ok(Some(introspection)));
+    }
+
+    #[actix_rt::test]
+    async fn should_report_rotated_refresh_token_as_inactive() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            mut client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_access_token()
+            .return_once(|_| Err(get_invalid_access_token_error()));
         token_provider
             .expect_validate_refresh_token()
             .with(predicate::eq("refresh_token".to_string()))
-            .return_once(|_| Ok((1, UserRole::USER)));
-        token_provider
-            .expect_generate_token()
-            .with(predicate::eq(1), predicate::eq(UserRole::USER))
-            .return_once(move |_, __| Err(failure_clone));
+            .return_once(|_| Ok(get_refresh_token_data()));
+        client_verifier
+            .expect_is_client_active()
+            .return_once(|_| Ok(true));
+        session_storage
+            .expect_get_user_sessions()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(vec![get_session()]));
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
-        let result = interactor.refresh(&"refresh_token".to_string()).await;
+        let result = interactor.introspect(&"refresh_token".to_string()).await;
 
-        assert_eq!(result, Err(failure));
+        assert_eq!(result, Ok(None));
     }
 
     #[actix_rt::test]
-    async fn should_return_new_token_pair_if_refresh_token_is_valid() {
-        let tokens_pair = TokensPair {
-            access_token: "access_token".to_string(),
-            refresh_token: "refresh_token".to_string(),
-            access_token_exp: 1,
-            refresh_token_exp: 2,
-        };
-        let tokens_pair_clone = tokens_pair.clone();
-
-        let password_manager = MockPasswordManager::new();
-        let auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let mut token_provider = MockTokenProvider::new();
+    async fn should_revoke_session_of_revoked_refresh_token() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
         token_provider
             .expect_validate_refresh_token()
             .with(predicate::eq("refresh_token".to_string()))
-            .return_once(|_| Ok((1, UserRole::USER)));
-        token_provider
-            .expect_generate_token()
-            .with(predicate::eq(1), predicate::eq(UserRole::USER))
-            .return_once(move |_, __| Ok(tokens_pair_clone));
+            .return_once(|_| Ok(get_refresh_token_data()));
+        session_storage
+            .expect_revoke_session()
+            .with(predicate::eq(1), predicate::eq("session_id".to_string()))
+            .times(1)
+            .return_once(|_, __| Ok(true));
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
-        let result = interactor.refresh(&"refresh_token".to_string()).await;
+        let result = interactor
+            .revoke(
+                &get_client(),
+                &"refresh_token".to_string(),
+                &get_device_info(),
+            )
+            .await;
 
-        assert_eq!(result, Ok(tokens_pair));
+        assert_eq!(result, Ok(()));
     }
 
     #[actix_rt::test]
-    async fn should_validate_access_token() {
-        let password_manager = MockPasswordManager::new();
-        let auth_data_repository = MockAuthDataRepository::new();
-        let auth_config_provider = MockAuthConfigProvider::new();
-        let mut token_provider = MockTokenProvider::new();
+    async fn should_ignore_revocation_of_token_issued_to_other_client() {
+        let (
+            password_manager,
+            mut token_provider,
+            auth_data_repository,
+            client_verifier,
+            mut session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
+            auth_config_provider,
+            auth_event_recorder,
+        ) = get_dependencies();
+        token_provider
+            .expect_validate_refresh_token()
+            .return_once(|_| Err(get_invalid_refresh_token_error()));
         token_provider
             .expect_validate_access_token()
-            .with(predicate::eq("access_token".to_string()))
-            .return_once(|_| Ok((1, UserRole::USER)));
+            .return_once(|_| {
+                Ok(AccessTokenData {
+                    client_id: "other_client_id".to_string(),
+                    ..get_access_token_data()
+                })
+            });
+        session_storage.expect_revoke_session().never();
         let interactor = AuthInteractor::new(
             password_manager,
             token_provider,
             auth_data_repository,
+            client_verifier,
+            session_storage,
+            second_factor_verifier,
+            personal_access_token_verifier,
+            login_throttle,
             auth_config_provider,
+            auth_event_recorder,
         );
 
         let result = interactor
-            .validate_access_token(&"access_token".to_string())
+            .revoke(
+                &get_client(),
+                &"access_token".to_string(),
+                &get_device_info(),
+            )
             .await;
 
-        assert_eq!(
-            result,
-            Ok(CurrentUserData {
-                id: 1,
-                role: UserRole::USER,
-            })
-        )
+        assert_eq!(result, Ok(()));
     }
 }
@@ -0,0 +1,79 @@
+use actix_web::{
+    get,
+    web::{self, ServiceConfig},
+    HttpResponse, Responder,
+};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    features::{
+        auth::{
+            api::{
+                auth_middleware::{require_role, verify_current_user},
+                scoped_user::{AdminRead, ProfileRead, ScopedUser},
+            },
+            domain::user_role::UserRole,
+        },
+        auth_event::{
+            domain::auth_event::AuthEvent,
+            infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+            interactors::auth_event_interactor::AuthEventInteractor,
+        },
+    },
+};
+
+use super::dtos::{
+    auth_event_dto::AuthEventDto, auth_event_filter_dto::AuthEventFilterDto,
+    auth_event_page_dto::AuthEventPageDto,
+};
+
+type Interactor = AuthEventInteractor<AuthEventRepositoryImpl>;
+
+// Has to be configured before the profile controller, whose `/profile` scope would
+// otherwise swallow the user's events.
+pub fn configure_auth_event_controller(config: &mut ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(verify_current_user);
+    config.service(
+        web::scope("/profile/me/security-events")
+            .wrap(auth_middleware)
+            .service(get_user_events),
+    );
+    config.service(
+        web::scope("/admin/security-events")
+            .wrap(require_role(UserRole::ADMIN))
+            .service(get_events),
+    );
+}
+
+#[get("")]
+async fn get_user_events(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileRead>,
+    page: web::Query<AuthEventPageDto>,
+) -> impl Responder {
+    let result = interactor
+        .get_user_events(&current_user.id, page.before, page.limit)
+        .await;
+    match result {
+        Ok(events) => HttpResponse::Ok().json(to_dtos(events)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[get("")]
+async fn get_events(
+    interactor: web::Data<Interactor>,
+    _current_user: ScopedUser<AdminRead>,
+    filter: web::Query<AuthEventFilterDto>,
+) -> impl Responder {
+    let result = interactor.get_events(&filter.into_inner().into()).await;
+    match result {
+        Ok(events) => HttpResponse::Ok().json(to_dtos(events)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+fn to_dtos(events: Vec<AuthEvent>) -> Vec<AuthEventDto> {
+    return events.into_iter().map(AuthEventDto::from).collect();
+}
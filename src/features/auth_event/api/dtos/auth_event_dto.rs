@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::features::auth_event::domain::auth_event::AuthEvent;
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthEventDto {
+    id: i64,
+    user_id: Option<i64>,
+    event_type: String,
+    outcome: String,
+    ip: Option<String>,
+    user_agent: Option<String>,
+    client_id: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AuthEvent> for AuthEventDto {
+    fn from(event: AuthEvent) -> Self {
+        AuthEventDto {
+            id: event.id,
+            user_id: event.user_id,
+            event_type: event.event_type,
+            outcome: event.outcome,
+            ip: event.ip,
+            user_agent: event.user_agent,
+            client_id: event.client_id,
+            created_at: event.created_at,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::features::auth_event::domain::auth_event_filter::AuthEventFilter;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthEventFilterDto {
+    pub user_id: Option<i64>,
+    pub event_type: Option<String>,
+    pub outcome: Option<String>,
+    pub client_id: Option<String>,
+    pub ip: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub before: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl From<AuthEventFilterDto> for AuthEventFilter {
+    fn from(dto: AuthEventFilterDto) -> Self {
+        AuthEventFilter {
+            user_id: dto.user_id,
+            event_type: dto.event_type,
+            outcome: dto.outcome,
+            client_id: dto.client_id,
+            ip: dto.ip,
+            from: dto.from,
+            to: dto.to,
+            before: dto.before,
+            limit: dto.limit,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthEventPageDto {
+    pub before: Option<i64>,
+    pub limit: Option<i64>,
+}
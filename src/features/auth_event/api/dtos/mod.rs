@@ -0,0 +1,3 @@
+pub mod auth_event_dto;
+pub mod auth_event_filter_dto;
+pub mod auth_event_page_dto;
@@ -0,0 +1,2 @@
+pub mod auth_event_controller;
+pub mod dtos;
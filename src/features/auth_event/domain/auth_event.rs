@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AuthEvent {
+    pub id: i64,
+    pub user_id: Option<i64>,
+    pub event_type: String,
+    pub outcome: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub client_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct AuthEventFilter {
+    pub user_id: Option<i64>,
+    pub event_type: Option<String>,
+    pub outcome: Option<String>,
+    pub client_id: Option<String>,
+    pub ip: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    // Events are listed newest first, the next page starts before the last returned id.
+    pub before: Option<i64>,
+    pub limit: Option<i64>,
+}
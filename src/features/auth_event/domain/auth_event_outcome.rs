@@ -0,0 +1,24 @@
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AuthEventOutcome {
+    Success,
+    Failure,
+}
+
+impl AuthEventOutcome {
+    pub fn parse(str: &str) -> Option<AuthEventOutcome> {
+        return match str {
+            "success" => Some(AuthEventOutcome::Success),
+            "failure" => Some(AuthEventOutcome::Failure),
+            _ => None,
+        };
+    }
+}
+
+impl From<&AuthEventOutcome> for String {
+    fn from(outcome: &AuthEventOutcome) -> Self {
+        return match outcome {
+            AuthEventOutcome::Success => "success".to_string(),
+            AuthEventOutcome::Failure => "failure".to_string(),
+        };
+    }
+}
@@ -0,0 +1,64 @@
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AuthEventType {
+    Login,
+    Refresh,
+    Logout,
+    PasswordChange,
+    PasswordReset,
+    EmailVerification,
+    RoleChange,
+}
+
+impl AuthEventType {
+    pub fn parse(str: &str) -> Option<AuthEventType> {
+        return match str {
+            "login" => Some(AuthEventType::Login),
+            "refresh" => Some(AuthEventType::Refresh),
+            "logout" => Some(AuthEventType::Logout),
+            "password_change" => Some(AuthEventType::PasswordChange),
+            "password_reset" => Some(AuthEventType::PasswordReset),
+            "email_verification" => Some(AuthEventType::EmailVerification),
+            "role_change" => Some(AuthEventType::RoleChange),
+            _ => None,
+        };
+    }
+}
+
+impl From<&AuthEventType> for String {
+    fn from(event_type: &AuthEventType) -> Self {
+        return match event_type {
+            AuthEventType::Login => "login".to_string(),
+            AuthEventType::Refresh => "refresh".to_string(),
+            AuthEventType::Logout => "logout".to_string(),
+            AuthEventType::PasswordChange => "password_change".to_string(),
+            AuthEventType::PasswordReset => "password_reset".to_string(),
+            AuthEventType::EmailVerification => "email_verification".to_string(),
+            AuthEventType::RoleChange => "role_change".to_string(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_own_string_representation() {
+        let event_types = vec![
+            AuthEventType::Login,
+            AuthEventType::Refresh,
+            AuthEventType::Logout,
+            AuthEventType::PasswordChange,
+            AuthEventType::PasswordReset,
+            AuthEventType::EmailVerification,
+            AuthEventType::RoleChange,
+        ];
+
+        for event_type in event_types {
+            let string = String::from(&event_type);
+
+            assert_eq!(AuthEventType::parse(&string), Some(event_type));
+        }
+        assert_eq!(AuthEventType::parse("unknown"), None);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod auth_event;
+pub mod auth_event_filter;
+pub mod auth_event_outcome;
+pub mod auth_event_type;
+pub mod new_auth_event;
@@ -0,0 +1,33 @@
+use crate::features::auth::domain::device_info::DeviceInfo;
+
+use super::{auth_event_outcome::AuthEventOutcome, auth_event_type::AuthEventType};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NewAuthEvent {
+    // Missing for failed logins with an unknown login.
+    pub user_id: Option<i64>,
+    pub event_type: AuthEventType,
+    pub outcome: AuthEventOutcome,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub client_id: Option<String>,
+}
+
+impl NewAuthEvent {
+    pub fn new(
+        event_type: AuthEventType,
+        outcome: AuthEventOutcome,
+        user_id: Option<i64>,
+        client_id: Option<String>,
+        device_info: &DeviceInfo,
+    ) -> NewAuthEvent {
+        return NewAuthEvent {
+            user_id,
+            event_type,
+            outcome,
+            ip: device_info.ip.clone(),
+            user_agent: device_info.user_agent.clone(),
+            client_id,
+        };
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_invalid_event_type_error(event_type: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("eventType".to_string(), event_type.clone());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_auth_event_type".to_string(),
+        message: format!("Unknown event type '{}'", event_type),
+        args: Some(args),
+    };
+}
+
+pub fn get_invalid_outcome_error(outcome: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("outcome".to_string(), outcome.clone());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_auth_event_outcome".to_string(),
+        message: format!("Unknown outcome '{}'", outcome),
+        args: Some(args),
+    };
+}
@@ -0,0 +1,95 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::auth_event::{
+        domain::{
+            auth_event::AuthEvent, auth_event_filter::AuthEventFilter, new_auth_event::NewAuthEvent,
+        },
+        errors::auth_event_errors::get_db_connection_error,
+        interactors::auth_event_interactor::{AuthEventRecorder, AuthEventRepository},
+    },
+    schema::auth_event,
+};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+use super::entities::{
+    auth_event_entity::AuthEventEntity, new_auth_event_entity::NewAuthEventEntity,
+};
+
+pub struct AuthEventRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl AuthEventRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> AuthEventRepositoryImpl {
+        return AuthEventRepositoryImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl AuthEventRecorder for AuthEventRepositoryImpl {
+    async fn record_event(&self, event: &NewAuthEvent) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::insert_into(auth_event::table)
+            .values(&NewAuthEventEntity::from(event))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl AuthEventRepository for AuthEventRepositoryImpl {
+    async fn get_events(&self, filter: &AuthEventFilter) -> Result<Vec<AuthEvent>, Failure> {
+        let connection = self.get_connection()?;
+        let mut query = auth_event::table.into_boxed();
+        if let Some(user_id) = filter.user_id {
+            query = query.filter(auth_event::user_id.eq(user_id));
+        }
+        if let Some(event_type) = &filter.event_type {
+            query = query.filter(auth_event::event_type.eq(event_type));
+        }
+        if let Some(outcome) = &filter.outcome {
+            query = query.filter(auth_event::outcome.eq(outcome));
+        }
+        if let Some(client_id) = &filter.client_id {
+            query = query.filter(auth_event::client_id.eq(client_id));
+        }
+        if let Some(ip) = &filter.ip {
+            query = query.filter(auth_event::ip.eq(ip));
+        }
+        if let Some(from) = filter.from {
+            query = query.filter(auth_event::created_at.ge(from));
+        }
+        if let Some(to) = filter.to {
+            query = query.filter(auth_event::created_at.lt(to));
+        }
+        if let Some(before) = filter.before {
+            query = query.filter(auth_event::id.lt(before));
+        }
+        if let Some(limit) = filter.limit {
+            query = query.limit(limit);
+        }
+        let result = query
+            .order(auth_event::id.desc())
+            .load::<AuthEventEntity>(&connection);
+
+        return match result {
+            Ok(entities) => Ok(entities.into_iter().map(AuthEvent::from).collect()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+}
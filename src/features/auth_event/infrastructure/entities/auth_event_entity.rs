@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::auth_event::domain::auth_event::AuthEvent;
+
+#[derive(Queryable, Debug, PartialEq, Clone)]
+pub struct AuthEventEntity {
+    pub id: i64,
+    pub user_id: Option<i64>,
+    pub event_type: String,
+    pub outcome: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub client_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuthEventEntity> for AuthEvent {
+    fn from(entity: AuthEventEntity) -> Self {
+        AuthEvent {
+            id: entity.id,
+            user_id: entity.user_id,
+            event_type: entity.event_type,
+            outcome: entity.outcome,
+            ip: entity.ip,
+            user_agent: entity.user_agent,
+            client_id: entity.client_id,
+            created_at: entity.created_at,
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod auth_event_entity;
+pub mod new_auth_event_entity;
@@ -0,0 +1,25 @@
+use crate::{features::auth_event::domain::new_auth_event::NewAuthEvent, schema::auth_event};
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "auth_event"]
+pub struct NewAuthEventEntity {
+    pub user_id: Option<i64>,
+    pub event_type: String,
+    pub outcome: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub client_id: Option<String>,
+}
+
+impl From<&NewAuthEvent> for NewAuthEventEntity {
+    fn from(event: &NewAuthEvent) -> Self {
+        NewAuthEventEntity {
+            user_id: event.user_id,
+            event_type: String::from(&event.event_type),
+            outcome: String::from(&event.outcome),
+            ip: event.ip.clone(),
+            user_agent: event.user_agent.clone(),
+            client_id: event.client_id.clone(),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod auth_event_repository_impl;
+pub mod entities;
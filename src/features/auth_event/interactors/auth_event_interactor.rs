@@ -0,0 +1,151 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::auth_event::{
+        domain::{
+            auth_event::AuthEvent, auth_event_filter::AuthEventFilter,
+            auth_event_outcome::AuthEventOutcome, auth_event_type::AuthEventType,
+            new_auth_event::NewAuthEvent,
+        },
+        errors::auth_event_errors::{get_invalid_event_type_error, get_invalid_outcome_error},
+    },
+};
+
+use async_trait::async_trait;
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+// Recording is best effort, callers ignore failures so that an unavailable audit log does not
+// lock everyone out.
+#[async_trait]
+pub trait AuthEventRecorder {
+    async fn record_event(&self, event: &NewAuthEvent) -> Result<(), Failure>;
+}
+
+#[async_trait]
+pub trait AuthEventRepository {
+    async fn get_events(&self, filter: &AuthEventFilter) -> Result<Vec<AuthEvent>, Failure>;
+}
+
+pub struct AuthEventInteractor<T> {
+    auth_event_repository: T,
+}
+
+impl<T> AuthEventInteractor<T>
+where
+    T: AuthEventRepository,
+{
+    pub fn new(auth_event_repository: T) -> AuthEventInteractor<T> {
+        AuthEventInteractor {
+            auth_event_repository,
+        }
+    }
+
+    pub async fn get_user_events(
+        &self,
+        user_id: &i64,
+        before: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<AuthEvent>, Failure> {
+        let filter = AuthEventFilter {
+            user_id: Some(*user_id),
+            before,
+            limit,
+            ..AuthEventFilter::default()
+        };
+
+        self.get_events(&filter).await
+    }
+
+    pub async fn get_events(&self, filter: &AuthEventFilter) -> Result<Vec<AuthEvent>, Failure> {
+        if let Some(event_type) = &filter.event_type {
+            AuthEventType::parse(event_type)
+                .ok_or_else(|| get_invalid_event_type_error(event_type.clone()))?;
+        }
+        if let Some(outcome) = &filter.outcome {
+            AuthEventOutcome::parse(outcome)
+                .ok_or_else(|| get_invalid_outcome_error(outcome.clone()))?;
+        }
+        let filter = AuthEventFilter {
+            limit: Some(filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)),
+            ..filter.clone()
+        };
+
+        self.auth_event_repository.get_events(&filter).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mockall::{mock, predicate};
+
+    use super::*;
+
+    mock! {
+        AuthEventRepository {}
+
+        #[async_trait]
+        impl AuthEventRepository for AuthEventRepository {
+            async fn get_events(&self, filter: &AuthEventFilter) -> Result<Vec<AuthEvent>, Failure>;
+        }
+    }
+
+    #[actix_rt::test]
+    async fn should_only_list_events_of_user() {
+        let mut repository = MockAuthEventRepository::new();
+        repository
+            .expect_get_events()
+            .with(predicate::eq(AuthEventFilter {
+                user_id: Some(1),
+                before: Some(10),
+                limit: Some(DEFAULT_LIMIT),
+                ..AuthEventFilter::default()
+            }))
+            .times(1)
+            .return_once(|_| Ok(vec![]));
+        let interactor = AuthEventInteractor::new(repository);
+
+        let result = interactor.get_user_events(&1, Some(10), None).await;
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[actix_rt::test]
+    async fn should_cap_page_size() {
+        let mut repository = MockAuthEventRepository::new();
+        repository
+            .expect_get_events()
+            .withf(|filter| filter.limit == Some(MAX_LIMIT))
+            .times(1)
+            .return_once(|_| Ok(vec![]));
+        let interactor = AuthEventInteractor::new(repository);
+
+        let result = interactor
+            .get_events(&AuthEventFilter {
+                limit: Some(10_000),
+                ..AuthEventFilter::default()
+            })
+            .await;
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_unknown_event_type() {
+        let mut repository = MockAuthEventRepository::new();
+        repository.expect_get_events().never();
+        let interactor = AuthEventInteractor::new(repository);
+
+        let result = interactor
+            .get_events(&AuthEventFilter {
+                event_type: Some("unknown".to_string()),
+                ..AuthEventFilter::default()
+            })
+            .await;
+
+        assert_eq!(
+            result,
+            Err(get_invalid_event_type_error("unknown".to_string()))
+        );
+    }
+}
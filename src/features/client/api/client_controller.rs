@@ -0,0 +1,90 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    post,
+    web::{self, ServiceConfig},
+    HttpResponse, Responder,
+};
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    config::client_config::ClientConfig,
+    features::{
+        auth::{
+            api::{
+                auth_middleware::require_role,
+                scoped_user::{AdminRead, AdminWrite, ScopedUser},
+            },
+            domain::user_role::UserRole,
+        },
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
+        },
+    },
+};
+
+use super::dtos::{
+    client_credentials_dto::ClientCredentialsDto, client_dto::ClientDto,
+    create_client_dto::CreateClientDto,
+};
+
+type Interactor = ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>;
+
+pub fn configure_client_controller(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/admin/clients")
+            .wrap(require_role(UserRole::ADMIN))
+            .service(get_clients)
+            .service(create_client)
+            .service(revoke_client),
+    );
+}
+
+#[get("")]
+async fn get_clients(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<AdminRead>,
+) -> impl Responder {
+    let result = interactor.get_clients(&current_user.role).await;
+    match result {
+        Ok(clients) => HttpResponse::Ok().json(
+            clients
+                .into_iter()
+                .map(ClientDto::from)
+                .collect::<Vec<ClientDto>>(),
+        ),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("")]
+async fn create_client(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<AdminWrite>,
+    dto: web::Json<CreateClientDto>,
+) -> impl Responder {
+    let result = interactor
+        .create_client(&current_user.role, &dto.into_inner().into())
+        .await;
+    match result {
+        Ok(credentials) => HttpResponse::Ok().json(ClientCredentialsDto::from(credentials)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[delete("/{id}")]
+async fn revoke_client(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<AdminWrite>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let result = interactor
+        .revoke_client(&current_user.role, &path.into_inner())
+        .await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
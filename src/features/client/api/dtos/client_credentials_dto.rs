@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+use crate::features::client::domain::client_credentials::ClientCredentials;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCredentialsDto {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+impl From<ClientCredentials> for ClientCredentialsDto {
+    fn from(credentials: ClientCredentials) -> Self {
+        return ClientCredentialsDto {
+            client_id: credentials.client.id,
+            client_secret: credentials.client_secret,
+        };
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::features::client::domain::client::Client;
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDto {
+    id: String,
+    name: String,
+    grant_types: Vec<String>,
+    scopes: Vec<String>,
+    access_token_exp: Option<u64>,
+    refresh_token_exp: Option<u64>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Client> for ClientDto {
+    fn from(client: Client) -> Self {
+        return ClientDto {
+            id: client.id,
+            name: client.name,
+            grant_types: client.grant_types.iter().map(String::from).collect(),
+            scopes: client.scopes.iter().map(String::from).collect(),
+            access_token_exp: client.access_token_exp,
+            refresh_token_exp: client.refresh_token_exp,
+            revoked_at: client.revoked_at,
+            created_at: client.created_at,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::auth::domain::{grant_type::GrantType, scope::Scope};
+
+    use super::*;
+
+    #[test]
+    fn should_map_model_to_dto_without_secret() {
+        let client = Client {
+            id: "client_id".to_string(),
+            name: "web".to_string(),
+            secret_hash: "secret_hash".to_string(),
+            grant_types: vec![GrantType::Password, GrantType::RefreshToken],
+            scopes: vec![Scope::ProfileRead],
+            access_token_exp: None,
+            refresh_token_exp: Some(60),
+            revoked_at: None,
+            created_at: Utc::now(),
+        };
+
+        let dto = ClientDto::from(client);
+        let json = serde_json::to_value(&dto).unwrap();
+
+        assert_eq!(
+            dto.grant_types,
+            vec!["password".to_string(), "refresh_token".to_string()]
+        );
+        assert_eq!(json["refreshTokenExp"], 60);
+        assert_eq!(json["scopes"][0], "profile:read");
+        assert!(json.get("secretHash").is_none());
+    }
+}
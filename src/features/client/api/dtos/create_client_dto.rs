@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+use crate::features::client::domain::create_client_model::CreateClientModel;
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateClientDto {
+    name: String,
+    grant_types: Vec<String>,
+    scopes: Vec<String>,
+    access_token_exp: Option<u64>,
+    refresh_token_exp: Option<u64>,
+}
+
+impl From<CreateClientDto> for CreateClientModel {
+    fn from(dto: CreateClientDto) -> CreateClientModel {
+        return CreateClientModel {
+            name: dto.name,
+            grant_types: dto.grant_types,
+            scopes: dto.scopes,
+            access_token_exp: dto.access_token_exp,
+            refresh_token_exp: dto.refresh_token_exp,
+        };
+    }
+}
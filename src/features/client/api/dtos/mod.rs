@@ -0,0 +1,3 @@
+pub mod client_credentials_dto;
+pub mod client_dto;
+pub mod create_client_dto;
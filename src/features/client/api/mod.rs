@@ -0,0 +1,2 @@
+pub mod client_controller;
+pub mod dtos;
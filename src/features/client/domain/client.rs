@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::auth::domain::{client_data::ClientData, grant_type::GrantType, scope::Scope};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Client {
+    pub id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub grant_types: Vec<GrantType>,
+    pub scopes: Vec<Scope>,
+    pub access_token_exp: Option<u64>,
+    pub refresh_token_exp: Option<u64>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Client> for ClientData {
+    fn from(client: Client) -> Self {
+        return ClientData {
+            id: client.id,
+            grant_types: client.grant_types,
+            scopes: client.scopes,
+            access_token_exp: client.access_token_exp,
+            refresh_token_exp: client.refresh_token_exp,
+        };
+    }
+}
@@ -0,0 +1,8 @@
+use super::client::Client;
+
+// The plain secret only exists at creation time, afterwards just its hash is stored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClientCredentials {
+    pub client: Client,
+    pub client_secret: String,
+}
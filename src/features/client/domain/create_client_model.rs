@@ -0,0 +1,8 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreateClientModel {
+    pub name: String,
+    pub grant_types: Vec<String>,
+    pub scopes: Vec<String>,
+    pub access_token_exp: Option<u64>,
+    pub refresh_token_exp: Option<u64>,
+}
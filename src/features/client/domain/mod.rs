@@ -0,0 +1,4 @@
+pub mod client;
+pub mod client_credentials;
+pub mod create_client_model;
+pub mod token_lifetimes;
@@ -0,0 +1,7 @@
+// Longest token lifetimes given to an active client, `None` when all of them use the
+// configured defaults.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TokenLifetimes {
+    pub access_token_exp: Option<u64>,
+    pub refresh_token_exp: Option<u64>,
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_unknown_client_update_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_client_update_error".to_string(),
+        message: "Unknown client update error".to_string(),
+    };
+}
+
+pub fn get_invalid_client_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.invalid_client".to_string(),
+        message: "Invalid client id or secret".to_string(),
+    };
+}
+
+pub fn get_admin_required_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.admin_required".to_string(),
+        message: "Only administrators can manage clients".to_string(),
+    };
+}
+
+pub fn get_client_not_found_error() -> Failure {
+    return Failure {
+        error_type: FailureType::NotFound,
+        args: None,
+        code: "error.client_not_found".to_string(),
+        message: "Client not found".to_string(),
+    };
+}
+
+pub fn get_invalid_client_name_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.invalid_client_name".to_string(),
+        message: "Client name must not be empty".to_string(),
+    };
+}
+
+pub fn get_invalid_grant_type_error(grant_type: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("grantType".to_string(), grant_type.clone());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_grant_type".to_string(),
+        message: format!("Unsupported grant type '{}'", grant_type),
+        args: Some(args),
+    };
+}
+
+pub fn get_invalid_scope_error(scope: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("scope".to_string(), scope.clone());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_scope".to_string(),
+        message: format!("Unknown scope '{}'", scope),
+        args: Some(args),
+    };
+}
+
+pub fn get_grant_types_required_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.grant_types_required".to_string(),
+        message: "Client must be allowed at least one grant type".to_string(),
+    };
+}
+
+pub fn get_scopes_required_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.scopes_required".to_string(),
+        message: "Client must be allowed at least one scope".to_string(),
+    };
+}
+
+// Also keeps the lifetime small enough to be added to a timestamp without overflowing.
+pub fn get_invalid_token_lifetime_error(field: String, max: u64) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("field".to_string(), field.clone());
+    args.insert("max".to_string(), max.to_string());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_token_lifetime".to_string(),
+        message: format!("{} must be between 1 and {} seconds", field, max),
+        args: Some(args),
+    };
+}
@@ -0,0 +1,115 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::client::{
+        domain::{client::Client, token_lifetimes::TokenLifetimes},
+        errors::client_errors::{get_db_connection_error, get_unknown_client_update_error},
+        interactors::client_interactor::ClientRepository,
+    },
+    schema::client,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+use super::entities::{client_entity::ClientEntity, new_client::NewClient};
+
+pub struct ClientRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ClientRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> ClientRepositoryImpl {
+        return ClientRepositoryImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl ClientRepository for ClientRepositoryImpl {
+    async fn save_client(&self, new_client: &Client) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::insert_into(client::table)
+            .values(&NewClient::from(new_client.to_owned()))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_client_update_error()),
+        };
+    }
+
+    async fn get_client(&self, client_id: &String) -> Result<Option<Client>, Failure> {
+        let connection = self.get_connection()?;
+        let result = client::table
+            .filter(client::id.eq(client_id))
+            .get_result::<ClientEntity>(&connection)
+            .optional();
+
+        return match result {
+            Ok(entity) => Ok(entity.map(|entity| entity.into())),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn get_clients(&self) -> Result<Vec<Client>, Failure> {
+        let connection = self.get_connection()?;
+        let result = client::table
+            .order(client::created_at.asc())
+            .load::<ClientEntity>(&connection);
+
+        return match result {
+            Ok(entities) => Ok(entities.into_iter().map(|entity| entity.into()).collect()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn revoke_client(&self, client_id: &String) -> Result<bool, Failure> {
+        let connection = self.get_connection()?;
+        let target = client::table
+            .filter(client::id.eq(client_id))
+            .filter(client::revoked_at.is_null());
+        let result = diesel::update(target)
+            .set(client::revoked_at.eq(Utc::now()))
+            .execute(&connection);
+
+        return match result {
+            Ok(updated) => Ok(updated == 1),
+            Err(_) => Err(get_unknown_client_update_error()),
+        };
+    }
+
+    // Tokens of revoked clients are rejected anyway, so they do not count.
+    async fn get_max_token_lifetimes(&self) -> Result<TokenLifetimes, Failure> {
+        let connection = self.get_connection()?;
+        let result = client::table
+            .filter(client::revoked_at.is_null())
+            .select((client::access_token_exp, client::refresh_token_exp))
+            .load::<(Option<i64>, Option<i64>)>(&connection);
+        let lifetimes = match result {
+            Ok(lifetimes) => lifetimes,
+            Err(_) => return Err(get_db_connection_error()),
+        };
+
+        return Ok(TokenLifetimes {
+            access_token_exp: lifetimes
+                .iter()
+                .filter_map(|(exp, _)| *exp)
+                .max()
+                .map(|exp| exp as u64),
+            refresh_token_exp: lifetimes
+                .iter()
+                .filter_map(|(_, exp)| *exp)
+                .max()
+                .map(|exp| exp as u64),
+        });
+    }
+}
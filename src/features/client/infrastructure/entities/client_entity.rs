@@ -0,0 +1,85 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Utc};
+
+use crate::features::{
+    auth::domain::{grant_type::GrantType, scope::Scope},
+    client::domain::client::Client,
+};
+
+#[derive(Queryable, Debug, PartialEq, Clone)]
+pub struct ClientEntity {
+    pub id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub grant_types: Vec<String>,
+    pub access_token_exp: Option<i64>,
+    pub refresh_token_exp: Option<i64>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub scopes: Vec<String>,
+}
+
+impl From<ClientEntity> for Client {
+    fn from(entity: ClientEntity) -> Self {
+        return Client {
+            id: entity.id,
+            name: entity.name,
+            secret_hash: entity.secret_hash,
+            grant_types: entity
+                .grant_types
+                .iter()
+                .filter_map(|grant_type| GrantType::parse(grant_type))
+                .collect(),
+            scopes: entity
+                .scopes
+                .iter()
+                .filter_map(|scope| Scope::parse(scope))
+                .collect(),
+            access_token_exp: entity.access_token_exp.and_then(to_lifetime),
+            refresh_token_exp: entity.refresh_token_exp.and_then(to_lifetime),
+            revoked_at: entity.revoked_at,
+            created_at: entity.created_at,
+        };
+    }
+}
+
+// Lifetimes that are not positive fall back to the configured defaults instead of wrapping.
+fn to_lifetime(exp: i64) -> Option<u64> {
+    return u64::try_from(exp).ok().filter(|exp| *exp > 0);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_map_entity_to_model() {
+        let entity = ClientEntity {
+            id: "client_id".to_string(),
+            name: "web".to_string(),
+            secret_hash: "hash".to_string(),
+            grant_types: vec!["password".to_string(), "unknown".to_string()],
+            access_token_exp: Some(60),
+            refresh_token_exp: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+            scopes: vec!["profile:read".to_string(), "admin".to_string()],
+        };
+
+        let result = Client::from(entity);
+
+        assert_eq!(result.id, "client_id".to_string());
+        assert_eq!(result.grant_types, vec![GrantType::Password]);
+        assert_eq!(result.scopes, vec![Scope::ProfileRead]);
+        assert_eq!(result.access_token_exp, Some(60));
+        assert_eq!(result.refresh_token_exp, None);
+    }
+
+    #[test]
+    fn should_ignore_lifetimes_that_are_not_positive() {
+        assert_eq!(to_lifetime(60), Some(60));
+        assert_eq!(to_lifetime(0), None);
+        assert_eq!(to_lifetime(-1), None);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod client_entity;
+pub mod new_client;
@@ -0,0 +1,37 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Utc};
+
+use crate::{features::client::domain::client::Client, schema::client};
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "client"]
+pub struct NewClient {
+    pub id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub grant_types: Vec<String>,
+    pub scopes: Vec<String>,
+    pub access_token_exp: Option<i64>,
+    pub refresh_token_exp: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Client> for NewClient {
+    fn from(client: Client) -> Self {
+        return NewClient {
+            id: client.id,
+            name: client.name,
+            secret_hash: client.secret_hash,
+            grant_types: client.grant_types.iter().map(String::from).collect(),
+            scopes: client.scopes.iter().map(String::from).collect(),
+            access_token_exp: client
+                .access_token_exp
+                .and_then(|exp| i64::try_from(exp).ok()),
+            refresh_token_exp: client
+                .refresh_token_exp
+                .and_then(|exp| i64::try_from(exp).ok()),
+            created_at: client.created_at,
+        };
+    }
+}
@@ -0,0 +1,2 @@
+pub mod client_repository_impl;
+pub mod entities;
@@ -0,0 +1,540 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::{
+            domain::{
+                client_data::ClientData, grant_type::GrantType, scope::Scope, user_role::UserRole,
+            },
+            interactors::auth_interactor::ClientVerifier,
+        },
+        client::{
+            domain::{
+                client::Client, client_credentials::ClientCredentials,
+                create_client_model::CreateClientModel, token_lifetimes::TokenLifetimes,
+            },
+            errors::client_errors::{
+                get_admin_required_error, get_client_not_found_error,
+                get_grant_types_required_error, get_invalid_client_error,
+                get_invalid_client_name_error, get_invalid_grant_type_error,
+                get_invalid_scope_error, get_invalid_token_lifetime_error,
+                get_scopes_required_error,
+            },
+        },
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+
+#[async_trait]
+pub trait ClientRepository {
+    async fn save_client(&self, client: &Client) -> Result<(), Failure>;
+
+    async fn get_client(&self, client_id: &String) -> Result<Option<Client>, Failure>;
+
+    async fn get_clients(&self) -> Result<Vec<Client>, Failure>;
+
+    async fn revoke_client(&self, client_id: &String) -> Result<bool, Failure>;
+
+    async fn get_max_token_lifetimes(&self) -> Result<TokenLifetimes, Failure>;
+}
+
+pub trait ClientSecretGenerator {
+    fn generate_client_id(&self) -> String;
+
+    fn generate_secret(&self) -> String;
+
+    fn hash_secret(&self, secret: &String) -> String;
+}
+
+pub trait ClientConfigProvider {
+    fn get_max_access_token_exp(&self) -> u64;
+
+    fn get_max_refresh_token_exp(&self) -> u64;
+}
+
+pub struct ClientInteractor<T, Y, U> {
+    client_repository: T,
+    secret_generator: Y,
+    config_provider: U,
+}
+
+impl<T, Y, U> ClientInteractor<T, Y, U>
+where
+    T: ClientRepository,
+    Y: ClientSecretGenerator,
+    U: ClientConfigProvider,
+{
+    pub fn new(
+        client_repository: T,
+        secret_generator: Y,
+        config_provider: U,
+    ) -> ClientInteractor<T, Y, U> {
+        return ClientInteractor {
+            client_repository,
+            secret_generator,
+            config_provider,
+        };
+    }
+
+    pub async fn create_client(
+        &self,
+        role: &UserRole,
+        model: &CreateClientModel,
+    ) -> Result<ClientCredentials, Failure> {
+        check_admin(role)?;
+        let name = model.name.trim().to_string();
+        if name.is_empty() {
+            return Err(get_invalid_client_name_error());
+        }
+        if model.grant_types.is_empty() {
+            return Err(get_grant_types_required_error());
+        }
+        let grant_types = model
+            .grant_types
+            .iter()
+            .map(|grant_type| {
+                GrantType::parse(grant_type).ok_or(get_invalid_grant_type_error(grant_type.clone()))
+            })
+            .collect::<Result<Vec<GrantType>, Failure>>()?;
+        if model.scopes.is_empty() {
+            return Err(get_scopes_required_error());
+        }
+        let scopes = model
+            .scopes
+            .iter()
+            .map(|scope| Scope::parse(scope).ok_or(get_invalid_scope_error(scope.clone())))
+            .collect::<Result<Vec<Scope>, Failure>>()?;
+        check_token_lifetime(
+            "accessTokenExp",
+            &model.access_token_exp,
+            self.config_provider.get_max_access_token_exp(),
+        )?;
+        check_token_lifetime(
+            "refreshTokenExp",
+            &model.refresh_token_exp,
+            self.config_provider.get_max_refresh_token_exp(),
+        )?;
+
+        let client_secret = self.secret_generator.generate_secret();
+        let client = Client {
+            id: self.secret_generator.generate_client_id(),
+            name,
+            secret_hash: self.secret_generator.hash_secret(&client_secret),
+            grant_types,
+            scopes,
+            access_token_exp: model.access_token_exp,
+            refresh_token_exp: model.refresh_token_exp,
+            revoked_at: None,
+            created_at: Utc::now(),
+        };
+        self.client_repository.save_client(&client).await?;
+
+        Ok(ClientCredentials {
+            client,
+            client_secret,
+        })
+    }
+
+    pub async fn get_clients(&self, role: &UserRole) -> Result<Vec<Client>, Failure> {
+        check_admin(role)?;
+
+        self.client_repository.get_clients().await
+    }
+
+    pub async fn revoke_client(&self, role: &UserRole, client_id: &String) -> Result<(), Failure> {
+        check_admin(role)?;
+        let revoked = self.client_repository.revoke_client(client_id).await?;
+
+        if revoked {
+            Ok(())
+        } else {
+            Err(get_client_not_found_error())
+        }
+    }
+}
+
+#[async_trait]
+impl<T, Y, U> ClientVerifier for ClientInteractor<T, Y, U>
+where
+    T: ClientRepository + Send + Sync,
+    Y: ClientSecretGenerator + Send + Sync,
+    U: ClientConfigProvider + Send + Sync,
+{
+    async fn verify_client(
+        &self,
+        client_id: &String,
+        client_secret: &String,
+    ) -> Result<ClientData, Failure> {
+        let client = match self.client_repository.get_client(client_id).await? {
+            Some(client) if client.revoked_at.is_none() => client,
+            _ => return Err(get_invalid_client_error()),
+        };
+        if self.secret_generator.hash_secret(client_secret) != client.secret_hash {
+            return Err(get_invalid_client_error());
+        }
+
+        Ok(client.into())
+    }
+
+    async fn is_client_active(&self, client_id: &String) -> Result<bool, Failure> {
+        let client = self.client_repository.get_client(client_id).await?;
+
+        Ok(matches!(client, Some(client) if client.revoked_at.is_none()))
+    }
+}
+
+// `None` falls back to the configured default lifetime.
+fn check_token_lifetime(field: &str, exp: &Option<u64>, max: u64) -> Result<(), Failure> {
+    return match exp {
+        Some(exp) if *exp == 0 || *exp > max => {
+            Err(get_invalid_token_lifetime_error(field.to_string(), max))
+        }
+        _ => Ok(()),
+    };
+}
+
+fn check_admin(role: &UserRole) -> Result<(), Failure> {
+    if role == &UserRole::ADMIN {
+        Ok(())
+    } else {
+        Err(get_admin_required_error())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mockall::predicate::*;
+    use mockall::*;
+
+    use super::*;
+
+    mock! {
+        ClientRepository {}
+
+        #[async_trait]
+        impl ClientRepository for ClientRepository {
+            async fn save_client(&self, client: &Client) -> Result<(), Failure>;
+
+            async fn get_client(&self, client_id: &String) -> Result<Option<Client>, Failure>;
+
+            async fn get_clients(&self) -> Result<Vec<Client>, Failure>;
+
+            async fn revoke_client(&self, client_id: &String) -> Result<bool, Failure>;
+
+            async fn get_max_token_lifetimes(&self) -> Result<TokenLifetimes, Failure>;
+        }
+    }
+
+    mock! {
+        ClientSecretGenerator {}
+
+        impl ClientSecretGenerator for ClientSecretGenerator {
+            fn generate_client_id(&self) -> String;
+
+            fn generate_secret(&self) -> String;
+
+            fn hash_secret(&self, secret: &String) -> String;
+        }
+    }
+
+    mock! {
+        ClientConfigProvider {}
+
+        impl ClientConfigProvider for ClientConfigProvider {
+            fn get_max_access_token_exp(&self) -> u64;
+
+            fn get_max_refresh_token_exp(&self) -> u64;
+        }
+    }
+
+    fn get_config_provider() -> MockClientConfigProvider {
+        let mut config_provider = MockClientConfigProvider::new();
+        config_provider
+            .expect_get_max_access_token_exp()
+            .returning(|| 3_600);
+        config_provider
+            .expect_get_max_refresh_token_exp()
+            .returning(|| 86_400);
+
+        return config_provider;
+    }
+
+    fn get_dependencies() -> (MockClientRepository, MockClientSecretGenerator) {
+        let repository = MockClientRepository::new();
+        let secret_generator = MockClientSecretGenerator::new();
+
+        return (repository, secret_generator);
+    }
+
+    fn get_client(revoked: bool) -> Client {
+        return Client {
+            id: "client_id".to_string(),
+            name: "web".to_string(),
+            secret_hash: "secret_hash".to_string(),
+            grant_types: vec![GrantType::Password, GrantType::RefreshToken],
+            scopes: vec![Scope::ProfileRead],
+            access_token_exp: Some(60),
+            refresh_token_exp: None,
+            revoked_at: if revoked { Some(Utc::now()) } else { None },
+            created_at: Utc::now(),
+        };
+    }
+
+    fn get_create_client_model(grant_types: Vec<&str>) -> CreateClientModel {
+        return CreateClientModel {
+            name: " web ".to_string(),
+            grant_types: grant_types.iter().map(|grant| grant.to_string()).collect(),
+            scopes: vec!["profile:read".to_string()],
+            access_token_exp: Some(60),
+            refresh_token_exp: None,
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_create_client_with_hashed_secret() {
+        let (mut repository, mut secret_generator) = get_dependencies();
+
+        secret_generator
+            .expect_generate_secret()
+            .return_once(|| "secret".to_string());
+        secret_generator
+            .expect_generate_client_id()
+            .return_once(|| "client_id".to_string());
+        secret_generator
+            .expect_hash_secret()
+            .with(eq("secret".to_string()))
+            .return_once(|_| "secret_hash".to_string());
+        repository
+            .expect_save_client()
+            .withf(|client| {
+                client.id == "client_id"
+                    && client.name == "web"
+                    && client.secret_hash == "secret_hash"
+                    && client.grant_types == vec![GrantType::Password]
+                    && client.scopes == vec![Scope::ProfileRead]
+            })
+            .return_once(|_| Ok(()));
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let result = interactor
+            .create_client(&UserRole::ADMIN, &get_create_client_model(vec!["password"]))
+            .await
+            .unwrap();
+
+        assert_eq!(result.client_secret, "secret".to_string());
+        assert_eq!(result.client.access_token_exp, Some(60));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_create_client_with_unknown_grant_type() {
+        let (mut repository, secret_generator) = get_dependencies();
+
+        repository.expect_save_client().never();
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let result = interactor
+            .create_client(
+                &UserRole::ADMIN,
+                &get_create_client_model(vec!["password", "implicit"]),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(get_invalid_grant_type_error("implicit".to_string()))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_not_create_client_without_grant_types() {
+        let (mut repository, secret_generator) = get_dependencies();
+
+        repository.expect_save_client().never();
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let result = interactor
+            .create_client(&UserRole::ADMIN, &get_create_client_model(vec![]))
+            .await;
+
+        assert_eq!(result, Err(get_grant_types_required_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_create_client_with_unknown_scope() {
+        let (mut repository, secret_generator) = get_dependencies();
+
+        repository.expect_save_client().never();
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+        let model = CreateClientModel {
+            scopes: vec!["profile:read".to_string(), "admin".to_string()],
+            ..get_create_client_model(vec!["password"])
+        };
+
+        let result = interactor.create_client(&UserRole::ADMIN, &model).await;
+
+        assert_eq!(result, Err(get_invalid_scope_error("admin".to_string())));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_create_client_with_invalid_token_lifetime() {
+        let (mut repository, secret_generator) = get_dependencies();
+
+        repository.expect_save_client().never();
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+        let mut expired = get_create_client_model(vec!["password"]);
+        expired.access_token_exp = Some(0);
+        let mut too_long = get_create_client_model(vec!["password"]);
+        too_long.refresh_token_exp = Some(86_401);
+        let mut overflowing = get_create_client_model(vec!["password"]);
+        overflowing.access_token_exp = Some(u64::MAX);
+
+        let expired_result = interactor.create_client(&UserRole::ADMIN, &expired).await;
+        let too_long_result = interactor.create_client(&UserRole::ADMIN, &too_long).await;
+        let overflowing_result = interactor
+            .create_client(&UserRole::ADMIN, &overflowing)
+            .await;
+
+        assert_eq!(
+            expired_result.err(),
+            Some(get_invalid_token_lifetime_error(
+                "accessTokenExp".to_string(),
+                3_600
+            ))
+        );
+        assert_eq!(
+            too_long_result.err(),
+            Some(get_invalid_token_lifetime_error(
+                "refreshTokenExp".to_string(),
+                86_400
+            ))
+        );
+        assert_eq!(
+            overflowing_result.err(),
+            Some(get_invalid_token_lifetime_error(
+                "accessTokenExp".to_string(),
+                3_600
+            ))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_allow_only_admins_to_manage_clients() {
+        let (mut repository, secret_generator) = get_dependencies();
+
+        repository.expect_save_client().never();
+        repository.expect_get_clients().never();
+        repository.expect_revoke_client().never();
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let create_result = interactor
+            .create_client(
+                &UserRole::MODERATOR,
+                &get_create_client_model(vec!["password"]),
+            )
+            .await;
+        let list_result = interactor.get_clients(&UserRole::USER).await;
+        let revoke_result = interactor
+            .revoke_client(&UserRole::MODERATOR, &"client_id".to_string())
+            .await;
+
+        assert_eq!(create_result, Err(get_admin_required_error()));
+        assert_eq!(list_result, Err(get_admin_required_error()));
+        assert_eq!(revoke_result, Err(get_admin_required_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_revoked_client_not_found() {
+        let (mut repository, secret_generator) = get_dependencies();
+
+        repository
+            .expect_revoke_client()
+            .with(eq("client_id".to_string()))
+            .return_once(|_| Ok(false));
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let result = interactor
+            .revoke_client(&UserRole::ADMIN, &"client_id".to_string())
+            .await;
+
+        assert_eq!(result, Err(get_client_not_found_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_verify_client_secret() {
+        let (mut repository, mut secret_generator) = get_dependencies();
+
+        repository
+            .expect_get_client()
+            .with(eq("client_id".to_string()))
+            .return_once(|_| Ok(Some(get_client(false))));
+        secret_generator
+            .expect_hash_secret()
+            .with(eq("secret".to_string()))
+            .return_once(|_| "secret_hash".to_string());
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let result = interactor
+            .verify_client(&"client_id".to_string(), &"secret".to_string())
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(ClientData {
+                id: "client_id".to_string(),
+                grant_types: vec![GrantType::Password, GrantType::RefreshToken],
+                scopes: vec![Scope::ProfileRead],
+                access_token_exp: Some(60),
+                refresh_token_exp: None,
+            })
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_invalid_client_secret() {
+        let (mut repository, mut secret_generator) = get_dependencies();
+
+        repository
+            .expect_get_client()
+            .return_once(|_| Ok(Some(get_client(false))));
+        secret_generator
+            .expect_hash_secret()
+            .return_once(|_| "other_hash".to_string());
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let result = interactor
+            .verify_client(&"client_id".to_string(), &"invalid".to_string())
+            .await;
+
+        assert_eq!(result, Err(get_invalid_client_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_revoked_client() {
+        let (mut repository, mut secret_generator) = get_dependencies();
+
+        repository
+            .expect_get_client()
+            .times(2)
+            .returning(|_| Ok(Some(get_client(true))));
+        secret_generator.expect_hash_secret().never();
+
+        let interactor = ClientInteractor::new(repository, secret_generator, get_config_provider());
+
+        let verify_result = interactor
+            .verify_client(&"client_id".to_string(), &"secret".to_string())
+            .await;
+        let active_result = interactor.is_client_active(&"client_id".to_string()).await;
+
+        assert_eq!(verify_result, Err(get_invalid_client_error()));
+        assert_eq!(active_result, Ok(false));
+    }
+}
@@ -0,0 +1,63 @@
+use nanoid::nanoid;
+use ring::digest::{digest, SHA256};
+
+use crate::features::client::interactors::client_interactor::ClientSecretGenerator;
+
+const CLIENT_ID_LENGTH: usize = 24;
+const CLIENT_SECRET_LENGTH: usize = 48;
+
+pub struct RandomClientSecretGenerator;
+
+impl RandomClientSecretGenerator {
+    pub fn new() -> RandomClientSecretGenerator {
+        return RandomClientSecretGenerator {};
+    }
+}
+
+impl ClientSecretGenerator for RandomClientSecretGenerator {
+    fn generate_client_id(&self) -> String {
+        return nanoid!(CLIENT_ID_LENGTH);
+    }
+
+    fn generate_secret(&self) -> String {
+        return nanoid!(CLIENT_SECRET_LENGTH);
+    }
+
+    // Secrets are long random strings, so unlike passwords they do not need a slow hash.
+    fn hash_secret(&self, secret: &String) -> String {
+        return digest(&SHA256, secret.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_generate_unique_secrets() {
+        let generator = RandomClientSecretGenerator::new();
+
+        let first = generator.generate_secret();
+        let second = generator.generate_secret();
+
+        assert_ne!(first, second);
+        assert_eq!(first.len(), CLIENT_SECRET_LENGTH);
+        assert_eq!(generator.generate_client_id().len(), CLIENT_ID_LENGTH);
+    }
+
+    #[test]
+    fn should_hash_secret_deterministically() {
+        let generator = RandomClientSecretGenerator::new();
+
+        let first = generator.hash_secret(&"secret".to_string());
+        let second = generator.hash_secret(&"secret".to_string());
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 64);
+        assert_ne!(first, "secret".to_string());
+    }
+}
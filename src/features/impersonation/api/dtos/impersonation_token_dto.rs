@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+use crate::features::impersonation::domain::impersonation_token::ImpersonationToken;
+
+// Carries no refresh token, impersonation ends when the access token expires.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ImpersonationTokenDto {
+    pub access_token: String,
+    pub access_token_exp: u64,
+}
+
+impl From<ImpersonationToken> for ImpersonationTokenDto {
+    fn from(token: ImpersonationToken) -> Self {
+        ImpersonationTokenDto {
+            access_token: token.access_token,
+            access_token_exp: token.access_token_exp,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use actix_web::{
+    post,
+    web::{self, ServiceConfig},
+    HttpResponse, Responder,
+};
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    features::{
+        auth::{
+            api::{
+                auth_middleware::require_role,
+                scoped_user::{AdminWrite, ScopedUser},
+            },
+            domain::user_role::UserRole,
+            infrastructure::jwt_token_provider::JwtTokenProvider,
+        },
+        impersonation::{
+            infrastructure::impersonation_audit_log_impl::ImpersonationAuditLogImpl,
+            interactors::impersonation_interactor::ImpersonationInteractor,
+        },
+        profile::infrastructure::profile_repository_impl::ProfileRepositoryImpl,
+    },
+};
+
+use super::dtos::impersonation_token_dto::ImpersonationTokenDto;
+
+type Interactor =
+    ImpersonationInteractor<ProfileRepositoryImpl, JwtTokenProvider, ImpersonationAuditLogImpl>;
+
+pub fn configure_impersonation_controller(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/admin/users")
+            .wrap(require_role(UserRole::ADMIN))
+            .service(impersonate),
+    );
+}
+
+#[post("/{id}/impersonate")]
+async fn impersonate(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<AdminWrite>,
+    path: web::Path<i64>,
+) -> impl Responder {
+    let result = interactor
+        .impersonate(&current_user, &path.into_inner())
+        .await;
+    match result {
+        Ok(token) => HttpResponse::Ok().json(ImpersonationTokenDto::from(token)),
+        Err(err) => handle_failure(err),
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImpersonatedRequest {
+    pub actor_id: i64,
+    pub user_id: i64,
+    pub method: String,
+    pub path: String,
+}
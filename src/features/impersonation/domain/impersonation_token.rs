@@ -0,0 +1,5 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImpersonationToken {
+    pub access_token: String,
+    pub access_token_exp: u64,
+}
@@ -0,0 +1,2 @@
+pub mod impersonated_request;
+pub mod impersonation_token;
@@ -0,0 +1,37 @@
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_impersonation_not_allowed_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.impersonation_not_allowed".to_string(),
+        message: "Admins can not be impersonated".to_string(),
+    };
+}
+
+pub fn get_impersonation_requires_client_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.impersonation_requires_client".to_string(),
+        message: "Impersonation is not available with personal access tokens".to_string(),
+    };
+}
+
+pub fn get_not_allowed_while_impersonating_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.not_allowed_while_impersonating".to_string(),
+        message: "Not allowed while impersonating a user".to_string(),
+    };
+}
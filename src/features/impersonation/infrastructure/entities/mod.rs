@@ -0,0 +1 @@
+pub mod new_impersonation_audit_entry;
@@ -0,0 +1,41 @@
+use crate::{
+    features::impersonation::domain::impersonated_request::ImpersonatedRequest,
+    schema::impersonation_audit_log,
+};
+
+const TOKEN_ISSUED_EVENT: &str = "token_issued";
+const REQUEST_EVENT: &str = "request";
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "impersonation_audit_log"]
+pub struct NewImpersonationAuditEntry {
+    pub actor_id: i64,
+    pub profile_id: i64,
+    pub event: String,
+    pub method: Option<String>,
+    pub path: Option<String>,
+}
+
+impl NewImpersonationAuditEntry {
+    pub fn token_issued(actor_id: &i64, user_id: &i64) -> NewImpersonationAuditEntry {
+        return NewImpersonationAuditEntry {
+            actor_id: *actor_id,
+            profile_id: *user_id,
+            event: TOKEN_ISSUED_EVENT.to_string(),
+            method: None,
+            path: None,
+        };
+    }
+}
+
+impl From<&ImpersonatedRequest> for NewImpersonationAuditEntry {
+    fn from(request: &ImpersonatedRequest) -> Self {
+        return NewImpersonationAuditEntry {
+            actor_id: request.actor_id,
+            profile_id: request.user_id,
+            event: REQUEST_EVENT.to_string(),
+            method: Some(request.method.clone()),
+            path: Some(request.path.clone()),
+        };
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::impersonation::{
+        domain::impersonated_request::ImpersonatedRequest,
+        errors::impersonation_errors::get_db_connection_error,
+        interactors::impersonation_interactor::ImpersonationAuditLog,
+    },
+    schema::impersonation_audit_log,
+};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+use super::entities::new_impersonation_audit_entry::NewImpersonationAuditEntry;
+
+pub struct ImpersonationAuditLogImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ImpersonationAuditLogImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> ImpersonationAuditLogImpl {
+        return ImpersonationAuditLogImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+
+    fn insert_entry(&self, entry: &NewImpersonationAuditEntry) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::insert_into(impersonation_audit_log::table)
+            .values(entry)
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl ImpersonationAuditLog for ImpersonationAuditLogImpl {
+    async fn record_token_issued(&self, actor_id: &i64, user_id: &i64) -> Result<(), Failure> {
+        self.insert_entry(&NewImpersonationAuditEntry::token_issued(actor_id, user_id))
+    }
+
+    async fn record_request(&self, request: &ImpersonatedRequest) -> Result<(), Failure> {
+        self.insert_entry(&NewImpersonationAuditEntry::from(request))
+    }
+}
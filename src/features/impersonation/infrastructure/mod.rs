@@ -0,0 +1,2 @@
+pub mod entities;
+pub mod impersonation_audit_log_impl;
@@ -0,0 +1,257 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::domain::{current_user_data::CurrentUserData, scope::Scope, user_role::UserRole},
+        impersonation::{
+            domain::{
+                impersonated_request::ImpersonatedRequest, impersonation_token::ImpersonationToken,
+            },
+            errors::impersonation_errors::{
+                get_impersonation_not_allowed_error, get_impersonation_requires_client_error,
+            },
+        },
+    },
+};
+
+use async_trait::async_trait;
+
+pub trait ImpersonationTokenProvider {
+    // Issues an access token for the user that names the admin as its actor. No session is
+    // created, so the token can not be refreshed.
+    fn generate_impersonation_token(
+        &self,
+        user_id: &i64,
+        role: &UserRole,
+        actor_id: &i64,
+        client_id: &String,
+        scopes: &[Scope],
+    ) -> Result<ImpersonationToken, Failure>;
+}
+
+#[async_trait]
+pub trait ImpersonatedUserProvider {
+    async fn get_user_role(&self, user_id: &i64) -> Result<UserRole, Failure>;
+}
+
+#[async_trait]
+pub trait ImpersonationAuditLog {
+    async fn record_token_issued(&self, actor_id: &i64, user_id: &i64) -> Result<(), Failure>;
+
+    async fn record_request(&self, request: &ImpersonatedRequest) -> Result<(), Failure>;
+}
+
+pub struct ImpersonationInteractor<T, Y, U> {
+    user_provider: T,
+    token_provider: Y,
+    audit_log: U,
+}
+
+impl<T, Y, U> ImpersonationInteractor<T, Y, U>
+where
+    T: ImpersonatedUserProvider,
+    Y: ImpersonationTokenProvider,
+    U: ImpersonationAuditLog,
+{
+    pub fn new(
+        user_provider: T,
+        token_provider: Y,
+        audit_log: U,
+    ) -> ImpersonationInteractor<T, Y, U> {
+        ImpersonationInteractor {
+            user_provider,
+            token_provider,
+            audit_log,
+        }
+    }
+
+    pub async fn impersonate(
+        &self,
+        actor: &CurrentUserData,
+        user_id: &i64,
+    ) -> Result<ImpersonationToken, Failure> {
+        // The token is issued for the client the admin is signed in with, so it is revoked
+        // together with that client.
+        let client_id = match &actor.client_id {
+            Some(client_id) => client_id,
+            None => return Err(get_impersonation_requires_client_error()),
+        };
+        let role = self.user_provider.get_user_role(user_id).await?;
+        // Acting as another admin would hide who made admin changes.
+        if role == UserRole::ADMIN {
+            return Err(get_impersonation_not_allowed_error());
+        }
+
+        let token = self.token_provider.generate_impersonation_token(
+            user_id,
+            &role,
+            &actor.id,
+            client_id,
+            &actor.scopes,
+        )?;
+        // Like impersonated requests, a token that can not be audited is not handed out.
+        self.audit_log
+            .record_token_issued(&actor.id, user_id)
+            .await?;
+
+        Ok(token)
+    }
+
+    pub async fn record_request(&self, request: &ImpersonatedRequest) -> Result<(), Failure> {
+        self.audit_log.record_request(request).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mockall::{mock, predicate};
+
+    use crate::features::impersonation::errors::impersonation_errors::get_db_connection_error;
+
+    use super::*;
+
+    mock! {
+        ImpersonatedUserProvider {}
+
+        #[async_trait]
+        impl ImpersonatedUserProvider for ImpersonatedUserProvider {
+            async fn get_user_role(&self, user_id: &i64) -> Result<UserRole, Failure>;
+        }
+    }
+
+    mock! {
+        ImpersonationTokenProvider {}
+
+        impl ImpersonationTokenProvider for ImpersonationTokenProvider {
+            fn generate_impersonation_token<'a>(
+                &self,
+                user_id: &i64,
+                role: &UserRole,
+                actor_id: &i64,
+                client_id: &String,
+                scopes: &'a [Scope],
+            ) -> Result<ImpersonationToken, Failure>;
+        }
+    }
+
+    mock! {
+        ImpersonationAuditLog {}
+
+        #[async_trait]
+        impl ImpersonationAuditLog for ImpersonationAuditLog {
+            async fn record_token_issued(&self, actor_id: &i64, user_id: &i64) -> Result<(), Failure>;
+
+            async fn record_request(&self, request: &ImpersonatedRequest) -> Result<(), Failure>;
+        }
+    }
+
+    fn get_admin(client_id: Option<String>) -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role: UserRole::ADMIN,
+            session_id: Some("session_id".to_string()),
+            actor_id: None,
+            client_id,
+            scopes: vec![Scope::ProfileRead],
+        };
+    }
+
+    fn get_token() -> ImpersonationToken {
+        return ImpersonationToken {
+            access_token: "access_token".to_string(),
+            access_token_exp: 900,
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_issue_token_naming_admin_as_actor() {
+        let mut user_provider = MockImpersonatedUserProvider::new();
+        user_provider
+            .expect_get_user_role()
+            .with(predicate::eq(2))
+            .return_once(|_| Ok(UserRole::USER));
+        let mut token_provider = MockImpersonationTokenProvider::new();
+        token_provider
+            .expect_generate_impersonation_token()
+            .withf(|user_id, role, actor_id, client_id, scopes| {
+                *user_id == 2
+                    && role == &UserRole::USER
+                    && *actor_id == 1
+                    && client_id == "client_id"
+                    && scopes == [Scope::ProfileRead]
+            })
+            .times(1)
+            .return_once(|_, __, ___, ____, _____| Ok(get_token()));
+        let mut audit_log = MockImpersonationAuditLog::new();
+        audit_log
+            .expect_record_token_issued()
+            .with(predicate::eq(1), predicate::eq(2))
+            .times(1)
+            .return_once(|_, __| Ok(()));
+        let interactor = ImpersonationInteractor::new(user_provider, token_provider, audit_log);
+
+        let result = interactor
+            .impersonate(&get_admin(Some("client_id".to_string())), &2)
+            .await;
+
+        assert_eq!(result, Ok(get_token()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_hand_out_token_that_can_not_be_audited() {
+        let mut user_provider = MockImpersonatedUserProvider::new();
+        user_provider
+            .expect_get_user_role()
+            .return_once(|_| Ok(UserRole::USER));
+        let mut token_provider = MockImpersonationTokenProvider::new();
+        token_provider
+            .expect_generate_impersonation_token()
+            .return_once(|_, __, ___, ____, _____| Ok(get_token()));
+        let mut audit_log = MockImpersonationAuditLog::new();
+        audit_log
+            .expect_record_token_issued()
+            .return_once(|_, __| Err(get_db_connection_error()));
+        let interactor = ImpersonationInteractor::new(user_provider, token_provider, audit_log);
+
+        let result = interactor
+            .impersonate(&get_admin(Some("client_id".to_string())), &2)
+            .await;
+
+        assert_eq!(result, Err(get_db_connection_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_impersonate_admins() {
+        let mut user_provider = MockImpersonatedUserProvider::new();
+        user_provider
+            .expect_get_user_role()
+            .return_once(|_| Ok(UserRole::ADMIN));
+        let mut token_provider = MockImpersonationTokenProvider::new();
+        token_provider.expect_generate_impersonation_token().never();
+        let interactor = ImpersonationInteractor::new(
+            user_provider,
+            token_provider,
+            MockImpersonationAuditLog::new(),
+        );
+
+        let result = interactor
+            .impersonate(&get_admin(Some("client_id".to_string())), &2)
+            .await;
+
+        assert_eq!(result, Err(get_impersonation_not_allowed_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_require_client_token() {
+        let mut user_provider = MockImpersonatedUserProvider::new();
+        user_provider.expect_get_user_role().never();
+        let interactor = ImpersonationInteractor::new(
+            user_provider,
+            MockImpersonationTokenProvider::new(),
+            MockImpersonationAuditLog::new(),
+        );
+
+        let result = interactor.impersonate(&get_admin(None), &2).await;
+
+        assert_eq!(result, Err(get_impersonation_requires_client_error()));
+    }
+}
@@ -0,0 +1 @@
+pub mod impersonation_interactor;
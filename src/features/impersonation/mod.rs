@@ -0,0 +1,5 @@
+pub mod api;
+pub mod domain;
+pub mod errors;
+pub mod infrastructure;
+pub mod interactors;
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MagicLinkConsumeDto {
+    pub code: String,
+    pub client_id: String,
+    pub client_secret: String,
+}
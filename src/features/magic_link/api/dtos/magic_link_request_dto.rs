@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MagicLinkRequestDto {
+    pub email: String,
+    pub client_id: String,
+    pub client_secret: String,
+}
@@ -0,0 +1,2 @@
+pub mod magic_link_consume_dto;
+pub mod magic_link_request_dto;
@@ -0,0 +1,153 @@
+use actix_web::{
+    http::StatusCode,
+    post,
+    web::{self, ServiceConfig},
+    HttpRequest, HttpResponse, Responder,
+};
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    config::{
+        auth_config::AuthConfig, client_config::ClientConfig, magic_link_config::MagicLinkConfig,
+    },
+    features::{
+        auth::{
+            api::{
+                dtos::{mfa_required_dto::MfaRequiredDto, tokens_pair_dto::TokensPairDto},
+                request_info::get_device_info,
+            },
+            domain::login_result::LoginResult,
+            infrastructure::{
+                auth_data_repository_impl::AuthDataRepositoryImpl,
+                jwt_token_provider::JwtTokenProvider, login_throttle_impl::LoginThrottleImpl,
+                password_manager_impl::PasswordManagerImpl,
+                session_storage_impl::SessionStorageImpl,
+            },
+            interactors::auth_interactor::AuthInteractor,
+        },
+        auth_event::infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
+        },
+        magic_link::{
+            infrastructure::{
+                magic_link_storage_impl::MagicLinkStorageImpl,
+                magic_link_user_repository_impl::MagicLinkUserRepositoryImpl,
+            },
+            interactors::magic_link_interactor::MagicLinkInteractor,
+        },
+        mailer::mailer::Mailer,
+        mfa::{
+            infrastructure::totp_repository_impl::TotpRepositoryImpl,
+            interactors::mfa_interactor::MfaInteractor,
+            utils::{
+                recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+            },
+        },
+        personal_access_token::{
+            infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+            interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+            utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+        },
+        profile::{
+            infrastructure::profile_repository_impl::ProfileRepositoryImpl,
+            utils::code_generator::VerificationCodeGenerator,
+        },
+    },
+};
+
+use super::dtos::{
+    magic_link_consume_dto::MagicLinkConsumeDto, magic_link_request_dto::MagicLinkRequestDto,
+};
+
+type Interactor = MagicLinkInteractor<
+    MagicLinkUserRepositoryImpl,
+    VerificationCodeGenerator,
+    MagicLinkStorageImpl,
+    Mailer,
+    ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+    AuthInteractor<
+        PasswordManagerImpl,
+        JwtTokenProvider,
+        AuthDataRepositoryImpl,
+        ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+        SessionStorageImpl,
+        MfaInteractor<
+            TotpRepositoryImpl,
+            TotpGenerator,
+            RandomRecoveryCodeGenerator,
+            ProfileRepositoryImpl,
+        >,
+        PersonalAccessTokenInteractor<
+            PersonalAccessTokenRepositoryImpl,
+            RandomPersonalAccessTokenGenerator,
+        >,
+        LoginThrottleImpl,
+        AuthConfig,
+        AuthEventRepositoryImpl,
+    >,
+    MagicLinkConfig,
+>;
+
+// Has to be configured before the auth controller, whose `/auth` scope would otherwise
+// swallow these routes.
+pub fn configure_magic_link_controller(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/auth/magic-link")
+            .service(send_magic_link)
+            .service(consume_magic_link),
+    );
+}
+
+#[post("")]
+async fn send_magic_link(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<MagicLinkRequestDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    if let Err(failure) = interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        return handle_failure(failure);
+    }
+
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .send_magic_link(&dto.email, &device_info.ip)
+        .await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/consume")]
+async fn consume_magic_link(
+    interactor: web::Data<Interactor>,
+    dto: web::Form<MagicLinkConsumeDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let device_info = get_device_info(&request);
+    let result = interactor.login(&client, &dto.code, &device_info).await;
+
+    match result {
+        Ok(LoginResult::Authenticated(tokens)) => {
+            HttpResponse::Ok().json(TokensPairDto::from(tokens))
+        }
+        Ok(LoginResult::MfaRequired(mfa_token)) => {
+            HttpResponse::Ok().json(MfaRequiredDto { mfa_token })
+        }
+        Err(err) => handle_failure(err),
+    }
+}
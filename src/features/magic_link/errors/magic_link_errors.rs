@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_redis_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to redis".to_string(),
+    };
+}
+
+pub fn get_invalid_magic_link_code() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.invalid_magic_link".to_string(),
+        message: "Login link is invalid or expired".to_string(),
+    };
+}
+
+pub fn get_too_many_magic_links_error(retry_after: u64) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("retry_after".to_string(), retry_after.to_string());
+
+    return Failure {
+        error_type: FailureType::TooManyRequests,
+        code: "error.too_many_magic_links".to_string(),
+        message: format!(
+            "Too many login links requested, retry in {} seconds",
+            retry_after
+        ),
+        args: Some(args),
+    };
+}
@@ -0,0 +1 @@
+pub mod magic_link_errors;
@@ -0,0 +1,133 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    config::magic_link_config::MagicLinkConfig,
+    features::magic_link::{
+        errors::magic_link_errors::{get_invalid_magic_link_code, get_redis_connection_error},
+        interactors::magic_link_interactor::MagicLinkStorage,
+    },
+};
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::{RedisError, Script};
+
+// Replaces the previous code of the email, so only the latest link can be used.
+const SAVE_CODE_SCRIPT: &str = r"
+local previous = redis.call('GET', KEYS[1])
+if previous then
+    redis.call('DEL', ARGV[3] .. previous)
+end
+redis.call('SET', KEYS[1], ARGV[2], 'EX', ARGV[4])
+redis.call('SET', KEYS[2], ARGV[1], 'EX', ARGV[4])
+return 1
+";
+
+// Returns the seconds until the next link may be sent, 0 when this one may go. The email
+// lock is checked before the IP is counted, so resends of a locked email do not add up.
+const REGISTER_SEND_SCRIPT: &str = r"
+local lock = redis.call('TTL', KEYS[1])
+if lock > 0 then
+    return lock
+end
+if KEYS[2] then
+    local sends = redis.call('INCR', KEYS[2])
+    if sends == 1 then
+        redis.call('EXPIRE', KEYS[2], ARGV[2])
+    end
+    if sends > tonumber(ARGV[3]) then
+        return math.max(redis.call('TTL', KEYS[2]), 1)
+    end
+end
+redis.call('SET', KEYS[1], 1, 'EX', ARGV[1])
+return 0
+";
+
+const MAGIC_LINK_KEY_PREFIX: &str = "magic_link:";
+
+pub struct MagicLinkStorageImpl {
+    redis_connection: MultiplexedConnection,
+    config: MagicLinkConfig,
+}
+
+impl MagicLinkStorageImpl {
+    pub fn new(redis: MultiplexedConnection, config: MagicLinkConfig) -> MagicLinkStorageImpl {
+        return MagicLinkStorageImpl {
+            redis_connection: redis,
+            config,
+        };
+    }
+
+    fn get_key(code: &String) -> String {
+        return format!("{}{}", MAGIC_LINK_KEY_PREFIX, code);
+    }
+
+    fn get_email_key(email: &String) -> String {
+        return format!("magic_link_email:{}", email.to_lowercase());
+    }
+
+    fn get_email_lock_key(email: &String) -> String {
+        return format!("magic_link_lock:{}", email.to_lowercase());
+    }
+
+    fn get_ip_sends_key(ip: &String) -> String {
+        return format!("magic_link_sends_ip:{}", ip);
+    }
+}
+
+#[async_trait]
+impl MagicLinkStorage for MagicLinkStorageImpl {
+    async fn save_magic_link_code(&self, email: &String, code: &String) -> Result<(), Failure> {
+        let result: Result<(), RedisError> = Script::new(SAVE_CODE_SCRIPT)
+            .key(MagicLinkStorageImpl::get_email_key(email))
+            .key(MagicLinkStorageImpl::get_key(code))
+            .arg(email)
+            .arg(code)
+            .arg(MAGIC_LINK_KEY_PREFIX)
+            .arg(self.config.magic_link_exp)
+            .invoke_async(&mut self.redis_connection.clone())
+            .await;
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn take_email_by_magic_link_code(&self, code: &String) -> Result<String, Failure> {
+        let result: Result<Option<String>, RedisError> = redis::cmd("GETDEL")
+            .arg(MagicLinkStorageImpl::get_key(code))
+            .query_async(&mut self.redis_connection.clone())
+            .await;
+
+        return match result {
+            Ok(Some(email)) => Ok(email),
+            Ok(None) => Err(get_invalid_magic_link_code()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn register_send(
+        &self,
+        email: &String,
+        ip: &Option<String>,
+    ) -> Result<Option<u64>, Failure> {
+        let script = Script::new(REGISTER_SEND_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        invocation.key(MagicLinkStorageImpl::get_email_lock_key(email));
+        if let Some(ip) = ip {
+            invocation.key(MagicLinkStorageImpl::get_ip_sends_key(ip));
+        }
+
+        let result: Result<u64, RedisError> = invocation
+            .arg(self.config.resend_delay)
+            .arg(self.config.ip_sends_window)
+            .arg(self.config.max_ip_sends)
+            .invoke_async(&mut self.redis_connection.clone())
+            .await;
+
+        return match result {
+            Ok(0) => Ok(None),
+            Ok(retry_after) => Ok(Some(retry_after)),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+}
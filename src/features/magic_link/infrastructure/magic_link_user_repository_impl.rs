@@ -0,0 +1,64 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::domain::auth_data::AuthData,
+        magic_link::{
+            errors::magic_link_errors::get_db_connection_error,
+            interactors::magic_link_interactor::MagicLinkUserRepository,
+        },
+        profile::infrastructure::entities::profile_entity::ProfileEntity,
+    },
+    schema::profile,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+pub struct MagicLinkUserRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl MagicLinkUserRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> MagicLinkUserRepositoryImpl {
+        return MagicLinkUserRepositoryImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl MagicLinkUserRepository for MagicLinkUserRepositoryImpl {
+    async fn get_user_by_email(&self, email: &String) -> Result<Option<AuthData>, Failure> {
+        let connection = self.get_connection()?;
+        let result = profile::table
+            .filter(profile::email.eq(email))
+            .first::<ProfileEntity>(&connection)
+            .optional();
+
+        return match result {
+            Ok(entity) => Ok(entity.map(|entity| entity.into())),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn confirm_email(&self, user_id: &i64) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::update(profile::table.find(user_id))
+            .filter(profile::email_confirmed_at.is_null())
+            .set(profile::email_confirmed_at.eq(Utc::now()))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+}
@@ -0,0 +1,2 @@
+pub mod magic_link_storage_impl;
+pub mod magic_link_user_repository_impl;
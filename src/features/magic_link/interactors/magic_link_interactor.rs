@@ -0,0 +1,497 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::{
+            domain::{
+                auth_data::AuthData, client_data::ClientData, device_info::DeviceInfo,
+                login_result::LoginResult,
+            },
+            interactors::auth_interactor::ClientVerifier,
+        },
+        magic_link::errors::magic_link_errors::{
+            get_invalid_magic_link_code, get_too_many_magic_links_error,
+        },
+        oidc::interactors::oidc_interactor::IdentityAuthenticator,
+        profile::interactors::profile_interactor::CodeGenerator,
+    },
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+#[async_trait]
+pub trait MagicLinkUserRepository {
+    async fn get_user_by_email(&self, email: &String) -> Result<Option<AuthData>, Failure>;
+
+    async fn confirm_email(&self, user_id: &i64) -> Result<(), Failure>;
+}
+
+#[async_trait]
+pub trait MagicLinkStorage {
+    // Replaces the previous code of the email, only the latest link stays valid.
+    async fn save_magic_link_code(&self, email: &String, code: &String) -> Result<(), Failure>;
+
+    // Magic links can be used only once.
+    async fn take_email_by_magic_link_code(&self, code: &String) -> Result<String, Failure>;
+
+    // Counted before the email is looked up, so unknown emails are throttled the same way.
+    // Returns the seconds until the next link may be sent, `None` when this one may go.
+    async fn register_send(
+        &self,
+        email: &String,
+        ip: &Option<String>,
+    ) -> Result<Option<u64>, Failure>;
+}
+
+#[async_trait]
+pub trait MagicLinkMailer {
+    async fn send_magic_link(&self, email: &String, link: &String) -> Result<(), Failure>;
+}
+
+pub trait MagicLinkConfigProvider {
+    fn get_magic_link(&self, code: &String) -> String;
+}
+
+pub struct MagicLinkInteractor<T, Y, U, I, J, K, L> {
+    user_repository: T,
+    code_generator: Y,
+    magic_link_storage: U,
+    mailer: I,
+    client_verifier: J,
+    identity_authenticator: K,
+    config: L,
+}
+
+impl<T, Y, U, I, J, K, L> MagicLinkInteractor<T, Y, U, I, J, K, L>
+where
+    T: MagicLinkUserRepository,
+    Y: CodeGenerator,
+    U: MagicLinkStorage,
+    I: MagicLinkMailer,
+    J: ClientVerifier,
+    K: IdentityAuthenticator,
+    L: MagicLinkConfigProvider,
+{
+    pub fn new(
+        user_repository: T,
+        code_generator: Y,
+        magic_link_storage: U,
+        mailer: I,
+        client_verifier: J,
+        identity_authenticator: K,
+        config: L,
+    ) -> MagicLinkInteractor<T, Y, U, I, J, K, L> {
+        return MagicLinkInteractor {
+            user_repository,
+            code_generator,
+            magic_link_storage,
+            mailer,
+            client_verifier,
+            identity_authenticator,
+            config,
+        };
+    }
+
+    pub async fn verify_client(
+        &self,
+        client_id: &String,
+        client_secret: &String,
+    ) -> Result<ClientData, Failure> {
+        return self
+            .client_verifier
+            .verify_client(client_id, client_secret)
+            .await;
+    }
+
+    pub async fn send_magic_link(
+        &self,
+        email: &String,
+        ip: &Option<String>,
+    ) -> Result<(), Failure> {
+        let retry_after = self.magic_link_storage.register_send(email, ip).await?;
+        if let Some(retry_after) = retry_after {
+            return Err(get_too_many_magic_links_error(retry_after));
+        }
+        let user = match self.user_repository.get_user_by_email(email).await? {
+            Some(user) => user,
+            // Do not reveal whether an account exists for the given email.
+            None => return Ok(()),
+        };
+        let code = self.code_generator.generate().await;
+        self.magic_link_storage
+            .save_magic_link_code(&user.email, &code)
+            .await?;
+        let link = self.config.get_magic_link(&code);
+        return self.mailer.send_magic_link(&user.email, &link).await;
+    }
+
+    pub async fn login(
+        &self,
+        client: &ClientData,
+        code: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<LoginResult, Failure> {
+        let email = self
+            .magic_link_storage
+            .take_email_by_magic_link_code(code)
+            .await?;
+        let mut user = match self.user_repository.get_user_by_email(&email).await? {
+            Some(user) => user,
+            None => return Err(get_invalid_magic_link_code()),
+        };
+        // The link could only be opened from the mailbox, which proves the email is owned.
+        if user.verified_at.is_none() {
+            self.user_repository.confirm_email(&user.id).await?;
+            user.verified_at = Some(Utc::now());
+        }
+
+        return self
+            .identity_authenticator
+            .login_with_identity(client, &user, device_info)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mockall::{mock, predicate};
+
+    use crate::features::auth::domain::{
+        grant_type::GrantType, scope::Scope, tokens_pair::TokensPair, user_role::UserRole,
+    };
+
+    use super::*;
+
+    mock! {
+        MagicLinkUserRepository {}
+
+        #[async_trait]
+        impl MagicLinkUserRepository for MagicLinkUserRepository {
+            async fn get_user_by_email(&self, email: &String) -> Result<Option<AuthData>, Failure>;
+
+            async fn confirm_email(&self, user_id: &i64) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        CodeGenerator {}
+
+        #[async_trait]
+        impl CodeGenerator for CodeGenerator {
+            async fn generate(&self) -> String;
+        }
+    }
+
+    mock! {
+        MagicLinkStorage {}
+
+        #[async_trait]
+        impl MagicLinkStorage for MagicLinkStorage {
+            async fn save_magic_link_code(&self, email: &String, code: &String) -> Result<(), Failure>;
+
+            async fn take_email_by_magic_link_code(&self, code: &String) -> Result<String, Failure>;
+
+            async fn register_send(&self, email: &String, ip: &Option<String>) -> Result<Option<u64>, Failure>;
+        }
+    }
+
+    mock! {
+        MagicLinkMailer {}
+
+        #[async_trait]
+        impl MagicLinkMailer for MagicLinkMailer {
+            async fn send_magic_link(&self, email: &String, link: &String) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        ClientVerifier {}
+
+        #[async_trait]
+        impl ClientVerifier for ClientVerifier {
+            async fn verify_client(
+                &self,
+                client_id: &String,
+                client_secret: &String,
+            ) -> Result<ClientData, Failure>;
+
+            async fn is_client_active(&self, client_id: &String) -> Result<bool, Failure>;
+        }
+    }
+
+    mock! {
+        IdentityAuthenticator {}
+
+        #[async_trait]
+        impl IdentityAuthenticator for IdentityAuthenticator {
+            async fn login_with_identity(
+                &self,
+                client: &ClientData,
+                user: &AuthData,
+                device_info: &DeviceInfo,
+            ) -> Result<LoginResult, Failure>;
+        }
+    }
+
+    mock! {
+        MagicLinkConfigProvider {}
+
+        impl MagicLinkConfigProvider for MagicLinkConfigProvider {
+            fn get_magic_link(&self, code: &String) -> String;
+        }
+    }
+
+    fn get_client() -> ClientData {
+        return ClientData {
+            id: "client_id".to_string(),
+            grant_types: vec![GrantType::Password],
+            scopes: vec![Scope::ProfileRead],
+            access_token_exp: None,
+            refresh_token_exp: None,
+        };
+    }
+
+    fn get_user(verified: bool) -> AuthData {
+        return AuthData {
+            id: 1,
+            username: "username".to_string(),
+            email: "email@test.com".to_string(),
+            password: "password".to_string(),
+            user_role: UserRole::USER,
+            verified_at: if verified { Some(Utc::now()) } else { None },
+        };
+    }
+
+    fn get_login_result() -> LoginResult {
+        return LoginResult::Authenticated(TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
+        });
+    }
+
+    fn get_storage_with_code() -> MockMagicLinkStorage {
+        let mut storage = MockMagicLinkStorage::new();
+        storage
+            .expect_take_email_by_magic_link_code()
+            .with(predicate::eq("code".to_string()))
+            .times(1)
+            .return_once(|_| Ok("email@test.com".to_string()));
+
+        return storage;
+    }
+
+    fn get_identity_authenticator() -> MockIdentityAuthenticator {
+        let mut identity_authenticator = MockIdentityAuthenticator::new();
+        identity_authenticator
+            .expect_login_with_identity()
+            .with(
+                predicate::eq(get_client()),
+                predicate::function(|user: &AuthData| user.id == 1 && user.verified_at.is_some()),
+                predicate::always(),
+            )
+            .return_once(|_, __, ___| Ok(get_login_result()));
+
+        return identity_authenticator;
+    }
+
+    #[actix_rt::test]
+    async fn should_send_magic_link() {
+        let mut repository = MockMagicLinkUserRepository::new();
+        repository
+            .expect_get_user_by_email()
+            .with(predicate::eq("email@test.com".to_string()))
+            .return_once(|_| Ok(Some(get_user(true))));
+        let mut code_generator = MockCodeGenerator::new();
+        code_generator
+            .expect_generate()
+            .return_once(|| "code".to_string());
+        let mut storage = MockMagicLinkStorage::new();
+        storage
+            .expect_register_send()
+            .with(
+                predicate::eq("email@test.com".to_string()),
+                predicate::eq(Some("127.0.0.1".to_string())),
+            )
+            .times(1)
+            .return_once(|_, __| Ok(None));
+        storage
+            .expect_save_magic_link_code()
+            .with(
+                predicate::eq("email@test.com".to_string()),
+                predicate::eq("code".to_string()),
+            )
+            .times(1)
+            .return_once(|_, __| Ok(()));
+        let mut config = MockMagicLinkConfigProvider::new();
+        config
+            .expect_get_magic_link()
+            .with(predicate::eq("code".to_string()))
+            .return_once(|_| "https://app.test/magic-link?code=code".to_string());
+        let mut mailer = MockMagicLinkMailer::new();
+        mailer
+            .expect_send_magic_link()
+            .with(
+                predicate::eq("email@test.com".to_string()),
+                predicate::eq("https://app.test/magic-link?code=code".to_string()),
+            )
+            .times(1)
+            .return_once(|_, __| Ok(()));
+        let interactor = MagicLinkInteractor::new(
+            repository,
+            code_generator,
+            storage,
+            mailer,
+            MockClientVerifier::new(),
+            MockIdentityAuthenticator::new(),
+            config,
+        );
+
+        let result = interactor
+            .send_magic_link(
+                &"email@test.com".to_string(),
+                &Some("127.0.0.1".to_string()),
+            )
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_send_magic_link_while_throttled() {
+        let mut repository = MockMagicLinkUserRepository::new();
+        repository.expect_get_user_by_email().never();
+        let mut storage = MockMagicLinkStorage::new();
+        storage
+            .expect_register_send()
+            .return_once(|_, __| Ok(Some(42)));
+        storage.expect_save_magic_link_code().never();
+        let mut mailer = MockMagicLinkMailer::new();
+        mailer.expect_send_magic_link().never();
+        let interactor = MagicLinkInteractor::new(
+            repository,
+            MockCodeGenerator::new(),
+            storage,
+            mailer,
+            MockClientVerifier::new(),
+            MockIdentityAuthenticator::new(),
+            MockMagicLinkConfigProvider::new(),
+        );
+
+        let result = interactor
+            .send_magic_link(&"email@test.com".to_string(), &None)
+            .await;
+
+        assert_eq!(result, Err(get_too_many_magic_links_error(42)));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_reveal_unknown_email() {
+        let mut repository = MockMagicLinkUserRepository::new();
+        repository
+            .expect_get_user_by_email()
+            .return_once(|_| Ok(None));
+        let mut storage = MockMagicLinkStorage::new();
+        storage
+            .expect_register_send()
+            .times(1)
+            .return_once(|_, __| Ok(None));
+        storage.expect_save_magic_link_code().never();
+        let mut mailer = MockMagicLinkMailer::new();
+        mailer.expect_send_magic_link().never();
+        let interactor = MagicLinkInteractor::new(
+            repository,
+            MockCodeGenerator::new(),
+            storage,
+            mailer,
+            MockClientVerifier::new(),
+            MockIdentityAuthenticator::new(),
+            MockMagicLinkConfigProvider::new(),
+        );
+
+        let result = interactor
+            .send_magic_link(&"unknown@test.com".to_string(), &None)
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_login_verified_user() {
+        let mut repository = MockMagicLinkUserRepository::new();
+        repository
+            .expect_get_user_by_email()
+            .with(predicate::eq("email@test.com".to_string()))
+            .return_once(|_| Ok(Some(get_user(true))));
+        repository.expect_confirm_email().never();
+        let interactor = MagicLinkInteractor::new(
+            repository,
+            MockCodeGenerator::new(),
+            get_storage_with_code(),
+            MockMagicLinkMailer::new(),
+            MockClientVerifier::new(),
+            get_identity_authenticator(),
+            MockMagicLinkConfigProvider::new(),
+        );
+
+        let result = interactor
+            .login(&get_client(), &"code".to_string(), &DeviceInfo::default())
+            .await;
+
+        assert_eq!(result, Ok(get_login_result()));
+    }
+
+    #[actix_rt::test]
+    async fn should_confirm_email_of_unverified_user() {
+        let mut repository = MockMagicLinkUserRepository::new();
+        repository
+            .expect_get_user_by_email()
+            .return_once(|_| Ok(Some(get_user(false))));
+        repository
+            .expect_confirm_email()
+            .with(predicate::eq(1))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let interactor = MagicLinkInteractor::new(
+            repository,
+            MockCodeGenerator::new(),
+            get_storage_with_code(),
+            MockMagicLinkMailer::new(),
+            MockClientVerifier::new(),
+            get_identity_authenticator(),
+            MockMagicLinkConfigProvider::new(),
+        );
+
+        let result = interactor
+            .login(&get_client(), &"code".to_string(), &DeviceInfo::default())
+            .await;
+
+        assert_eq!(result, Ok(get_login_result()));
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_invalid_code() {
+        let mut storage = MockMagicLinkStorage::new();
+        storage
+            .expect_take_email_by_magic_link_code()
+            .return_once(|_| Err(get_invalid_magic_link_code()));
+        let mut identity_authenticator = MockIdentityAuthenticator::new();
+        identity_authenticator.expect_login_with_identity().never();
+        let interactor = MagicLinkInteractor::new(
+            MockMagicLinkUserRepository::new(),
+            MockCodeGenerator::new(),
+            storage,
+            MockMagicLinkMailer::new(),
+            MockClientVerifier::new(),
+            identity_authenticator,
+            MockMagicLinkConfigProvider::new(),
+        );
+
+        let result = interactor
+            .login(&get_client(), &"code".to_string(), &DeviceInfo::default())
+            .await;
+
+        assert_eq!(result, Err(get_invalid_magic_link_code()));
+    }
+}
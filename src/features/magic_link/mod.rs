@@ -0,0 +1,4 @@
+pub mod api;
+pub mod errors;
+pub mod infrastructure;
+pub mod interactors;
@@ -1,6 +1,9 @@
 use crate::{
     common::failure::domain::failure::Failure,
-    features::profile::interactors::profile_interactor::VerificationMailer,
+    features::{
+        magic_link::interactors::magic_link_interactor::MagicLinkMailer,
+        profile::interactors::profile_interactor::VerificationMailer,
+    },
 };
 use async_trait::async_trait;
 
@@ -19,4 +22,19 @@ impl VerificationMailer for Mailer {
         let _ = code;
         return Ok(());
     }
+
+    async fn send_password_reset_code(&self, email: &String, code: &String) -> Result<(), Failure> {
+        let _ = email;
+        let _ = code;
+        return Ok(());
+    }
+}
+
+#[async_trait]
+impl MagicLinkMailer for Mailer {
+    async fn send_magic_link(&self, email: &String, link: &String) -> Result<(), Failure> {
+        let _ = email;
+        let _ = link;
+        return Ok(());
+    }
 }
@@ -0,0 +1,3 @@
+pub mod mfa_code_dto;
+pub mod recovery_codes_dto;
+pub mod totp_enrollment_dto;
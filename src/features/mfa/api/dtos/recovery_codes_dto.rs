@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesDto {
+    pub recovery_codes: Vec<String>,
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+use crate::features::mfa::domain::totp_enrollment::TotpEnrollment;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollmentDto {
+    pub secret: String,
+    pub uri: String,
+}
+
+impl From<TotpEnrollment> for TotpEnrollmentDto {
+    fn from(enrollment: TotpEnrollment) -> Self {
+        return TotpEnrollmentDto {
+            secret: enrollment.secret,
+            uri: enrollment.uri,
+        };
+    }
+}
@@ -0,0 +1,86 @@
+use actix_web::{
+    http::StatusCode,
+    post,
+    web::{self, ServiceConfig},
+    HttpResponse, Responder,
+};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    features::{
+        auth::api::{
+            auth_middleware::verify_current_user,
+            scoped_user::{ProfileWrite, ScopedUser},
+        },
+        mfa::{
+            infrastructure::totp_repository_impl::TotpRepositoryImpl,
+            interactors::mfa_interactor::MfaInteractor,
+            utils::{
+                recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+            },
+        },
+        profile::infrastructure::profile_repository_impl::ProfileRepositoryImpl,
+    },
+};
+
+use super::dtos::{
+    mfa_code_dto::MfaCodeDto, recovery_codes_dto::RecoveryCodesDto,
+    totp_enrollment_dto::TotpEnrollmentDto,
+};
+
+type Interactor = MfaInteractor<
+    TotpRepositoryImpl,
+    TotpGenerator,
+    RandomRecoveryCodeGenerator,
+    ProfileRepositoryImpl,
+>;
+
+pub fn configure_mfa_controller(config: &mut ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(verify_current_user);
+    config.service(
+        web::scope("/mfa")
+            .wrap(auth_middleware)
+            .service(enroll_totp)
+            .service(confirm_totp)
+            .service(disable_totp),
+    );
+}
+
+#[post("/totp")]
+async fn enroll_totp(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+) -> impl Responder {
+    let result = interactor.enroll_totp(&current_user).await;
+    match result {
+        Ok(enrollment) => HttpResponse::Ok().json(TotpEnrollmentDto::from(enrollment)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/totp/confirm")]
+async fn confirm_totp(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+    dto: web::Json<MfaCodeDto>,
+) -> impl Responder {
+    let result = interactor.confirm_totp(&current_user, &dto.code).await;
+    match result {
+        Ok(recovery_codes) => HttpResponse::Ok().json(RecoveryCodesDto { recovery_codes }),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/totp/disable")]
+async fn disable_totp(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+    dto: web::Json<MfaCodeDto>,
+) -> impl Responder {
+    let result = interactor.disable_totp(&current_user, &dto.code).await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
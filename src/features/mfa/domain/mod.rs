@@ -0,0 +1,2 @@
+pub mod totp;
+pub mod totp_enrollment;
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Totp {
+    pub user_id: i64,
+    pub secret: String,
+    pub last_used_step: Option<i64>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
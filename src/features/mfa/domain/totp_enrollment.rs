@@ -0,0 +1,5 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct TotpEnrollment {
+    pub secret: String,
+    pub uri: String,
+}
@@ -0,0 +1,64 @@
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_mfa_not_allowed_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.mfa_not_allowed".to_string(),
+        message: "Two-factor authentication is not available for this role".to_string(),
+    };
+}
+
+pub fn get_mfa_already_enabled_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Conflict,
+        args: None,
+        code: "error.mfa_already_enabled".to_string(),
+        message: "Two-factor authentication is already enabled".to_string(),
+    };
+}
+
+pub fn get_mfa_not_enrolled_error() -> Failure {
+    return Failure {
+        error_type: FailureType::NotFound,
+        args: None,
+        code: "error.mfa_not_enrolled".to_string(),
+        message: "Two-factor authentication is not enrolled".to_string(),
+    };
+}
+
+pub fn get_invalid_mfa_code_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.invalid_mfa_code".to_string(),
+        message: "Invalid two-factor authentication code".to_string(),
+    };
+}
+
+pub fn get_secret_generating_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to generate totp secret".to_string(),
+    };
+}
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_unknown_mfa_update_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_mfa_update_error".to_string(),
+        message: "Unknown two-factor authentication update error".to_string(),
+    };
+}
@@ -0,0 +1 @@
+pub mod mfa_errors;
@@ -0,0 +1,3 @@
+pub mod new_recovery_code;
+pub mod new_totp;
+pub mod totp_entity;
@@ -0,0 +1,8 @@
+use crate::schema::recovery_code;
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "recovery_code"]
+pub struct NewRecoveryCode {
+    pub profile_id: i64,
+    pub code_hash: String,
+}
@@ -0,0 +1,8 @@
+use crate::schema::totp;
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "totp"]
+pub struct NewTotp {
+    pub profile_id: i64,
+    pub secret: String,
+}
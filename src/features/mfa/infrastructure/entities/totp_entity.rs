@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::mfa::domain::totp::Totp;
+
+#[derive(Queryable, Debug, PartialEq, Clone)]
+pub struct TotpEntity {
+    pub profile_id: i64,
+    pub secret: String,
+    pub last_used_step: Option<i64>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TotpEntity> for Totp {
+    fn from(entity: TotpEntity) -> Self {
+        return Totp {
+            user_id: entity.profile_id,
+            secret: entity.secret,
+            last_used_step: entity.last_used_step,
+            confirmed_at: entity.confirmed_at,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_map_entity_to_model() {
+        let entity = TotpEntity {
+            profile_id: 1,
+            secret: "secret".to_string(),
+            last_used_step: Some(10),
+            confirmed_at: None,
+            created_at: Utc::now(),
+        };
+
+        let result = Totp::from(entity);
+
+        assert_eq!(result.user_id, 1);
+        assert_eq!(result.secret, "secret".to_string());
+        assert_eq!(result.last_used_step, Some(10));
+        assert_eq!(result.confirmed_at, None);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::mfa::{
+        domain::totp::Totp,
+        errors::mfa_errors::{get_db_connection_error, get_unknown_mfa_update_error},
+        interactors::mfa_interactor::TotpRepository,
+    },
+    schema::{recovery_code, totp},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+use super::entities::{
+    new_recovery_code::NewRecoveryCode, new_totp::NewTotp, totp_entity::TotpEntity,
+};
+
+pub struct TotpRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl TotpRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> TotpRepositoryImpl {
+        return TotpRepositoryImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl TotpRepository for TotpRepositoryImpl {
+    async fn get_totp(&self, user_id: &i64) -> Result<Option<Totp>, Failure> {
+        let connection = self.get_connection()?;
+        let result = totp::table
+            .filter(totp::profile_id.eq(user_id))
+            .get_result::<TotpEntity>(&connection)
+            .optional();
+
+        return match result {
+            Ok(entity) => Ok(entity.map(|entity| entity.into())),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn save_pending_totp(&self, user_id: &i64, secret: &String) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let new_totp = NewTotp {
+            profile_id: user_id.to_owned(),
+            secret: secret.to_owned(),
+        };
+        let result = diesel::insert_into(totp::table)
+            .values(&new_totp)
+            .on_conflict(totp::profile_id)
+            .do_update()
+            .set((
+                totp::secret.eq(secret),
+                totp::last_used_step.eq(None::<i64>),
+                totp::confirmed_at.eq(None::<DateTime<Utc>>),
+            ))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_mfa_update_error()),
+        };
+    }
+
+    async fn confirm_totp(
+        &self,
+        user_id: &i64,
+        step: &i64,
+        recovery_code_hashes: &[String],
+    ) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let new_recovery_codes = recovery_code_hashes
+            .iter()
+            .map(|code_hash| NewRecoveryCode {
+                profile_id: user_id.to_owned(),
+                code_hash: code_hash.to_owned(),
+            })
+            .collect::<Vec<NewRecoveryCode>>();
+        let result = connection.transaction::<_, diesel::result::Error, _>(|| {
+            diesel::update(totp::table.filter(totp::profile_id.eq(user_id)))
+                .set((
+                    totp::last_used_step.eq(step),
+                    totp::confirmed_at.eq(Utc::now()),
+                ))
+                .execute(&connection)?;
+            diesel::delete(recovery_code::table.filter(recovery_code::profile_id.eq(user_id)))
+                .execute(&connection)?;
+            diesel::insert_into(recovery_code::table)
+                .values(&new_recovery_codes)
+                .execute(&connection)?;
+            Ok(())
+        });
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_mfa_update_error()),
+        };
+    }
+
+    async fn update_last_used_step(&self, user_id: &i64, step: &i64) -> Result<bool, Failure> {
+        let connection = self.get_connection()?;
+        // The step only moves forward, so a code can not be replayed within its window.
+        let target = totp::table.filter(totp::profile_id.eq(user_id)).filter(
+            totp::last_used_step
+                .is_null()
+                .or(totp::last_used_step.lt(step)),
+        );
+        let result = diesel::update(target)
+            .set(totp::last_used_step.eq(step))
+            .execute(&connection);
+
+        return match result {
+            Ok(updated) => Ok(updated == 1),
+            Err(_) => Err(get_unknown_mfa_update_error()),
+        };
+    }
+
+    async fn use_recovery_code(&self, user_id: &i64, code_hash: &String) -> Result<bool, Failure> {
+        let connection = self.get_connection()?;
+        let target = recovery_code::table
+            .filter(recovery_code::profile_id.eq(user_id))
+            .filter(recovery_code::code_hash.eq(code_hash));
+        let result = diesel::delete(target).execute(&connection);
+
+        return match result {
+            Ok(deleted) => Ok(deleted > 0),
+            Err(_) => Err(get_unknown_mfa_update_error()),
+        };
+    }
+
+    async fn delete_totp(&self, user_id: &i64) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = connection.transaction::<_, diesel::result::Error, _>(|| {
+            diesel::delete(recovery_code::table.filter(recovery_code::profile_id.eq(user_id)))
+                .execute(&connection)?;
+            diesel::delete(totp::table.filter(totp::profile_id.eq(user_id)))
+                .execute(&connection)?;
+            Ok(())
+        });
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_mfa_update_error()),
+        };
+    }
+}
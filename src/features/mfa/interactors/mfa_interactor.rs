@@ -0,0 +1,610 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::{
+            domain::{current_user_data::CurrentUserData, user_role::UserRole},
+            interactors::auth_interactor::SecondFactorVerifier,
+        },
+        impersonation::errors::impersonation_errors::get_not_allowed_while_impersonating_error,
+        mfa::{
+            domain::{totp::Totp, totp_enrollment::TotpEnrollment},
+            errors::mfa_errors::{
+                get_invalid_mfa_code_error, get_mfa_already_enabled_error,
+                get_mfa_not_allowed_error, get_mfa_not_enrolled_error,
+            },
+        },
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+
+#[async_trait]
+pub trait TotpRepository {
+    async fn get_totp(&self, user_id: &i64) -> Result<Option<Totp>, Failure>;
+
+    async fn save_pending_totp(&self, user_id: &i64, secret: &String) -> Result<(), Failure>;
+
+    async fn confirm_totp(
+        &self,
+        user_id: &i64,
+        step: &i64,
+        recovery_code_hashes: &[String],
+    ) -> Result<(), Failure>;
+
+    async fn update_last_used_step(&self, user_id: &i64, step: &i64) -> Result<bool, Failure>;
+
+    async fn use_recovery_code(&self, user_id: &i64, code_hash: &String) -> Result<bool, Failure>;
+
+    async fn delete_totp(&self, user_id: &i64) -> Result<(), Failure>;
+}
+
+#[async_trait]
+pub trait MfaAccountProvider {
+    async fn get_account_name(&self, user_id: &i64) -> Result<String, Failure>;
+}
+
+pub trait TotpProvider {
+    fn generate_secret(&self) -> Result<String, Failure>;
+
+    fn get_uri(&self, secret: &String, account_name: &String) -> String;
+
+    fn verify_code(&self, secret: &String, code: &String, timestamp: i64) -> Option<i64>;
+}
+
+pub trait RecoveryCodeGenerator {
+    fn generate_codes(&self) -> Vec<String>;
+
+    fn hash_code(&self, code: &String) -> String;
+}
+
+pub struct MfaInteractor<T, Y, U, I> {
+    totp_repository: T,
+    totp_provider: Y,
+    recovery_code_generator: U,
+    account_provider: I,
+}
+
+impl<T, Y, U, I> MfaInteractor<T, Y, U, I>
+where
+    T: TotpRepository,
+    Y: TotpProvider,
+    U: RecoveryCodeGenerator,
+    I: MfaAccountProvider,
+{
+    pub fn new(
+        totp_repository: T,
+        totp_provider: Y,
+        recovery_code_generator: U,
+        account_provider: I,
+    ) -> MfaInteractor<T, Y, U, I> {
+        return MfaInteractor {
+            totp_repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        };
+    }
+
+    // The second factor guards the account itself, so an admin acting as the user must not be
+    // able to replace or remove it.
+    pub async fn enroll_totp(
+        &self,
+        current_user: &CurrentUserData,
+    ) -> Result<TotpEnrollment, Failure> {
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        let user_id = &current_user.id;
+        if current_user.role == UserRole::USER {
+            return Err(get_mfa_not_allowed_error());
+        }
+        if let Some(totp) = self.totp_repository.get_totp(user_id).await? {
+            if totp.confirmed_at.is_some() {
+                return Err(get_mfa_already_enabled_error());
+            }
+        }
+        let account_name = self.account_provider.get_account_name(user_id).await?;
+        let secret = self.totp_provider.generate_secret()?;
+        self.totp_repository
+            .save_pending_totp(user_id, &secret)
+            .await?;
+        let uri = self.totp_provider.get_uri(&secret, &account_name);
+
+        Ok(TotpEnrollment { secret, uri })
+    }
+
+    pub async fn confirm_totp(
+        &self,
+        current_user: &CurrentUserData,
+        code: &String,
+    ) -> Result<Vec<String>, Failure> {
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        let user_id = &current_user.id;
+        let totp = match self.totp_repository.get_totp(user_id).await? {
+            None => return Err(get_mfa_not_enrolled_error()),
+            Some(totp) if totp.confirmed_at.is_some() => {
+                return Err(get_mfa_already_enabled_error())
+            }
+            Some(totp) => totp,
+        };
+        let step = self
+            .totp_provider
+            .verify_code(&totp.secret, code, Utc::now().timestamp())
+            .ok_or(get_invalid_mfa_code_error())?;
+        let recovery_codes = self.recovery_code_generator.generate_codes();
+        let recovery_code_hashes = recovery_codes
+            .iter()
+            .map(|code| self.recovery_code_generator.hash_code(code))
+            .collect::<Vec<String>>();
+        self.totp_repository
+            .confirm_totp(user_id, &step, &recovery_code_hashes)
+            .await?;
+
+        Ok(recovery_codes)
+    }
+
+    pub async fn disable_totp(
+        &self,
+        current_user: &CurrentUserData,
+        code: &String,
+    ) -> Result<(), Failure> {
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        let user_id = &current_user.id;
+        if !self.verify_code(user_id, code).await? {
+            return Err(get_invalid_mfa_code_error());
+        }
+
+        self.totp_repository.delete_totp(user_id).await
+    }
+
+    pub async fn is_enabled(&self, user_id: &i64) -> Result<bool, Failure> {
+        let totp = self.totp_repository.get_totp(user_id).await?;
+
+        Ok(matches!(totp, Some(totp) if totp.confirmed_at.is_some()))
+    }
+
+    // Accepts either a current totp code or one of the unused recovery codes. Both are
+    // single use: a totp step is never accepted twice and recovery codes are deleted.
+    pub async fn verify_code(&self, user_id: &i64, code: &String) -> Result<bool, Failure> {
+        let totp = match self.totp_repository.get_totp(user_id).await? {
+            Some(totp) if totp.confirmed_at.is_some() => totp,
+            _ => return Err(get_mfa_not_enrolled_error()),
+        };
+        let step = self
+            .totp_provider
+            .verify_code(&totp.secret, code, Utc::now().timestamp());
+        if let Some(step) = step {
+            return self
+                .totp_repository
+                .update_last_used_step(user_id, &step)
+                .await;
+        }
+
+        let code_hash = self.recovery_code_generator.hash_code(code);
+        self.totp_repository
+            .use_recovery_code(user_id, &code_hash)
+            .await
+    }
+}
+
+#[async_trait]
+impl<T, Y, U, I> SecondFactorVerifier for MfaInteractor<T, Y, U, I>
+where
+    T: TotpRepository + Send + Sync,
+    Y: TotpProvider + Send + Sync,
+    U: RecoveryCodeGenerator + Send + Sync,
+    I: MfaAccountProvider + Send + Sync,
+{
+    async fn is_second_factor_enabled(&self, user_id: &i64) -> Result<bool, Failure> {
+        self.is_enabled(user_id).await
+    }
+
+    async fn verify_second_factor(&self, user_id: &i64, code: &String) -> Result<bool, Failure> {
+        self.verify_code(user_id, code).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mockall::predicate::*;
+    use mockall::*;
+
+    use super::*;
+
+    mock! {
+        TotpRepository {}
+
+        #[async_trait]
+        impl TotpRepository for TotpRepository {
+            async fn get_totp(&self, user_id: &i64) -> Result<Option<Totp>, Failure>;
+
+            async fn save_pending_totp(&self, user_id: &i64, secret: &String) -> Result<(), Failure>;
+
+            async fn confirm_totp(
+                &self,
+                user_id: &i64,
+                step: &i64,
+                recovery_code_hashes: &[String],
+            ) -> Result<(), Failure>;
+
+            async fn update_last_used_step(&self, user_id: &i64, step: &i64) -> Result<bool, Failure>;
+
+            async fn use_recovery_code(&self, user_id: &i64, code_hash: &String) -> Result<bool, Failure>;
+
+            async fn delete_totp(&self, user_id: &i64) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        TotpProvider {}
+
+        impl TotpProvider for TotpProvider {
+            fn generate_secret(&self) -> Result<String, Failure>;
+
+            fn get_uri(&self, secret: &String, account_name: &String) -> String;
+
+            fn verify_code(&self, secret: &String, code: &String, timestamp: i64) -> Option<i64>;
+        }
+    }
+
+    mock! {
+        RecoveryCodeGenerator {}
+
+        impl RecoveryCodeGenerator for RecoveryCodeGenerator {
+            fn generate_codes(&self) -> Vec<String>;
+
+            fn hash_code(&self, code: &String) -> String;
+        }
+    }
+
+    mock! {
+        AccountProvider {}
+
+        #[async_trait]
+        impl MfaAccountProvider for AccountProvider {
+            async fn get_account_name(&self, user_id: &i64) -> Result<String, Failure>;
+        }
+    }
+
+    fn get_dependencies() -> (
+        MockTotpRepository,
+        MockTotpProvider,
+        MockRecoveryCodeGenerator,
+        MockAccountProvider,
+    ) {
+        let repository = MockTotpRepository::new();
+        let totp_provider = MockTotpProvider::new();
+        let recovery_code_generator = MockRecoveryCodeGenerator::new();
+        let account_provider = MockAccountProvider::new();
+
+        return (
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+    }
+
+    fn get_current_user(role: UserRole, actor_id: Option<i64>) -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role,
+            session_id: None,
+            actor_id,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![],
+        };
+    }
+
+    fn get_totp(confirmed: bool) -> Totp {
+        return Totp {
+            user_id: 1,
+            secret: "secret".to_string(),
+            last_used_step: None,
+            confirmed_at: if confirmed { Some(Utc::now()) } else { None },
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_enroll_totp() {
+        let (mut repository, mut totp_provider, recovery_code_generator, mut account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .with(eq(1))
+            .return_once(|_| Ok(None));
+        account_provider
+            .expect_get_account_name()
+            .with(eq(1))
+            .return_once(|_| Ok("email".to_string()));
+        totp_provider
+            .expect_generate_secret()
+            .return_once(|| Ok("secret".to_string()));
+        repository
+            .expect_save_pending_totp()
+            .with(eq(1), eq("secret".to_string()))
+            .return_once(|_, __| Ok(()));
+        totp_provider
+            .expect_get_uri()
+            .with(eq("secret".to_string()), eq("email".to_string()))
+            .return_once(|_, __| "uri".to_string());
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor
+            .enroll_totp(&get_current_user(UserRole::MODERATOR, None))
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(TotpEnrollment {
+                secret: "secret".to_string(),
+                uri: "uri".to_string(),
+            })
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_not_enroll_regular_users() {
+        let (mut repository, totp_provider, recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository.expect_save_pending_totp().never();
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor
+            .enroll_totp(&get_current_user(UserRole::USER, None))
+            .await;
+
+        assert_eq!(result, Err(get_mfa_not_allowed_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_enroll_twice() {
+        let (mut repository, totp_provider, recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .return_once(|_| Ok(Some(get_totp(true))));
+        repository.expect_save_pending_totp().never();
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor
+            .enroll_totp(&get_current_user(UserRole::ADMIN, None))
+            .await;
+
+        assert_eq!(result, Err(get_mfa_already_enabled_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_confirm_totp_and_return_recovery_codes() {
+        let (mut repository, mut totp_provider, mut recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .return_once(|_| Ok(Some(get_totp(false))));
+        totp_provider
+            .expect_verify_code()
+            .withf(|secret, code, _| secret == "secret" && code == "123456")
+            .return_once(|_, __, ___| Some(10));
+        recovery_code_generator
+            .expect_generate_codes()
+            .return_once(|| vec!["code".to_string()]);
+        recovery_code_generator
+            .expect_hash_code()
+            .with(eq("code".to_string()))
+            .return_once(|_| "hash".to_string());
+        repository
+            .expect_confirm_totp()
+            .with(
+                eq(1),
+                eq(10),
+                function(|hashes: &[String]| hashes == ["hash".to_string()]),
+            )
+            .return_once(|_, __, ___| Ok(()));
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor
+            .confirm_totp(
+                &get_current_user(UserRole::ADMIN, None),
+                &"123456".to_string(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(vec!["code".to_string()]));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_confirm_totp_with_invalid_code() {
+        let (mut repository, mut totp_provider, recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .return_once(|_| Ok(Some(get_totp(false))));
+        totp_provider
+            .expect_verify_code()
+            .return_once(|_, __, ___| None);
+        repository.expect_confirm_totp().never();
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor
+            .confirm_totp(
+                &get_current_user(UserRole::ADMIN, None),
+                &"123456".to_string(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_mfa_code_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_accept_unused_totp_step() {
+        let (mut repository, mut totp_provider, recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .return_once(|_| Ok(Some(get_totp(true))));
+        totp_provider
+            .expect_verify_code()
+            .return_once(|_, __, ___| Some(10));
+        repository
+            .expect_update_last_used_step()
+            .with(eq(1), eq(10))
+            .return_once(|_, __| Ok(true));
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor.verify_code(&1, &"123456".to_string()).await;
+
+        assert_eq!(result, Ok(true));
+    }
+
+    #[actix_rt::test]
+    async fn should_fall_back_to_recovery_code() {
+        let (mut repository, mut totp_provider, mut recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .return_once(|_| Ok(Some(get_totp(true))));
+        totp_provider
+            .expect_verify_code()
+            .return_once(|_, __, ___| None);
+        recovery_code_generator
+            .expect_hash_code()
+            .with(eq("recovery".to_string()))
+            .return_once(|_| "hash".to_string());
+        repository
+            .expect_use_recovery_code()
+            .with(eq(1), eq("hash".to_string()))
+            .return_once(|_, __| Ok(true));
+        repository.expect_update_last_used_step().never();
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor.verify_code(&1, &"recovery".to_string()).await;
+
+        assert_eq!(result, Ok(true));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_disable_totp_with_invalid_code() {
+        let (mut repository, mut totp_provider, mut recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository
+            .expect_get_totp()
+            .return_once(|_| Ok(Some(get_totp(true))));
+        totp_provider
+            .expect_verify_code()
+            .return_once(|_, __, ___| None);
+        recovery_code_generator
+            .expect_hash_code()
+            .return_once(|_| "hash".to_string());
+        repository
+            .expect_use_recovery_code()
+            .return_once(|_, __| Ok(false));
+        repository.expect_delete_totp().never();
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+
+        let result = interactor
+            .disable_totp(
+                &get_current_user(UserRole::ADMIN, None),
+                &"123456".to_string(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_mfa_code_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_change_totp_while_impersonating() {
+        let (mut repository, totp_provider, recovery_code_generator, account_provider) =
+            get_dependencies();
+
+        repository.expect_get_totp().never();
+        repository.expect_save_pending_totp().never();
+        repository.expect_confirm_totp().never();
+        repository.expect_delete_totp().never();
+
+        let interactor = MfaInteractor::new(
+            repository,
+            totp_provider,
+            recovery_code_generator,
+            account_provider,
+        );
+        let current_user = get_current_user(UserRole::MODERATOR, Some(2));
+        let code = "123456".to_string();
+
+        let enroll_result = interactor.enroll_totp(&current_user).await;
+        let confirm_result = interactor.confirm_totp(&current_user, &code).await;
+        let disable_result = interactor.disable_totp(&current_user, &code).await;
+
+        assert_eq!(
+            enroll_result,
+            Err(get_not_allowed_while_impersonating_error())
+        );
+        assert_eq!(
+            confirm_result,
+            Err(get_not_allowed_while_impersonating_error())
+        );
+        assert_eq!(
+            disable_result,
+            Err(get_not_allowed_while_impersonating_error())
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod api;
+pub mod domain;
+pub mod errors;
+pub mod infrastructure;
+pub mod interactors;
+pub mod utils;
@@ -0,0 +1,2 @@
+pub mod recovery_code_generator;
+pub mod totp_generator;
@@ -0,0 +1,73 @@
+use nanoid::nanoid;
+use ring::digest::{digest, SHA256};
+
+use crate::features::mfa::interactors::mfa_interactor::RecoveryCodeGenerator;
+
+const RECOVERY_CODES_COUNT: usize = 10;
+// Lowercase letters and digits without the easily confused `i`, `l`, `o`, `0` and `1`.
+const RECOVERY_CODE_ALPHABET: [char; 31] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'j', 'k', 'm', 'n', 'p', 'q', 'r', 's', 't', 'u', 'v',
+    'w', 'x', 'y', 'z', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+pub struct RandomRecoveryCodeGenerator;
+
+impl RandomRecoveryCodeGenerator {
+    pub fn new() -> RandomRecoveryCodeGenerator {
+        return RandomRecoveryCodeGenerator {};
+    }
+}
+
+impl RecoveryCodeGenerator for RandomRecoveryCodeGenerator {
+    fn generate_codes(&self) -> Vec<String> {
+        return (0..RECOVERY_CODES_COUNT)
+            .map(|_| {
+                format!(
+                    "{}-{}",
+                    nanoid!(5, &RECOVERY_CODE_ALPHABET),
+                    nanoid!(5, &RECOVERY_CODE_ALPHABET)
+                )
+            })
+            .collect();
+    }
+
+    // Recovery codes are random enough that a fast hash is sufficient. Dashes and case are
+    // ignored so the code can be typed the way it was displayed or without separators.
+    fn hash_code(&self, code: &String) -> String {
+        let normalized = code.trim().replace('-', "").to_lowercase();
+
+        return digest(&SHA256, normalized.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_generate_unique_codes() {
+        let generator = RandomRecoveryCodeGenerator::new();
+
+        let mut codes = generator.generate_codes();
+        codes.sort();
+        codes.dedup();
+
+        assert_eq!(codes.len(), RECOVERY_CODES_COUNT);
+        assert!(codes.iter().all(|code| code.len() == 11));
+    }
+
+    #[test]
+    fn should_ignore_formatting_when_hashing() {
+        let generator = RandomRecoveryCodeGenerator::new();
+
+        let formatted = generator.hash_code(&"abcde-fghjk".to_string());
+        let plain = generator.hash_code(&" ABCDEFGHJK ".to_string());
+
+        assert_eq!(formatted, plain);
+        assert_eq!(formatted.len(), 64);
+    }
+}
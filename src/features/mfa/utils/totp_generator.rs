@@ -0,0 +1,148 @@
+use base32::Alphabet;
+use ring::{
+    constant_time::verify_slices_are_equal,
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+
+use crate::{
+    common::failure::domain::failure::Failure,
+    config::mfa_config::MfaConfig,
+    features::mfa::{
+        errors::mfa_errors::get_secret_generating_error, interactors::mfa_interactor::TotpProvider,
+    },
+};
+
+const SECRET_LENGTH: usize = 20;
+const STEP_SECONDS: i64 = 30;
+const DIGITS: u32 = 6;
+// Number of neighbouring steps accepted on each side to tolerate clock drift.
+const ALLOWED_DRIFT: i64 = 1;
+const BASE32_ALPHABET: Alphabet = Alphabet::RFC4648 { padding: false };
+
+pub struct TotpGenerator {
+    config: MfaConfig,
+    random: SystemRandom,
+}
+
+impl TotpGenerator {
+    pub fn new(config: MfaConfig) -> TotpGenerator {
+        return TotpGenerator {
+            config,
+            random: SystemRandom::new(),
+        };
+    }
+
+    fn generate_code(key: &[u8], step: i64) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, key);
+        let tag = hmac::sign(&key, &step.to_be_bytes());
+        let hash = tag.as_ref();
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = ((hash[offset] as u32 & 0x7f) << 24)
+            | ((hash[offset + 1] as u32) << 16)
+            | ((hash[offset + 2] as u32) << 8)
+            | (hash[offset + 3] as u32);
+
+        return format!(
+            "{:0width$}",
+            binary % 10_u32.pow(DIGITS),
+            width = DIGITS as usize
+        );
+    }
+
+    fn encode_uri_component(value: &String) -> String {
+        return value
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{:02X}", byte),
+            })
+            .collect();
+    }
+}
+
+impl TotpProvider for TotpGenerator {
+    fn generate_secret(&self) -> Result<String, Failure> {
+        let mut secret = [0u8; SECRET_LENGTH];
+
+        return match self.random.fill(&mut secret) {
+            Ok(_) => Ok(base32::encode(BASE32_ALPHABET, &secret)),
+            Err(_) => Err(get_secret_generating_error()),
+        };
+    }
+
+    fn get_uri(&self, secret: &String, account_name: &String) -> String {
+        let issuer = TotpGenerator::encode_uri_component(&self.config.totp_issuer);
+        let account_name = TotpGenerator::encode_uri_component(account_name);
+
+        return format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            issuer, account_name, secret, issuer, DIGITS, STEP_SECONDS
+        );
+    }
+
+    fn verify_code(&self, secret: &String, code: &String, timestamp: i64) -> Option<i64> {
+        let key = base32::decode(BASE32_ALPHABET, secret)?;
+        let current_step = timestamp / STEP_SECONDS;
+
+        return (current_step - ALLOWED_DRIFT..=current_step + ALLOWED_DRIFT).find(|step| {
+            let expected = TotpGenerator::generate_code(&key, *step);
+            verify_slices_are_equal(expected.as_bytes(), code.trim().as_bytes()).is_ok()
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Shared secret used by the RFC 6238 test vectors.
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    fn get_generator() -> TotpGenerator {
+        return TotpGenerator::new(MfaConfig {
+            totp_issuer: "vvvvv app".to_string(),
+        });
+    }
+
+    #[test]
+    fn should_match_rfc_test_vectors() {
+        assert_eq!(TotpGenerator::generate_code(RFC_SECRET, 59 / 30), "287082");
+        assert_eq!(
+            TotpGenerator::generate_code(RFC_SECRET, 1111111109 / 30),
+            "081804"
+        );
+        assert_eq!(
+            TotpGenerator::generate_code(RFC_SECRET, 1234567890 / 30),
+            "005924"
+        );
+    }
+
+    #[test]
+    fn should_verify_code_within_allowed_drift() {
+        let generator = get_generator();
+        let secret = base32::encode(BASE32_ALPHABET, RFC_SECRET);
+
+        let current = generator.verify_code(&secret, &"081804".to_string(), 1111111109);
+        let previous = generator.verify_code(&secret, &"081804".to_string(), 1111111109 + 30);
+        let expired = generator.verify_code(&secret, &"081804".to_string(), 1111111109 + 90);
+
+        assert_eq!(current, Some(1111111109 / 30));
+        assert_eq!(previous, Some(1111111109 / 30));
+        assert_eq!(expired, None);
+    }
+
+    #[test]
+    fn should_build_otpauth_uri() {
+        let generator = get_generator();
+
+        let result = generator.get_uri(&"SECRET".to_string(), &"user@test.com".to_string());
+
+        assert_eq!(
+            result,
+            "otpauth://totp/vvvvv%20app:user%40test.com?secret=SECRET&issuer=vvvvv%20app&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
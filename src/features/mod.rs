@@ -1,3 +1,10 @@
 pub mod auth;
+pub mod auth_event;
+pub mod client;
+pub mod impersonation;
+pub mod magic_link;
 pub mod mailer;
+pub mod mfa;
+pub mod oidc;
+pub mod personal_access_token;
 pub mod profile;
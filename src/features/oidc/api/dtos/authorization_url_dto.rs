@@ -0,0 +1,6 @@
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct AuthorizationUrlDto {
+    pub authorization_url: String,
+}
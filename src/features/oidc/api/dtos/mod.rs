@@ -0,0 +1,3 @@
+pub mod authorization_url_dto;
+pub mod oidc_authorize_dto;
+pub mod oidc_login_dto;
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcAuthorizeDto {
+    pub client_id: String,
+    pub client_secret: String,
+}
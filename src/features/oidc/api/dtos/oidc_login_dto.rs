@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcLoginDto {
+    pub code: String,
+    pub state: String,
+    pub client_id: String,
+    pub client_secret: String,
+}
@@ -0,0 +1,150 @@
+use actix_web::{
+    post,
+    web::{self, ServiceConfig},
+    HttpRequest, HttpResponse, Responder,
+};
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    config::{auth_config::AuthConfig, client_config::ClientConfig},
+    features::{
+        auth::{
+            api::{
+                dtos::{mfa_required_dto::MfaRequiredDto, tokens_pair_dto::TokensPairDto},
+                request_info::get_device_info,
+            },
+            domain::login_result::LoginResult,
+            infrastructure::{
+                auth_data_repository_impl::AuthDataRepositoryImpl,
+                jwt_token_provider::JwtTokenProvider, login_throttle_impl::LoginThrottleImpl,
+                password_manager_impl::PasswordManagerImpl,
+                session_storage_impl::SessionStorageImpl,
+            },
+            interactors::auth_interactor::AuthInteractor,
+        },
+        auth_event::infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
+        },
+        mfa::{
+            infrastructure::totp_repository_impl::TotpRepositoryImpl,
+            interactors::mfa_interactor::MfaInteractor,
+            utils::{
+                recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+            },
+        },
+        oidc::{
+            infrastructure::{
+                authorization_request_storage_impl::AuthorizationRequestStorageImpl,
+                oidc_provider_client_impl::OidcProviderClientImpl,
+                profile_identity_repository_impl::ProfileIdentityRepositoryImpl,
+            },
+            interactors::oidc_interactor::OidcInteractor,
+            utils::oidc_secret_generator::RandomOidcSecretGenerator,
+        },
+        personal_access_token::{
+            infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+            interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+            utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+        },
+        profile::infrastructure::profile_repository_impl::ProfileRepositoryImpl,
+    },
+};
+
+use super::dtos::{
+    authorization_url_dto::AuthorizationUrlDto, oidc_authorize_dto::OidcAuthorizeDto,
+    oidc_login_dto::OidcLoginDto,
+};
+
+type Interactor = OidcInteractor<
+    OidcProviderClientImpl,
+    AuthorizationRequestStorageImpl,
+    ProfileIdentityRepositoryImpl,
+    RandomOidcSecretGenerator,
+    ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+    AuthInteractor<
+        PasswordManagerImpl,
+        JwtTokenProvider,
+        AuthDataRepositoryImpl,
+        ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+        SessionStorageImpl,
+        MfaInteractor<
+            TotpRepositoryImpl,
+            TotpGenerator,
+            RandomRecoveryCodeGenerator,
+            ProfileRepositoryImpl,
+        >,
+        PersonalAccessTokenInteractor<
+            PersonalAccessTokenRepositoryImpl,
+            RandomPersonalAccessTokenGenerator,
+        >,
+        LoginThrottleImpl,
+        AuthConfig,
+        AuthEventRepositoryImpl,
+    >,
+>;
+
+// Has to be configured before the auth controller, whose `/auth` scope would otherwise
+// swallow these routes.
+pub fn configure_oidc_controller(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/auth/oidc/{provider}")
+            .service(authorize)
+            .service(login),
+    );
+}
+
+#[post("/authorize")]
+async fn authorize(
+    interactor: web::Data<Interactor>,
+    provider: web::Path<String>,
+    dto: web::Form<OidcAuthorizeDto>,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let result = interactor.get_authorization_url(&client, &provider).await;
+
+    match result {
+        Ok(authorization_url) => HttpResponse::Ok().json(AuthorizationUrlDto { authorization_url }),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/login")]
+async fn login(
+    interactor: web::Data<Interactor>,
+    provider: web::Path<String>,
+    dto: web::Form<OidcLoginDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let client = match interactor
+        .verify_client(&dto.client_id, &dto.client_secret)
+        .await
+    {
+        Ok(client) => client,
+        Err(failure) => return handle_failure(failure),
+    };
+
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .login(&client, &provider, &dto.code, &dto.state, &device_info)
+        .await;
+
+    match result {
+        Ok(LoginResult::Authenticated(tokens)) => {
+            HttpResponse::Ok().json(TokensPairDto::from(tokens))
+        }
+        Ok(LoginResult::MfaRequired(mfa_token)) => {
+            HttpResponse::Ok().json(MfaRequiredDto { mfa_token })
+        }
+        Err(err) => handle_failure(err),
+    }
+}
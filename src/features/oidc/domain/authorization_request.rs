@@ -0,0 +1,9 @@
+// Started authorization, kept until the provider redirects back with the same `state`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AuthorizationRequest {
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub provider: String,
+    pub client_id: String,
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreateIdentityUserModel {
+    pub username: String,
+    pub email: String,
+    pub provider: String,
+    pub subject: String,
+}
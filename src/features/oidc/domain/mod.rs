@@ -0,0 +1,3 @@
+pub mod authorization_request;
+pub mod create_identity_user_model;
+pub mod oidc_identity;
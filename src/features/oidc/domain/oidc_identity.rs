@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct OidcIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_redis_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to redis".to_string(),
+    };
+}
+
+pub fn get_unknown_identity_creation_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_identity_creation_error".to_string(),
+        message: "Unknown identity creation error".to_string(),
+    };
+}
+
+pub fn get_unknown_provider_error(provider: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("provider".to_string(), provider.clone());
+
+    return Failure {
+        error_type: FailureType::NotFound,
+        code: "error.unknown_oidc_provider".to_string(),
+        message: format!("Login with '{}' is not supported", provider),
+        args: Some(args),
+    };
+}
+
+pub fn get_invalid_state_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.invalid_oidc_state".to_string(),
+        message: "Authorization request is invalid or expired".to_string(),
+    };
+}
+
+pub fn get_provider_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.oidc_provider_connection".to_string(),
+        message: "Failed to connect to identity provider".to_string(),
+    };
+}
+
+pub fn get_code_exchange_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.oidc_code_exchange".to_string(),
+        message: "Identity provider rejected authorization code".to_string(),
+    };
+}
+
+pub fn get_invalid_id_token_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Authentication,
+        args: None,
+        code: "error.invalid_id_token".to_string(),
+        message: "Identity provider returned invalid identity".to_string(),
+    };
+}
+
+pub fn get_provider_email_not_verified_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.oidc_email_not_verified".to_string(),
+        message: "Identity provider did not return a verified email".to_string(),
+    };
+}
+
+pub fn get_email_already_registered_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Conflict,
+        args: None,
+        code: "error.oidc_email_already_registered".to_string(),
+        message: "Email is already registered by an unverified account".to_string(),
+    };
+}
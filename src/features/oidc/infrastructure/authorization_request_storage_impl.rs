@@ -0,0 +1,83 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    config::oidc_config::OidcConfig,
+    features::oidc::{
+        domain::authorization_request::AuthorizationRequest,
+        errors::oidc_errors::{get_invalid_state_error, get_redis_connection_error},
+        interactors::oidc_interactor::AuthorizationRequestStorage,
+    },
+};
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, RedisError};
+
+use super::entities::authorization_request_entity::AuthorizationRequestEntity;
+
+pub struct AuthorizationRequestStorageImpl {
+    redis_connection: MultiplexedConnection,
+    config: OidcConfig,
+}
+
+impl AuthorizationRequestStorageImpl {
+    pub fn new(
+        redis: MultiplexedConnection,
+        config: OidcConfig,
+    ) -> AuthorizationRequestStorageImpl {
+        return AuthorizationRequestStorageImpl {
+            redis_connection: redis,
+            config,
+        };
+    }
+
+    fn get_key(state: &String) -> String {
+        return format!("oidc_state:{}", state);
+    }
+}
+
+#[async_trait]
+impl AuthorizationRequestStorage for AuthorizationRequestStorageImpl {
+    async fn save_authorization_request(
+        &self,
+        request: &AuthorizationRequest,
+    ) -> Result<(), Failure> {
+        let exp = self.config.authorization_request_exp;
+        let value = match serde_json::to_string(&AuthorizationRequestEntity::from(request)) {
+            Ok(value) => value,
+            Err(_) => return Err(get_redis_connection_error()),
+        };
+
+        let result: Result<(), RedisError> = self
+            .redis_connection
+            .clone()
+            .set_ex(
+                AuthorizationRequestStorageImpl::get_key(&request.state),
+                value,
+                exp as usize,
+            )
+            .await;
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn take_authorization_request(
+        &self,
+        state: &String,
+    ) -> Result<AuthorizationRequest, Failure> {
+        let result: Result<Option<String>, RedisError> = redis::cmd("GETDEL")
+            .arg(AuthorizationRequestStorageImpl::get_key(state))
+            .query_async(&mut self.redis_connection.clone())
+            .await;
+
+        return match result {
+            Ok(Some(value)) => match serde_json::from_str::<AuthorizationRequestEntity>(&value) {
+                Ok(entity) => Ok(entity.into()),
+                Err(_) => Err(get_invalid_state_error()),
+            },
+            Ok(None) => Err(get_invalid_state_error()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+}
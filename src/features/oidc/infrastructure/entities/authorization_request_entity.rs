@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::oidc::domain::authorization_request::AuthorizationRequest;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct AuthorizationRequestEntity {
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub provider: String,
+    pub client_id: String,
+}
+
+impl From<&AuthorizationRequest> for AuthorizationRequestEntity {
+    fn from(request: &AuthorizationRequest) -> Self {
+        return AuthorizationRequestEntity {
+            state: request.state.clone(),
+            nonce: request.nonce.clone(),
+            code_verifier: request.code_verifier.clone(),
+            provider: request.provider.clone(),
+            client_id: request.client_id.clone(),
+        };
+    }
+}
+
+impl From<AuthorizationRequestEntity> for AuthorizationRequest {
+    fn from(entity: AuthorizationRequestEntity) -> AuthorizationRequest {
+        return AuthorizationRequest {
+            state: entity.state,
+            nonce: entity.nonce,
+            code_verifier: entity.code_verifier,
+            provider: entity.provider,
+            client_id: entity.client_id,
+        };
+    }
+}
@@ -0,0 +1,3 @@
+pub mod authorization_request_entity;
+pub mod new_identity_profile;
+pub mod new_profile_identity;
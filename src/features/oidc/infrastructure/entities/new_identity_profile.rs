@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    features::oidc::domain::create_identity_user_model::CreateIdentityUserModel, schema::profile,
+};
+
+// Accounts created through an identity provider have no password until the user resets it.
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "profile"]
+pub struct NewIdentityProfile {
+    pub name: String,
+    pub email: String,
+    pub password: String,
+    pub email_confirmed_at: DateTime<Utc>,
+}
+
+impl From<&CreateIdentityUserModel> for NewIdentityProfile {
+    fn from(user: &CreateIdentityUserModel) -> Self {
+        return NewIdentityProfile {
+            name: user.username.clone(),
+            email: user.email.clone(),
+            password: "".to_string(),
+            email_confirmed_at: Utc::now(),
+        };
+    }
+}
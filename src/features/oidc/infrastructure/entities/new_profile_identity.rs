@@ -0,0 +1,9 @@
+use crate::schema::profile_identity;
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "profile_identity"]
+pub struct NewProfileIdentity {
+    pub profile_id: i64,
+    pub provider: String,
+    pub subject: String,
+}
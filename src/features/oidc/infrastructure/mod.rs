@@ -0,0 +1,4 @@
+pub mod authorization_request_storage_impl;
+pub mod entities;
+pub mod oidc_provider_client_impl;
+pub mod profile_identity_repository_impl;
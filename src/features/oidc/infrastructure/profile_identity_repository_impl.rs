@@ -0,0 +1,124 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::domain::auth_data::AuthData,
+        oidc::{
+            domain::create_identity_user_model::CreateIdentityUserModel,
+            errors::oidc_errors::{get_db_connection_error, get_unknown_identity_creation_error},
+            interactors::oidc_interactor::ProfileIdentityRepository,
+        },
+        profile::infrastructure::entities::profile_entity::ProfileEntity,
+    },
+    schema::{profile, profile_identity},
+};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+use super::entities::{
+    new_identity_profile::NewIdentityProfile, new_profile_identity::NewProfileIdentity,
+};
+
+pub struct ProfileIdentityRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ProfileIdentityRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> ProfileIdentityRepositoryImpl {
+        return ProfileIdentityRepositoryImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl ProfileIdentityRepository for ProfileIdentityRepositoryImpl {
+    async fn get_identity_user(
+        &self,
+        provider: &String,
+        subject: &String,
+    ) -> Result<Option<AuthData>, Failure> {
+        let connection = self.get_connection()?;
+        let result = profile_identity::table
+            .inner_join(profile::table)
+            .filter(profile_identity::provider.eq(provider))
+            .filter(profile_identity::subject.eq(subject))
+            .select(profile::all_columns)
+            .first::<ProfileEntity>(&connection)
+            .optional();
+
+        return match result {
+            Ok(entity) => Ok(entity.map(|entity| entity.into())),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn get_user_by_email(&self, email: &String) -> Result<Option<AuthData>, Failure> {
+        let connection = self.get_connection()?;
+        let result = profile::table
+            .filter(profile::email.eq(email))
+            .first::<ProfileEntity>(&connection)
+            .optional();
+
+        return match result {
+            Ok(entity) => Ok(entity.map(|entity| entity.into())),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn link_identity(
+        &self,
+        user_id: &i64,
+        provider: &String,
+        subject: &String,
+    ) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let new_identity = NewProfileIdentity {
+            profile_id: *user_id,
+            provider: provider.clone(),
+            subject: subject.clone(),
+        };
+        let result = diesel::insert_into(profile_identity::table)
+            .values(&new_identity)
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_identity_creation_error()),
+        };
+    }
+
+    async fn create_user_with_identity(
+        &self,
+        user: &CreateIdentityUserModel,
+    ) -> Result<AuthData, Failure> {
+        let connection = self.get_connection()?;
+        let result = connection.transaction::<ProfileEntity, diesel::result::Error, _>(|| {
+            let entity = diesel::insert_into(profile::table)
+                .values(&NewIdentityProfile::from(user))
+                .get_result::<ProfileEntity>(&connection)?;
+            let new_identity = NewProfileIdentity {
+                profile_id: entity.id,
+                provider: user.provider.clone(),
+                subject: user.subject.clone(),
+            };
+            diesel::insert_into(profile_identity::table)
+                .values(&new_identity)
+                .execute(&connection)?;
+
+            Ok(entity)
+        });
+
+        return match result {
+            Ok(entity) => Ok(entity.into()),
+            Err(_) => Err(get_unknown_identity_creation_error()),
+        };
+    }
+}
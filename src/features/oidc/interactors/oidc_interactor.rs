@@ -0,0 +1,723 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::{
+            domain::{
+                auth_data::AuthData, client_data::ClientData, device_info::DeviceInfo,
+                login_result::LoginResult,
+            },
+            interactors::auth_interactor::ClientVerifier,
+        },
+        oidc::{
+            domain::{
+                authorization_request::AuthorizationRequest,
+                create_identity_user_model::CreateIdentityUserModel, oidc_identity::OidcIdentity,
+            },
+            errors::oidc_errors::{
+                get_email_already_registered_error, get_invalid_state_error,
+                get_provider_email_not_verified_error,
+            },
+        },
+    },
+};
+
+use async_trait::async_trait;
+
+// Talks to the identity providers over HTTP. The http client is not `Send`, so neither are
+// the returned futures.
+#[async_trait(?Send)]
+pub trait OidcProviderClient {
+    fn get_authorization_url(
+        &self,
+        provider: &String,
+        state: &String,
+        nonce: &String,
+        code_challenge: &String,
+    ) -> Result<String, Failure>;
+
+    async fn exchange_code(
+        &self,
+        provider: &String,
+        code: &String,
+        code_verifier: &String,
+        nonce: &String,
+    ) -> Result<OidcIdentity, Failure>;
+}
+
+#[async_trait]
+pub trait AuthorizationRequestStorage {
+    async fn save_authorization_request(
+        &self,
+        request: &AuthorizationRequest,
+    ) -> Result<(), Failure>;
+
+    // Authorization requests can be used only once.
+    async fn take_authorization_request(
+        &self,
+        state: &String,
+    ) -> Result<AuthorizationRequest, Failure>;
+}
+
+#[async_trait]
+pub trait ProfileIdentityRepository {
+    async fn get_identity_user(
+        &self,
+        provider: &String,
+        subject: &String,
+    ) -> Result<Option<AuthData>, Failure>;
+
+    async fn get_user_by_email(&self, email: &String) -> Result<Option<AuthData>, Failure>;
+
+    async fn link_identity(
+        &self,
+        user_id: &i64,
+        provider: &String,
+        subject: &String,
+    ) -> Result<(), Failure>;
+
+    async fn create_user_with_identity(
+        &self,
+        user: &CreateIdentityUserModel,
+    ) -> Result<AuthData, Failure>;
+}
+
+pub trait OidcSecretGenerator {
+    fn generate_state(&self) -> String;
+
+    fn generate_nonce(&self) -> String;
+
+    fn generate_code_verifier(&self) -> String;
+
+    fn get_code_challenge(&self, code_verifier: &String) -> String;
+
+    fn generate_username(&self, email: &String) -> String;
+}
+
+// Issues our own tokens for a user that was authenticated by an identity provider.
+#[async_trait]
+pub trait IdentityAuthenticator {
+    async fn login_with_identity(
+        &self,
+        client: &ClientData,
+        user: &AuthData,
+        device_info: &DeviceInfo,
+    ) -> Result<LoginResult, Failure>;
+}
+
+pub struct OidcInteractor<T, Y, U, I, J, K> {
+    provider_client: T,
+    authorization_request_storage: Y,
+    profile_identity_repository: U,
+    secret_generator: I,
+    client_verifier: J,
+    identity_authenticator: K,
+}
+
+impl<T, Y, U, I, J, K> OidcInteractor<T, Y, U, I, J, K>
+where
+    T: OidcProviderClient,
+    Y: AuthorizationRequestStorage,
+    U: ProfileIdentityRepository,
+    I: OidcSecretGenerator,
+    J: ClientVerifier,
+    K: IdentityAuthenticator,
+{
+    pub fn new(
+        provider_client: T,
+        authorization_request_storage: Y,
+        profile_identity_repository: U,
+        secret_generator: I,
+        client_verifier: J,
+        identity_authenticator: K,
+    ) -> OidcInteractor<T, Y, U, I, J, K> {
+        OidcInteractor {
+            provider_client,
+            authorization_request_storage,
+            profile_identity_repository,
+            secret_generator,
+            client_verifier,
+            identity_authenticator,
+        }
+    }
+
+    pub async fn verify_client(
+        &self,
+        client_id: &String,
+        client_secret: &String,
+    ) -> Result<ClientData, Failure> {
+        self.client_verifier
+            .verify_client(client_id, client_secret)
+            .await
+    }
+
+    pub async fn get_authorization_url(
+        &self,
+        client: &ClientData,
+        provider: &String,
+    ) -> Result<String, Failure> {
+        let request = AuthorizationRequest {
+            state: self.secret_generator.generate_state(),
+            nonce: self.secret_generator.generate_nonce(),
+            code_verifier: self.secret_generator.generate_code_verifier(),
+            provider: provider.clone(),
+            client_id: client.id.clone(),
+        };
+        let code_challenge = self
+            .secret_generator
+            .get_code_challenge(&request.code_verifier);
+        let url = self.provider_client.get_authorization_url(
+            provider,
+            &request.state,
+            &request.nonce,
+            &code_challenge,
+        )?;
+        self.authorization_request_storage
+            .save_authorization_request(&request)
+            .await?;
+
+        Ok(url)
+    }
+
+    pub async fn login(
+        &self,
+        client: &ClientData,
+        provider: &String,
+        code: &String,
+        state: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<LoginResult, Failure> {
+        let request = self
+            .authorization_request_storage
+            .take_authorization_request(state)
+            .await?;
+        if &request.provider != provider || request.client_id != client.id {
+            return Err(get_invalid_state_error());
+        }
+        let identity = self
+            .provider_client
+            .exchange_code(provider, code, &request.code_verifier, &request.nonce)
+            .await?;
+        let user = self.get_identity_user(&identity).await?;
+
+        self.identity_authenticator
+            .login_with_identity(client, &user, device_info)
+            .await
+    }
+
+    async fn get_identity_user(&self, identity: &OidcIdentity) -> Result<AuthData, Failure> {
+        let user = self
+            .profile_identity_repository
+            .get_identity_user(&identity.provider, &identity.subject)
+            .await?;
+        if let Some(user) = user {
+            return Ok(user);
+        }
+
+        let email = match &identity.email {
+            Some(email) if identity.email_verified => email,
+            _ => return Err(get_provider_email_not_verified_error()),
+        };
+        let user = self
+            .profile_identity_repository
+            .get_user_by_email(email)
+            .await?;
+        match user {
+            // Unverified accounts may have been registered by someone else to take over the
+            // account once its owner signs in with the provider.
+            Some(user) if user.verified_at.is_none() => Err(get_email_already_registered_error()),
+            Some(user) => {
+                self.profile_identity_repository
+                    .link_identity(&user.id, &identity.provider, &identity.subject)
+                    .await?;
+                Ok(user)
+            }
+            None => {
+                let model = CreateIdentityUserModel {
+                    username: self.secret_generator.generate_username(email),
+                    email: email.clone(),
+                    provider: identity.provider.clone(),
+                    subject: identity.subject.clone(),
+                };
+                self.profile_identity_repository
+                    .create_user_with_identity(&model)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use mockall::{mock, predicate};
+
+    use crate::features::auth::domain::{
+        grant_type::GrantType, scope::Scope, tokens_pair::TokensPair, user_role::UserRole,
+    };
+
+    use super::*;
+
+    mock! {
+        OidcProviderClient {}
+
+        #[async_trait(?Send)]
+        impl OidcProviderClient for OidcProviderClient {
+            fn get_authorization_url(
+                &self,
+                provider: &String,
+                state: &String,
+                nonce: &String,
+                code_challenge: &String,
+            ) -> Result<String, Failure>;
+
+            async fn exchange_code(
+                &self,
+                provider: &String,
+                code: &String,
+                code_verifier: &String,
+                nonce: &String,
+            ) -> Result<OidcIdentity, Failure>;
+        }
+    }
+
+    mock! {
+        AuthorizationRequestStorage {}
+
+        #[async_trait]
+        impl AuthorizationRequestStorage for AuthorizationRequestStorage {
+            async fn save_authorization_request(
+                &self,
+                request: &AuthorizationRequest,
+            ) -> Result<(), Failure>;
+
+            async fn take_authorization_request(
+                &self,
+                state: &String,
+            ) -> Result<AuthorizationRequest, Failure>;
+        }
+    }
+
+    mock! {
+        ProfileIdentityRepository {}
+
+        #[async_trait]
+        impl ProfileIdentityRepository for ProfileIdentityRepository {
+            async fn get_identity_user(
+                &self,
+                provider: &String,
+                subject: &String,
+            ) -> Result<Option<AuthData>, Failure>;
+
+            async fn get_user_by_email(&self, email: &String) -> Result<Option<AuthData>, Failure>;
+
+            async fn link_identity(
+                &self,
+                user_id: &i64,
+                provider: &String,
+                subject: &String,
+            ) -> Result<(), Failure>;
+
+            async fn create_user_with_identity(
+                &self,
+                user: &CreateIdentityUserModel,
+            ) -> Result<AuthData, Failure>;
+        }
+    }
+
+    mock! {
+        OidcSecretGenerator {}
+
+        impl OidcSecretGenerator for OidcSecretGenerator {
+            fn generate_state(&self) -> String;
+
+            fn generate_nonce(&self) -> String;
+
+            fn generate_code_verifier(&self) -> String;
+
+            fn get_code_challenge(&self, code_verifier: &String) -> String;
+
+            fn generate_username(&self, email: &String) -> String;
+        }
+    }
+
+    mock! {
+        ClientVerifier {}
+
+        #[async_trait]
+        impl ClientVerifier for ClientVerifier {
+            async fn verify_client(
+                &self,
+                client_id: &String,
+                client_secret: &String,
+            ) -> Result<ClientData, Failure>;
+
+            async fn is_client_active(&self, client_id: &String) -> Result<bool, Failure>;
+        }
+    }
+
+    mock! {
+        IdentityAuthenticator {}
+
+        #[async_trait]
+        impl IdentityAuthenticator for IdentityAuthenticator {
+            async fn login_with_identity(
+                &self,
+                client: &ClientData,
+                user: &AuthData,
+                device_info: &DeviceInfo,
+            ) -> Result<LoginResult, Failure>;
+        }
+    }
+
+    fn get_client() -> ClientData {
+        return ClientData {
+            id: "client_id".to_string(),
+            grant_types: vec![GrantType::Password],
+            scopes: vec![Scope::ProfileRead],
+            access_token_exp: None,
+            refresh_token_exp: None,
+        };
+    }
+
+    fn get_authorization_request() -> AuthorizationRequest {
+        return AuthorizationRequest {
+            state: "state".to_string(),
+            nonce: "nonce".to_string(),
+            code_verifier: "code_verifier".to_string(),
+            provider: "google".to_string(),
+            client_id: "client_id".to_string(),
+        };
+    }
+
+    fn get_identity() -> OidcIdentity {
+        return OidcIdentity {
+            provider: "google".to_string(),
+            subject: "subject".to_string(),
+            email: Some("email@test.com".to_string()),
+            email_verified: true,
+        };
+    }
+
+    fn get_user(verified: bool) -> AuthData {
+        return AuthData {
+            id: 1,
+            username: "username".to_string(),
+            email: "email@test.com".to_string(),
+            password: "".to_string(),
+            user_role: UserRole::USER,
+            verified_at: if verified { Some(Utc::now()) } else { None },
+        };
+    }
+
+    fn get_login_result() -> LoginResult {
+        return LoginResult::Authenticated(TokensPair {
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            access_token_exp: 1,
+            refresh_token_exp: 2,
+        });
+    }
+
+    // Dependencies for a login that passes the state check and the code exchange.
+    fn get_login_dependencies() -> (
+        MockOidcProviderClient,
+        MockAuthorizationRequestStorage,
+        MockOidcSecretGenerator,
+        MockClientVerifier,
+        MockIdentityAuthenticator,
+    ) {
+        let mut provider_client = MockOidcProviderClient::new();
+        provider_client
+            .expect_exchange_code()
+            .with(
+                predicate::eq("google".to_string()),
+                predicate::eq("code".to_string()),
+                predicate::eq("code_verifier".to_string()),
+                predicate::eq("nonce".to_string()),
+            )
+            .return_once(|_, __, ___, ____| Ok(get_identity()));
+        let mut storage = MockAuthorizationRequestStorage::new();
+        storage
+            .expect_take_authorization_request()
+            .with(predicate::eq("state".to_string()))
+            .return_once(|_| Ok(get_authorization_request()));
+        let mut identity_authenticator = MockIdentityAuthenticator::new();
+        identity_authenticator
+            .expect_login_with_identity()
+            .with(
+                predicate::eq(get_client()),
+                predicate::function(|user: &AuthData| user.id == 1),
+                predicate::always(),
+            )
+            .return_once(|_, __, ___| Ok(get_login_result()));
+
+        return (
+            provider_client,
+            storage,
+            MockOidcSecretGenerator::new(),
+            MockClientVerifier::new(),
+            identity_authenticator,
+        );
+    }
+
+    async fn login<T, Y, U, I, J, K>(
+        interactor: &OidcInteractor<T, Y, U, I, J, K>,
+    ) -> Result<LoginResult, Failure>
+    where
+        T: OidcProviderClient,
+        Y: AuthorizationRequestStorage,
+        U: ProfileIdentityRepository,
+        I: OidcSecretGenerator,
+        J: ClientVerifier,
+        K: IdentityAuthenticator,
+    {
+        interactor
+            .login(
+                &get_client(),
+                &"google".to_string(),
+                &"code".to_string(),
+                &"state".to_string(),
+                &DeviceInfo::default(),
+            )
+            .await
+    }
+
+    #[actix_rt::test]
+    async fn should_save_authorization_request_and_return_url() {
+        let mut provider_client = MockOidcProviderClient::new();
+        let mut storage = MockAuthorizationRequestStorage::new();
+        let mut secret_generator = MockOidcSecretGenerator::new();
+        secret_generator
+            .expect_generate_state()
+            .return_once(|| "state".to_string());
+        secret_generator
+            .expect_generate_nonce()
+            .return_once(|| "nonce".to_string());
+        secret_generator
+            .expect_generate_code_verifier()
+            .return_once(|| "code_verifier".to_string());
+        secret_generator
+            .expect_get_code_challenge()
+            .with(predicate::eq("code_verifier".to_string()))
+            .return_once(|_| "code_challenge".to_string());
+        provider_client
+            .expect_get_authorization_url()
+            .with(
+                predicate::eq("google".to_string()),
+                predicate::eq("state".to_string()),
+                predicate::eq("nonce".to_string()),
+                predicate::eq("code_challenge".to_string()),
+            )
+            .return_once(|_, __, ___, ____| Ok("url".to_string()));
+        storage
+            .expect_save_authorization_request()
+            .with(predicate::eq(get_authorization_request()))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            MockProfileIdentityRepository::new(),
+            secret_generator,
+            MockClientVerifier::new(),
+            MockIdentityAuthenticator::new(),
+        );
+
+        let result = interactor
+            .get_authorization_url(&get_client(), &"google".to_string())
+            .await;
+
+        assert_eq!(result, Ok("url".to_string()));
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_authorization_request_of_other_client() {
+        let mut storage = MockAuthorizationRequestStorage::new();
+        storage
+            .expect_take_authorization_request()
+            .return_once(|_| {
+                Ok(AuthorizationRequest {
+                    client_id: "other_client_id".to_string(),
+                    ..get_authorization_request()
+                })
+            });
+        let mut provider_client = MockOidcProviderClient::new();
+        provider_client.expect_exchange_code().never();
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            MockProfileIdentityRepository::new(),
+            MockOidcSecretGenerator::new(),
+            MockClientVerifier::new(),
+            MockIdentityAuthenticator::new(),
+        );
+
+        let result = login(&interactor).await;
+
+        assert_eq!(result, Err(get_invalid_state_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_login_user_with_linked_identity() {
+        let (provider_client, storage, secret_generator, client_verifier, identity_authenticator) =
+            get_login_dependencies();
+        let mut repository = MockProfileIdentityRepository::new();
+        repository
+            .expect_get_identity_user()
+            .with(
+                predicate::eq("google".to_string()),
+                predicate::eq("subject".to_string()),
+            )
+            .return_once(|_, __| Ok(Some(get_user(true))));
+        repository.expect_link_identity().never();
+        repository.expect_create_user_with_identity().never();
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            repository,
+            secret_generator,
+            client_verifier,
+            identity_authenticator,
+        );
+
+        let result = login(&interactor).await;
+
+        assert_eq!(result, Ok(get_login_result()));
+    }
+
+    #[actix_rt::test]
+    async fn should_create_verified_user_on_first_login() {
+        let (
+            provider_client,
+            storage,
+            mut secret_generator,
+            client_verifier,
+            identity_authenticator,
+        ) = get_login_dependencies();
+        let mut repository = MockProfileIdentityRepository::new();
+        repository
+            .expect_get_identity_user()
+            .return_once(|_, __| Ok(None));
+        repository
+            .expect_get_user_by_email()
+            .with(predicate::eq("email@test.com".to_string()))
+            .return_once(|_| Ok(None));
+        secret_generator
+            .expect_generate_username()
+            .return_once(|_| "email_1234".to_string());
+        repository
+            .expect_create_user_with_identity()
+            .with(predicate::eq(CreateIdentityUserModel {
+                username: "email_1234".to_string(),
+                email: "email@test.com".to_string(),
+                provider: "google".to_string(),
+                subject: "subject".to_string(),
+            }))
+            .times(1)
+            .return_once(|_| Ok(get_user(true)));
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            repository,
+            secret_generator,
+            client_verifier,
+            identity_authenticator,
+        );
+
+        let result = login(&interactor).await;
+
+        assert_eq!(result, Ok(get_login_result()));
+    }
+
+    #[actix_rt::test]
+    async fn should_link_identity_to_verified_account_with_same_email() {
+        let (provider_client, storage, secret_generator, client_verifier, identity_authenticator) =
+            get_login_dependencies();
+        let mut repository = MockProfileIdentityRepository::new();
+        repository
+            .expect_get_identity_user()
+            .return_once(|_, __| Ok(None));
+        repository
+            .expect_get_user_by_email()
+            .return_once(|_| Ok(Some(get_user(true))));
+        repository
+            .expect_link_identity()
+            .with(
+                predicate::eq(1),
+                predicate::eq("google".to_string()),
+                predicate::eq("subject".to_string()),
+            )
+            .times(1)
+            .return_once(|_, __, ___| Ok(()));
+        repository.expect_create_user_with_identity().never();
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            repository,
+            secret_generator,
+            client_verifier,
+            identity_authenticator,
+        );
+
+        let result = login(&interactor).await;
+
+        assert_eq!(result, Ok(get_login_result()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_link_identity_to_unverified_account() {
+        let (provider_client, storage, secret_generator, client_verifier, _) =
+            get_login_dependencies();
+        let mut repository = MockProfileIdentityRepository::new();
+        repository
+            .expect_get_identity_user()
+            .return_once(|_, __| Ok(None));
+        repository
+            .expect_get_user_by_email()
+            .return_once(|_| Ok(Some(get_user(false))));
+        repository.expect_link_identity().never();
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            repository,
+            secret_generator,
+            client_verifier,
+            MockIdentityAuthenticator::new(),
+        );
+
+        let result = login(&interactor).await;
+
+        assert_eq!(result, Err(get_email_already_registered_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_require_email_verified_by_provider() {
+        let (_, storage, secret_generator, client_verifier, _) = get_login_dependencies();
+        let mut provider_client = MockOidcProviderClient::new();
+        provider_client
+            .expect_exchange_code()
+            .return_once(|_, __, ___, ____| {
+                Ok(OidcIdentity {
+                    email_verified: false,
+                    ..get_identity()
+                })
+            });
+        let mut repository = MockProfileIdentityRepository::new();
+        repository
+            .expect_get_identity_user()
+            .return_once(|_, __| Ok(None));
+        repository.expect_get_user_by_email().never();
+        let interactor = OidcInteractor::new(
+            provider_client,
+            storage,
+            repository,
+            secret_generator,
+            client_verifier,
+            MockIdentityAuthenticator::new(),
+        );
+
+        let result = login(&interactor).await;
+
+        assert_eq!(result, Err(get_provider_email_not_verified_error()));
+    }
+}
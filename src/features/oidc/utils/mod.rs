@@ -0,0 +1 @@
+pub mod oidc_secret_generator;
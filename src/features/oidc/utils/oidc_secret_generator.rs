@@ -0,0 +1,91 @@
+use nanoid::nanoid;
+use ring::digest::{digest, SHA256};
+
+use crate::features::oidc::interactors::oidc_interactor::OidcSecretGenerator;
+
+const STATE_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 32;
+// RFC 7636 allows 43 to 128 characters, nanoid only uses the allowed unreserved ones.
+const CODE_VERIFIER_LENGTH: usize = 64;
+const USERNAME_PREFIX_LENGTH: usize = 32;
+const USERNAME_SUFFIX_LENGTH: usize = 8;
+const USERNAME_SUFFIX_ALPHABET: [char; 36] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+pub struct RandomOidcSecretGenerator;
+
+impl RandomOidcSecretGenerator {
+    pub fn new() -> RandomOidcSecretGenerator {
+        return RandomOidcSecretGenerator {};
+    }
+}
+
+impl OidcSecretGenerator for RandomOidcSecretGenerator {
+    fn generate_state(&self) -> String {
+        return nanoid!(STATE_LENGTH);
+    }
+
+    fn generate_nonce(&self) -> String {
+        return nanoid!(NONCE_LENGTH);
+    }
+
+    fn generate_code_verifier(&self) -> String {
+        return nanoid!(CODE_VERIFIER_LENGTH);
+    }
+
+    fn get_code_challenge(&self, code_verifier: &String) -> String {
+        let hash = digest(&SHA256, code_verifier.as_bytes());
+
+        return base64::encode_config(hash.as_ref(), base64::URL_SAFE_NO_PAD);
+    }
+
+    // Usernames are unique, so the part of the email before `@` gets a random suffix.
+    fn generate_username(&self, email: &String) -> String {
+        let prefix: String = email
+            .split('@')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.' || *c == '-')
+            .take(USERNAME_PREFIX_LENGTH)
+            .collect();
+        let prefix = if prefix.is_empty() {
+            "user".to_string()
+        } else {
+            prefix
+        };
+
+        return format!(
+            "{}_{}",
+            prefix,
+            nanoid!(USERNAME_SUFFIX_LENGTH, &USERNAME_SUFFIX_ALPHABET)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_get_s256_code_challenge() {
+        let generator = RandomOidcSecretGenerator::new();
+
+        let result = generator
+            .get_code_challenge(&"dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+
+        assert_eq!(result, "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+    }
+
+    #[test]
+    fn should_generate_username_from_email() {
+        let generator = RandomOidcSecretGenerator::new();
+
+        let result = generator.generate_username(&"john+test@email.com".to_string());
+
+        assert!(result.starts_with("johntest_"));
+        assert_eq!(result.len(), "johntest_".len() + USERNAME_SUFFIX_LENGTH);
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::features::personal_access_token::domain::create_personal_access_token_model::CreatePersonalAccessTokenModel;
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePersonalAccessTokenDto {
+    name: String,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<CreatePersonalAccessTokenDto> for CreatePersonalAccessTokenModel {
+    fn from(dto: CreatePersonalAccessTokenDto) -> CreatePersonalAccessTokenModel {
+        return CreatePersonalAccessTokenModel {
+            name: dto.name,
+            scopes: dto.scopes,
+            expires_at: dto.expires_at,
+        };
+    }
+}
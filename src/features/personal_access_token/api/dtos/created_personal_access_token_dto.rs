@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+use crate::features::personal_access_token::domain::created_personal_access_token::CreatedPersonalAccessToken;
+
+use super::personal_access_token_dto::PersonalAccessTokenDto;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedPersonalAccessTokenDto {
+    #[serde(flatten)]
+    pub personal_access_token: PersonalAccessTokenDto,
+    pub token: String,
+}
+
+impl From<CreatedPersonalAccessToken> for CreatedPersonalAccessTokenDto {
+    fn from(created: CreatedPersonalAccessToken) -> Self {
+        return CreatedPersonalAccessTokenDto {
+            personal_access_token: created.personal_access_token.into(),
+            token: created.token,
+        };
+    }
+}
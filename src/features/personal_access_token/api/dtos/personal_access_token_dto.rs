@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::features::personal_access_token::domain::personal_access_token::PersonalAccessToken;
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalAccessTokenDto {
+    id: String,
+    name: String,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessToken> for PersonalAccessTokenDto {
+    fn from(token: PersonalAccessToken) -> Self {
+        return PersonalAccessTokenDto {
+            id: token.id,
+            name: token.name,
+            scopes: token.scopes.iter().map(String::from).collect(),
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            created_at: token.created_at,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::auth::domain::scope::Scope;
+
+    use super::*;
+
+    #[test]
+    fn should_map_model_to_dto_without_hash() {
+        let token = PersonalAccessToken {
+            id: "token_id".to_string(),
+            user_id: 1,
+            name: "ci".to_string(),
+            token_hash: "token_hash".to_string(),
+            scopes: vec![Scope::ProfileRead],
+            expires_at: None,
+            last_used_at: None,
+            created_at: Utc::now(),
+        };
+
+        let dto = PersonalAccessTokenDto::from(token);
+        let json = serde_json::to_value(&dto).unwrap();
+
+        assert_eq!(json["id"], "token_id");
+        assert_eq!(json["scopes"][0], "profile:read");
+        assert!(json["expiresAt"].is_null());
+        assert!(json.get("tokenHash").is_none());
+    }
+}
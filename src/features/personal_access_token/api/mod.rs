@@ -0,0 +1,2 @@
+pub mod dtos;
+pub mod personal_access_token_controller;
@@ -0,0 +1,94 @@
+use actix_web::{
+    delete, get,
+    http::StatusCode,
+    post,
+    web::{self, ServiceConfig},
+    HttpResponse, Responder,
+};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+use crate::{
+    common::failure::failure_handler::handle_failure,
+    features::{
+        auth::api::{
+            auth_middleware::verify_current_user,
+            scoped_user::{ProfileRead, ProfileWrite, ScopedUser},
+        },
+        personal_access_token::{
+            infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+            interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+            utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+        },
+    },
+};
+
+use super::dtos::{
+    create_personal_access_token_dto::CreatePersonalAccessTokenDto,
+    created_personal_access_token_dto::CreatedPersonalAccessTokenDto,
+    personal_access_token_dto::PersonalAccessTokenDto,
+};
+
+type Interactor = PersonalAccessTokenInteractor<
+    PersonalAccessTokenRepositoryImpl,
+    RandomPersonalAccessTokenGenerator,
+>;
+
+// Has to be configured before the profile controller, whose `/profile` scope would
+// otherwise swallow these routes.
+pub fn configure_personal_access_token_controller(config: &mut ServiceConfig) {
+    let auth_middleware = HttpAuthentication::bearer(verify_current_user);
+    config.service(
+        web::scope("/profile/me/tokens")
+            .wrap(auth_middleware)
+            .service(get_tokens)
+            .service(create_token)
+            .service(revoke_token),
+    );
+}
+
+#[get("")]
+async fn get_tokens(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileRead>,
+) -> impl Responder {
+    let result = interactor.get_tokens(&current_user.id).await;
+    match result {
+        Ok(tokens) => HttpResponse::Ok().json(
+            tokens
+                .into_iter()
+                .map(PersonalAccessTokenDto::from)
+                .collect::<Vec<PersonalAccessTokenDto>>(),
+        ),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("")]
+async fn create_token(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+    dto: web::Json<CreatePersonalAccessTokenDto>,
+) -> impl Responder {
+    let result = interactor
+        .create_token(&current_user, &dto.into_inner().into())
+        .await;
+    match result {
+        Ok(created) => HttpResponse::Ok().json(CreatedPersonalAccessTokenDto::from(created)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[delete("/{id}")]
+async fn revoke_token(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let result = interactor
+        .revoke_token(&current_user.id, &path.into_inner())
+        .await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
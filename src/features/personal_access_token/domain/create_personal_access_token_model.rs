@@ -0,0 +1,8 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreatePersonalAccessTokenModel {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,8 @@
+use super::personal_access_token::PersonalAccessToken;
+
+// The plain token is only returned once, afterwards just its hash is stored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreatedPersonalAccessToken {
+    pub personal_access_token: PersonalAccessToken,
+    pub token: String,
+}
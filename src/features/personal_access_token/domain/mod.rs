@@ -0,0 +1,3 @@
+pub mod create_personal_access_token_model;
+pub mod created_personal_access_token;
+pub mod personal_access_token;
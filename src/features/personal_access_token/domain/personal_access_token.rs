@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::auth::domain::scope::Scope;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PersonalAccessToken {
+    pub id: String,
+    pub user_id: i64,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PersonalAccessToken {
+    pub fn is_expired(&self, now: &DateTime<Utc>) -> bool {
+        return matches!(self.expires_at, Some(expires_at) if &expires_at <= now);
+    }
+}
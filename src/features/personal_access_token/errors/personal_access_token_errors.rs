@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use crate::common::failure::domain::failure::{Failure, FailureType};
+
+pub fn get_db_connection_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_error".to_string(),
+        message: "Failed to connect to db".to_string(),
+    };
+}
+
+pub fn get_unknown_personal_access_token_update_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Unknown,
+        args: None,
+        code: "error.unknown_personal_access_token_update_error".to_string(),
+        message: "Unknown personal access token update error".to_string(),
+    };
+}
+
+pub fn get_personal_access_token_not_found_error() -> Failure {
+    return Failure {
+        error_type: FailureType::NotFound,
+        args: None,
+        code: "error.personal_access_token_not_found".to_string(),
+        message: "Personal access token not found".to_string(),
+    };
+}
+
+pub fn get_invalid_personal_access_token_name_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.invalid_personal_access_token_name".to_string(),
+        message: "Personal access token name must not be empty".to_string(),
+    };
+}
+
+pub fn get_scopes_required_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.scopes_required".to_string(),
+        message: "Personal access token must have at least one scope".to_string(),
+    };
+}
+
+pub fn get_invalid_scope_error(scope: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("scope".to_string(), scope.clone());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_scope".to_string(),
+        message: format!("Unknown scope '{}'", scope),
+        args: Some(args),
+    };
+}
+
+pub fn get_invalid_expiration_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        args: None,
+        code: "error.invalid_expiration".to_string(),
+        message: "Expiration date must be in the future".to_string(),
+    };
+}
@@ -0,0 +1,2 @@
+pub mod new_personal_access_token;
+pub mod personal_access_token_entity;
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    features::personal_access_token::domain::personal_access_token::PersonalAccessToken,
+    schema::personal_access_token,
+};
+
+#[derive(Insertable, Debug, PartialEq, Clone)]
+#[table_name = "personal_access_token"]
+pub struct NewPersonalAccessToken {
+    pub id: String,
+    pub profile_id: i64,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessToken> for NewPersonalAccessToken {
+    fn from(token: PersonalAccessToken) -> Self {
+        return NewPersonalAccessToken {
+            id: token.id,
+            profile_id: token.user_id,
+            name: token.name,
+            token_hash: token.token_hash,
+            scopes: token.scopes.iter().map(String::from).collect(),
+            expires_at: token.expires_at,
+            created_at: token.created_at,
+        };
+    }
+}
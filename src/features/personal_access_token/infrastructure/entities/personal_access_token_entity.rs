@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::{
+    auth::domain::scope::Scope,
+    personal_access_token::domain::personal_access_token::PersonalAccessToken,
+};
+
+#[derive(Queryable, Debug, PartialEq, Clone)]
+pub struct PersonalAccessTokenEntity {
+    pub id: String,
+    pub profile_id: i64,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessTokenEntity> for PersonalAccessToken {
+    fn from(entity: PersonalAccessTokenEntity) -> Self {
+        return PersonalAccessToken {
+            id: entity.id,
+            user_id: entity.profile_id,
+            name: entity.name,
+            token_hash: entity.token_hash,
+            scopes: entity
+                .scopes
+                .iter()
+                .filter_map(|scope| Scope::parse(scope))
+                .collect(),
+            expires_at: entity.expires_at,
+            last_used_at: entity.last_used_at,
+            created_at: entity.created_at,
+        };
+    }
+}
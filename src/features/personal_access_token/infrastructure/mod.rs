@@ -0,0 +1,2 @@
+pub mod entities;
+pub mod personal_access_token_repository_impl;
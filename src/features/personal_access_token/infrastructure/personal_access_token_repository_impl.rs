@@ -0,0 +1,125 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::domain::user_role::UserRole,
+        personal_access_token::{
+            domain::personal_access_token::PersonalAccessToken,
+            errors::personal_access_token_errors::{
+                get_db_connection_error, get_unknown_personal_access_token_update_error,
+            },
+            interactors::personal_access_token_interactor::PersonalAccessTokenRepository,
+        },
+    },
+    schema::{personal_access_token, profile},
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{r2d2::ConnectionManager, PgConnection};
+use r2d2::{Pool, PooledConnection};
+
+use super::entities::{
+    new_personal_access_token::NewPersonalAccessToken,
+    personal_access_token_entity::PersonalAccessTokenEntity,
+};
+
+pub struct PersonalAccessTokenRepositoryImpl {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl PersonalAccessTokenRepositoryImpl {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> PersonalAccessTokenRepositoryImpl {
+        return PersonalAccessTokenRepositoryImpl { pool };
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>, Failure> {
+        let pool = self.pool.get();
+        return match pool {
+            Ok(pool) => Ok(pool),
+            _ => Err(get_db_connection_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepository for PersonalAccessTokenRepositoryImpl {
+    async fn save_token(&self, token: &PersonalAccessToken) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::insert_into(personal_access_token::table)
+            .values(&NewPersonalAccessToken::from(token.to_owned()))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_personal_access_token_update_error()),
+        };
+    }
+
+    async fn get_user_tokens(&self, user_id: &i64) -> Result<Vec<PersonalAccessToken>, Failure> {
+        let connection = self.get_connection()?;
+        let result = personal_access_token::table
+            .filter(personal_access_token::profile_id.eq(user_id))
+            .order(personal_access_token::created_at.desc())
+            .load::<PersonalAccessTokenEntity>(&connection);
+
+        return match result {
+            Ok(entities) => Ok(entities.into_iter().map(|entity| entity.into()).collect()),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn get_token_by_hash(
+        &self,
+        token_hash: &String,
+    ) -> Result<Option<(PersonalAccessToken, UserRole)>, Failure> {
+        let connection = self.get_connection()?;
+        let result = personal_access_token::table
+            .inner_join(profile::table)
+            .filter(personal_access_token::token_hash.eq(token_hash))
+            .select((personal_access_token::all_columns, profile::role))
+            .get_result::<(PersonalAccessTokenEntity, String)>(&connection)
+            .optional();
+
+        return match result {
+            Ok(result) => Ok(result.map(|(entity, role)| (entity.into(), role.as_str().into()))),
+            Err(_) => Err(get_db_connection_error()),
+        };
+    }
+
+    async fn update_last_used(&self, token_id: &String) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let result = diesel::update(personal_access_token::table.find(token_id))
+            .set(personal_access_token::last_used_at.eq(Utc::now()))
+            .execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_personal_access_token_update_error()),
+        };
+    }
+
+    async fn delete_token(&self, user_id: &i64, token_id: &String) -> Result<bool, Failure> {
+        let connection = self.get_connection()?;
+        let target = personal_access_token::table
+            .filter(personal_access_token::id.eq(token_id))
+            .filter(personal_access_token::profile_id.eq(user_id));
+        let result = diesel::delete(target).execute(&connection);
+
+        return match result {
+            Ok(deleted) => Ok(deleted == 1),
+            Err(_) => Err(get_unknown_personal_access_token_update_error()),
+        };
+    }
+
+    async fn delete_user_tokens(&self, user_id: &i64) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let target =
+            personal_access_token::table.filter(personal_access_token::profile_id.eq(user_id));
+        let result = diesel::delete(target).execute(&connection);
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_unknown_personal_access_token_update_error()),
+        };
+    }
+}
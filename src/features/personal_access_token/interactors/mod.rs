@@ -0,0 +1 @@
+pub mod personal_access_token_interactor;
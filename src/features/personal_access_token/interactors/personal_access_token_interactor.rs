@@ -0,0 +1,643 @@
+use crate::{
+    common::failure::domain::failure::Failure,
+    features::{
+        auth::{
+            domain::{
+                current_user_data::CurrentUserData, introspected_token_type::IntrospectedTokenType,
+                scope::Scope, token_introspection::TokenIntrospection, user_role::UserRole,
+            },
+            errors::{
+                auth_errors::get_insufficient_scope_error,
+                token_errors::get_invalid_access_token_error,
+            },
+            interactors::auth_interactor::PersonalAccessTokenVerifier,
+        },
+        impersonation::errors::impersonation_errors::get_not_allowed_while_impersonating_error,
+        personal_access_token::{
+            domain::{
+                create_personal_access_token_model::CreatePersonalAccessTokenModel,
+                created_personal_access_token::CreatedPersonalAccessToken,
+                personal_access_token::PersonalAccessToken,
+            },
+            errors::personal_access_token_errors::{
+                get_invalid_expiration_error, get_invalid_personal_access_token_name_error,
+                get_invalid_scope_error, get_personal_access_token_not_found_error,
+                get_scopes_required_error,
+            },
+        },
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+
+#[async_trait]
+pub trait PersonalAccessTokenRepository {
+    async fn save_token(&self, token: &PersonalAccessToken) -> Result<(), Failure>;
+
+    async fn get_user_tokens(&self, user_id: &i64) -> Result<Vec<PersonalAccessToken>, Failure>;
+
+    async fn get_token_by_hash(
+        &self,
+        token_hash: &String,
+    ) -> Result<Option<(PersonalAccessToken, UserRole)>, Failure>;
+
+    async fn update_last_used(&self, token_id: &String) -> Result<(), Failure>;
+
+    async fn delete_token(&self, user_id: &i64, token_id: &String) -> Result<bool, Failure>;
+
+    async fn delete_user_tokens(&self, user_id: &i64) -> Result<(), Failure>;
+}
+
+// Personal access tokens do not belong to a session and outlive the `tokens_valid_after`
+// cut-off, so signing out everywhere and resetting the password revoke them explicitly.
+#[async_trait]
+pub trait PersonalAccessTokenRevoker {
+    async fn revoke_user_tokens(&self, user_id: &i64) -> Result<(), Failure>;
+}
+
+pub trait PersonalAccessTokenGenerator {
+    fn generate_token_id(&self) -> String;
+
+    fn generate_token(&self) -> String;
+
+    fn is_personal_access_token(&self, token: &String) -> bool;
+
+    fn hash_token(&self, token: &String) -> String;
+}
+
+pub struct PersonalAccessTokenInteractor<T, Y> {
+    token_repository: T,
+    token_generator: Y,
+}
+
+impl<T, Y> PersonalAccessTokenInteractor<T, Y>
+where
+    T: PersonalAccessTokenRepository,
+    Y: PersonalAccessTokenGenerator,
+{
+    pub fn new(token_repository: T, token_generator: Y) -> PersonalAccessTokenInteractor<T, Y> {
+        return PersonalAccessTokenInteractor {
+            token_repository,
+            token_generator,
+        };
+    }
+
+    pub async fn create_token(
+        &self,
+        current_user: &CurrentUserData,
+        model: &CreatePersonalAccessTokenModel,
+    ) -> Result<CreatedPersonalAccessToken, Failure> {
+        // The token would outlive the impersonation and be used without the audit log.
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        let name = model.name.trim().to_string();
+        if name.is_empty() {
+            return Err(get_invalid_personal_access_token_name_error());
+        }
+        if model.scopes.is_empty() {
+            return Err(get_scopes_required_error());
+        }
+        let scopes = model
+            .scopes
+            .iter()
+            .map(|scope| Scope::parse(scope).ok_or(get_invalid_scope_error(scope.clone())))
+            .collect::<Result<Vec<Scope>, Failure>>()?;
+        // Otherwise a client with limited scopes could mint itself a broader token.
+        if let Some(scope) = scopes
+            .iter()
+            .find(|scope| !current_user.scopes.contains(scope))
+        {
+            return Err(get_insufficient_scope_error(String::from(scope)));
+        }
+        let now = Utc::now();
+        if matches!(model.expires_at, Some(expires_at) if expires_at <= now) {
+            return Err(get_invalid_expiration_error());
+        }
+
+        let token = self.token_generator.generate_token();
+        let personal_access_token = PersonalAccessToken {
+            id: self.token_generator.generate_token_id(),
+            user_id: current_user.id,
+            name,
+            token_hash: self.token_generator.hash_token(&token),
+            scopes,
+            expires_at: model.expires_at,
+            last_used_at: None,
+            created_at: now,
+        };
+        self.token_repository
+            .save_token(&personal_access_token)
+            .await?;
+
+        Ok(CreatedPersonalAccessToken {
+            personal_access_token,
+            token,
+        })
+    }
+
+    pub async fn get_tokens(&self, user_id: &i64) -> Result<Vec<PersonalAccessToken>, Failure> {
+        self.token_repository.get_user_tokens(user_id).await
+    }
+
+    pub async fn revoke_token(&self, user_id: &i64, token_id: &String) -> Result<(), Failure> {
+        let deleted = self
+            .token_repository
+            .delete_token(user_id, token_id)
+            .await?;
+
+        if deleted {
+            Ok(())
+        } else {
+            Err(get_personal_access_token_not_found_error())
+        }
+    }
+}
+
+#[async_trait]
+impl<T, Y> PersonalAccessTokenVerifier for PersonalAccessTokenInteractor<T, Y>
+where
+    T: PersonalAccessTokenRepository + Send + Sync,
+    Y: PersonalAccessTokenGenerator + Send + Sync,
+{
+    async fn verify_personal_access_token(
+        &self,
+        token: &String,
+    ) -> Result<Option<CurrentUserData>, Failure> {
+        if !self.token_generator.is_personal_access_token(token) {
+            return Ok(None);
+        }
+        let token_hash = self.token_generator.hash_token(token);
+        let (personal_access_token, role) =
+            match self.token_repository.get_token_by_hash(&token_hash).await? {
+                Some((token, _)) if token.is_expired(&Utc::now()) => {
+                    return Err(get_invalid_access_token_error())
+                }
+                Some(result) => result,
+                None => return Err(get_invalid_access_token_error()),
+            };
+        self.token_repository
+            .update_last_used(&personal_access_token.id)
+            .await?;
+
+        Ok(Some(CurrentUserData {
+            id: personal_access_token.user_id,
+            role,
+            session_id: None,
+            actor_id: None,
+            client_id: None,
+            scopes: personal_access_token.scopes,
+        }))
+    }
+
+    async fn introspect_personal_access_token(
+        &self,
+        token: &String,
+    ) -> Result<Option<TokenIntrospection>, Failure> {
+        if !self.token_generator.is_personal_access_token(token) {
+            return Ok(None);
+        }
+        let token_hash = self.token_generator.hash_token(token);
+        let (personal_access_token, role) =
+            match self.token_repository.get_token_by_hash(&token_hash).await? {
+                Some((token, _)) if token.is_expired(&Utc::now()) => return Ok(None),
+                Some(result) => result,
+                None => return Ok(None),
+            };
+
+        Ok(Some(TokenIntrospection {
+            user_id: personal_access_token.user_id,
+            role,
+            // Personal access tokens are used like access tokens.
+            token_type: IntrospectedTokenType::Access,
+            client_id: None,
+            scopes: personal_access_token.scopes,
+            actor_id: None,
+            issued_at: Some(personal_access_token.created_at.timestamp()),
+            expires_at: personal_access_token
+                .expires_at
+                .map(|expires_at| expires_at.timestamp()),
+        }))
+    }
+}
+
+#[async_trait]
+impl<T, Y> PersonalAccessTokenRevoker for PersonalAccessTokenInteractor<T, Y>
+where
+    T: PersonalAccessTokenRepository + Send + Sync,
+    Y: PersonalAccessTokenGenerator + Send + Sync,
+{
+    async fn revoke_user_tokens(&self, user_id: &i64) -> Result<(), Failure> {
+        self.token_repository.delete_user_tokens(user_id).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+    use mockall::predicate::*;
+    use mockall::*;
+
+    use super::*;
+
+    mock! {
+        PersonalAccessTokenRepository {}
+
+        #[async_trait]
+        impl PersonalAccessTokenRepository for PersonalAccessTokenRepository {
+            async fn save_token(&self, token: &PersonalAccessToken) -> Result<(), Failure>;
+
+            async fn get_user_tokens(&self, user_id: &i64) -> Result<Vec<PersonalAccessToken>, Failure>;
+
+            async fn get_token_by_hash(
+                &self,
+                token_hash: &String,
+            ) -> Result<Option<(PersonalAccessToken, UserRole)>, Failure>;
+
+            async fn update_last_used(&self, token_id: &String) -> Result<(), Failure>;
+
+            async fn delete_token(&self, user_id: &i64, token_id: &String) -> Result<bool, Failure>;
+
+            async fn delete_user_tokens(&self, user_id: &i64) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        PersonalAccessTokenGenerator {}
+
+        impl PersonalAccessTokenGenerator for PersonalAccessTokenGenerator {
+            fn generate_token_id(&self) -> String;
+
+            fn generate_token(&self) -> String;
+
+            fn is_personal_access_token(&self, token: &String) -> bool;
+
+            fn hash_token(&self, token: &String) -> String;
+        }
+    }
+
+    fn get_dependencies() -> (
+        MockPersonalAccessTokenRepository,
+        MockPersonalAccessTokenGenerator,
+    ) {
+        let repository = MockPersonalAccessTokenRepository::new();
+        let token_generator = MockPersonalAccessTokenGenerator::new();
+
+        return (repository, token_generator);
+    }
+
+    fn get_personal_access_token(expires_in: Option<Duration>) -> PersonalAccessToken {
+        return PersonalAccessToken {
+            id: "token_id".to_string(),
+            user_id: 1,
+            name: "ci".to_string(),
+            token_hash: "token_hash".to_string(),
+            scopes: vec![Scope::ProfileRead],
+            expires_at: expires_in.map(|expires_in| Utc::now() + expires_in),
+            last_used_at: None,
+            created_at: Utc::now(),
+        };
+    }
+
+    fn get_current_user() -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role: UserRole::USER,
+            session_id: Some("session_id".to_string()),
+            actor_id: None,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![Scope::ProfileRead, Scope::ProfileWrite],
+        };
+    }
+
+    fn get_create_model(
+        name: &str,
+        scopes: Vec<&str>,
+        expires_in: Option<Duration>,
+    ) -> CreatePersonalAccessTokenModel {
+        return CreatePersonalAccessTokenModel {
+            name: name.to_string(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_at: expires_in.map(|expires_in| Utc::now() + expires_in),
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_create_token_with_hashed_secret() {
+        let (mut repository, mut token_generator) = get_dependencies();
+
+        token_generator
+            .expect_generate_token()
+            .return_once(|| "vvp_token".to_string());
+        token_generator
+            .expect_generate_token_id()
+            .return_once(|| "token_id".to_string());
+        token_generator
+            .expect_hash_token()
+            .with(eq("vvp_token".to_string()))
+            .return_once(|_| "token_hash".to_string());
+        repository
+            .expect_save_token()
+            .withf(|token| {
+                token.id == "token_id"
+                    && token.user_id == 1
+                    && token.name == "ci"
+                    && token.token_hash == "token_hash"
+                    && token.scopes == vec![Scope::ProfileRead]
+            })
+            .return_once(|_| Ok(()));
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor
+            .create_token(
+                &get_current_user(),
+                &get_create_model(" ci ", vec!["profile:read"], Some(Duration::days(30))),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.token, "vvp_token".to_string());
+        assert!(result.personal_access_token.expires_at.is_some());
+    }
+
+    #[actix_rt::test]
+    async fn should_not_create_token_while_impersonating() {
+        let (mut repository, token_generator) = get_dependencies();
+
+        repository.expect_save_token().never();
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor
+            .create_token(
+                &CurrentUserData {
+                    actor_id: Some(2),
+                    ..get_current_user()
+                },
+                &get_create_model("ci", vec!["profile:read"], None),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_not_allowed_while_impersonating_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_validate_created_token() {
+        let (mut repository, token_generator) = get_dependencies();
+
+        repository.expect_save_token().never();
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let name_result = interactor
+            .create_token(
+                &get_current_user(),
+                &get_create_model(" ", vec!["profile:read"], None),
+            )
+            .await;
+        let scopes_result = interactor
+            .create_token(&get_current_user(), &get_create_model("ci", vec![], None))
+            .await;
+        let expiration_result = interactor
+            .create_token(
+                &get_current_user(),
+                &get_create_model("ci", vec!["profile:read"], Some(Duration::days(-1))),
+            )
+            .await;
+
+        assert_eq!(
+            name_result,
+            Err(get_invalid_personal_access_token_name_error())
+        );
+        assert_eq!(scopes_result, Err(get_scopes_required_error()));
+        assert_eq!(expiration_result, Err(get_invalid_expiration_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_create_token_with_broader_scopes() {
+        let (mut repository, token_generator) = get_dependencies();
+
+        repository.expect_save_token().never();
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let unknown_result = interactor
+            .create_token(
+                &get_current_user(),
+                &get_create_model("ci", vec!["profile:delete"], None),
+            )
+            .await;
+        let broader_result = interactor
+            .create_token(
+                &get_current_user(),
+                &get_create_model("ci", vec!["profile:read", "posts:write"], None),
+            )
+            .await;
+
+        assert_eq!(
+            unknown_result,
+            Err(get_invalid_scope_error("profile:delete".to_string()))
+        );
+        assert_eq!(
+            broader_result,
+            Err(get_insufficient_scope_error("posts:write".to_string()))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_revoke_all_tokens_of_user() {
+        let (mut repository, token_generator) = get_dependencies();
+
+        repository
+            .expect_delete_user_tokens()
+            .with(eq(1))
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor.revoke_user_tokens(&1).await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_revoked_token_not_found() {
+        let (mut repository, token_generator) = get_dependencies();
+
+        repository
+            .expect_delete_token()
+            .with(eq(1), eq("token_id".to_string()))
+            .return_once(|_, __| Ok(false));
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor.revoke_token(&1, &"token_id".to_string()).await;
+
+        assert_eq!(result, Err(get_personal_access_token_not_found_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_skip_tokens_without_prefix() {
+        let (mut repository, mut token_generator) = get_dependencies();
+
+        token_generator
+            .expect_is_personal_access_token()
+            .return_once(|_| false);
+        repository.expect_get_token_by_hash().never();
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor
+            .verify_personal_access_token(&"jwt".to_string())
+            .await;
+
+        assert_eq!(result, Ok(None));
+    }
+
+    #[actix_rt::test]
+    async fn should_resolve_token_to_current_user() {
+        let (mut repository, mut token_generator) = get_dependencies();
+
+        token_generator
+            .expect_is_personal_access_token()
+            .return_once(|_| true);
+        token_generator
+            .expect_hash_token()
+            .with(eq("vvp_token".to_string()))
+            .return_once(|_| "token_hash".to_string());
+        repository
+            .expect_get_token_by_hash()
+            .with(eq("token_hash".to_string()))
+            .return_once(|_| Ok(Some((get_personal_access_token(None), UserRole::MODERATOR))));
+        repository
+            .expect_update_last_used()
+            .with(eq("token_id".to_string()))
+            .return_once(|_| Ok(()));
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor
+            .verify_personal_access_token(&"vvp_token".to_string())
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(Some(CurrentUserData {
+                id: 1,
+                role: UserRole::MODERATOR,
+                session_id: None,
+                actor_id: None,
+                client_id: None,
+                scopes: vec![Scope::ProfileRead],
+            }))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_unknown_and_expired_tokens() {
+        let (mut repository, mut token_generator) = get_dependencies();
+
+        token_generator
+            .expect_is_personal_access_token()
+            .times(2)
+            .returning(|_| true);
+        token_generator
+            .expect_hash_token()
+            .times(2)
+            .returning(|_| "token_hash".to_string());
+        let mut responses = vec![
+            None,
+            Some((
+                get_personal_access_token(Some(Duration::seconds(-1))),
+                UserRole::USER,
+            )),
+        ]
+        .into_iter();
+        repository
+            .expect_get_token_by_hash()
+            .times(2)
+            .returning(move |_| Ok(responses.next().unwrap()));
+        repository.expect_update_last_used().never();
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let unknown_result = interactor
+            .verify_personal_access_token(&"vvp_unknown".to_string())
+            .await;
+        let expired_result = interactor
+            .verify_personal_access_token(&"vvp_expired".to_string())
+            .await;
+
+        assert_eq!(unknown_result, Err(get_invalid_access_token_error()));
+        assert_eq!(expired_result, Err(get_invalid_access_token_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_introspect_expiring_token_without_using_it() {
+        let (mut repository, mut token_generator) = get_dependencies();
+        let personal_access_token = get_personal_access_token(Some(Duration::days(30)));
+        let expires_at = personal_access_token.expires_at.unwrap().timestamp();
+        let created_at = personal_access_token.created_at.timestamp();
+
+        token_generator
+            .expect_is_personal_access_token()
+            .return_once(|_| true);
+        token_generator
+            .expect_hash_token()
+            .with(eq("vvp_token".to_string()))
+            .return_once(|_| "token_hash".to_string());
+        repository
+            .expect_get_token_by_hash()
+            .with(eq("token_hash".to_string()))
+            .return_once(move |_| Ok(Some((personal_access_token, UserRole::USER))));
+        repository.expect_update_last_used().never();
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor
+            .introspect_personal_access_token(&"vvp_token".to_string())
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(Some(TokenIntrospection {
+                user_id: 1,
+                role: UserRole::USER,
+                token_type: IntrospectedTokenType::Access,
+                client_id: None,
+                scopes: vec![Scope::ProfileRead],
+                actor_id: None,
+                issued_at: Some(created_at),
+                expires_at: Some(expires_at),
+            }))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn should_report_expired_token_as_inactive() {
+        let (mut repository, mut token_generator) = get_dependencies();
+
+        token_generator
+            .expect_is_personal_access_token()
+            .return_once(|_| true);
+        token_generator
+            .expect_hash_token()
+            .return_once(|_| "token_hash".to_string());
+        repository.expect_get_token_by_hash().return_once(|_| {
+            Ok(Some((
+                get_personal_access_token(Some(Duration::seconds(-1))),
+                UserRole::USER,
+            )))
+        });
+
+        let interactor = PersonalAccessTokenInteractor::new(repository, token_generator);
+
+        let result = interactor
+            .introspect_personal_access_token(&"vvp_expired".to_string())
+            .await;
+
+        assert_eq!(result, Ok(None));
+    }
+}
@@ -0,0 +1,58 @@
+use nanoid::nanoid;
+use ring::digest::{digest, SHA256};
+
+use crate::features::personal_access_token::interactors::personal_access_token_interactor::PersonalAccessTokenGenerator;
+
+// The prefix tells personal access tokens apart from JWTs without a database lookup and
+// makes leaked tokens easy to find with secret scanners.
+const TOKEN_PREFIX: &str = "vvp_";
+const TOKEN_ID_LENGTH: usize = 16;
+const TOKEN_LENGTH: usize = 40;
+
+pub struct RandomPersonalAccessTokenGenerator;
+
+impl RandomPersonalAccessTokenGenerator {
+    pub fn new() -> RandomPersonalAccessTokenGenerator {
+        return RandomPersonalAccessTokenGenerator {};
+    }
+}
+
+impl PersonalAccessTokenGenerator for RandomPersonalAccessTokenGenerator {
+    fn generate_token_id(&self) -> String {
+        return nanoid!(TOKEN_ID_LENGTH);
+    }
+
+    fn generate_token(&self) -> String {
+        return format!("{}{}", TOKEN_PREFIX, nanoid!(TOKEN_LENGTH));
+    }
+
+    fn is_personal_access_token(&self, token: &String) -> bool {
+        return token.starts_with(TOKEN_PREFIX);
+    }
+
+    fn hash_token(&self, token: &String) -> String {
+        return digest(&SHA256, token.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_generate_prefixed_tokens() {
+        let generator = RandomPersonalAccessTokenGenerator::new();
+
+        let first = generator.generate_token();
+        let second = generator.generate_token();
+
+        assert_ne!(first, second);
+        assert_eq!(first.len(), TOKEN_PREFIX.len() + TOKEN_LENGTH);
+        assert!(generator.is_personal_access_token(&first));
+        assert!(!generator.is_personal_access_token(&"eyJhbGciOiJIUzI1NiJ9".to_string()));
+    }
+}
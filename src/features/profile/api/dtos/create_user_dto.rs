@@ -10,12 +10,12 @@ pub struct CreateUserDto {
     password: String,
 }
 
-impl Into<CreateUserModel> for CreateUserDto {
-    fn into(self) -> CreateUserModel {
+impl From<CreateUserDto> for CreateUserModel {
+    fn from(dto: CreateUserDto) -> CreateUserModel {
         return CreateUserModel {
-            username: self.username,
-            email: self.email,
-            password: self.password,
+            username: dto.username,
+            email: dto.email,
+            password: dto.password,
         };
     }
 }
@@ -1,4 +1,9 @@
+pub mod change_password_dto;
+pub mod change_role_dto;
 pub mod create_user_dto;
+pub mod forgot_password_dto;
 pub mod resend_email_dto;
+pub mod reset_password_dto;
+pub mod session_dto;
 pub mod user_dto;
 pub mod verify_email_dto;
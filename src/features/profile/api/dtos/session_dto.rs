@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::features::auth::domain::session::Session;
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDto {
+    id: String,
+    user_agent: Option<String>,
+    ip: Option<String>,
+    created_at: DateTime<Utc>,
+    refreshed_at: Option<DateTime<Utc>>,
+    current: bool,
+}
+
+impl SessionDto {
+    pub fn from_session(session: Session, current_session_id: &Option<String>) -> SessionDto {
+        let current = current_session_id.as_ref() == Some(&session.id);
+        return SessionDto {
+            id: session.id,
+            user_agent: session.user_agent,
+            ip: session.ip,
+            created_at: session.created_at,
+            refreshed_at: session.refreshed_at,
+            current,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_session() -> Session {
+        return Session {
+            id: "session_id".to_string(),
+            user_id: 1,
+            refresh_token_id: "refresh_token_id".to_string(),
+            user_agent: Some("user_agent".to_string()),
+            ip: Some("127.0.0.1".to_string()),
+            created_at: Utc::now(),
+            refreshed_at: None,
+        };
+    }
+
+    #[test]
+    fn should_map_model_to_dto() {
+        let session = get_session();
+        let created_at = session.created_at;
+
+        let dto = SessionDto::from_session(session, &Some("session_id".to_string()));
+
+        assert_eq!(dto.id, "session_id".to_string());
+        assert_eq!(dto.user_agent, Some("user_agent".to_string()));
+        assert_eq!(dto.ip, Some("127.0.0.1".to_string()));
+        assert_eq!(dto.created_at, created_at);
+        assert_eq!(dto.refreshed_at, None);
+        assert!(dto.current);
+    }
+
+    #[test]
+    fn should_not_mark_other_session_as_current() {
+        let dto = SessionDto::from_session(get_session(), &Some("other_id".to_string()));
+
+        assert!(!dto.current);
+    }
+}
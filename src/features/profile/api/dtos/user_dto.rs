@@ -45,8 +45,8 @@ mod tests {
             birthday: None,
             avatar_id: None,
             verified_at: None,
-            created_at: now.clone(),
-            updated_at: now.clone(),
+            created_at: now,
+            updated_at: now,
         };
 
         let dto = UserDto::from(user);
@@ -1,7 +1,7 @@
 use actix_web::{
-    get,
+    delete, get,
     http::StatusCode,
-    post,
+    post, put,
     web::{self, ServiceConfig},
     HttpRequest, HttpResponse, Responder,
 };
@@ -9,28 +9,62 @@ use actix_web_httpauth::middleware::HttpAuthentication;
 
 use crate::{
     common::failure::failure_handler::handle_failure,
+    config::{auth_config::AuthConfig, client_config::ClientConfig},
     features::{
         auth::{
-            api::auth_middleware::verify_current_user, domain::current_user_data::CurrentUserData,
-            infrastructure::password_manager_impl::PasswordManagerImpl,
+            api::{
+                auth_middleware::{require_role, verify_current_user},
+                request_info::get_device_info,
+                scoped_user::{AdminWrite, ProfileRead, ProfileWrite, ScopedUser},
+            },
+            domain::user_role::UserRole,
+            infrastructure::{
+                auth_data_repository_impl::AuthDataRepositoryImpl,
+                jwt_token_provider::JwtTokenProvider, login_throttle_impl::LoginThrottleImpl,
+                password_manager_impl::PasswordManagerImpl,
+                session_storage_impl::SessionStorageImpl,
+            },
+            interactors::auth_interactor::AuthInteractor,
+        },
+        auth_event::infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+        client::{
+            infrastructure::client_repository_impl::ClientRepositoryImpl,
+            interactors::client_interactor::ClientInteractor,
+            utils::client_secret_generator::RandomClientSecretGenerator,
         },
         mailer::mailer::Mailer,
+        mfa::{
+            infrastructure::totp_repository_impl::TotpRepositoryImpl,
+            interactors::mfa_interactor::MfaInteractor,
+            utils::{
+                recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+            },
+        },
+        personal_access_token::{
+            infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+            interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+            utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+        },
         profile::{
             domain::create_user_model::CreateUserModel,
-            errors::profile_errors::get_user_not_found_error,
             infrastructure::{
+                password_reset_keys_storage_impl::PasswordResetKeysStorageImpl,
                 profile_repository_impl::ProfileRepositoryImpl,
                 verification_keys_storage_impl::VerificationKeysStorageImpl,
             },
             interactors::profile_interactor::ProfileInteractor,
-            utils::code_generator::VerificationCodeGenerator,
+            utils::{
+                code_generator::VerificationCodeGenerator, password_policy_impl::PasswordPolicyImpl,
+            },
         },
     },
 };
 
 use super::dtos::{
-    create_user_dto::CreateUserDto, resend_email_dto::ResendEmailDto, user_dto::UserDto,
-    verify_email_dto::VerifyEmailDto,
+    change_password_dto::ChangePasswordDto, change_role_dto::ChangeRoleDto,
+    create_user_dto::CreateUserDto, forgot_password_dto::ForgotPasswordDto,
+    resend_email_dto::ResendEmailDto, reset_password_dto::ResetPasswordDto,
+    session_dto::SessionDto, user_dto::UserDto, verify_email_dto::VerifyEmailDto,
 };
 
 type Interactor = ProfileInteractor<
@@ -39,6 +73,35 @@ type Interactor = ProfileInteractor<
     VerificationKeysStorageImpl,
     Mailer,
     PasswordManagerImpl,
+    PasswordResetKeysStorageImpl,
+    SessionStorageImpl,
+    PasswordPolicyImpl,
+    AuthEventRepositoryImpl,
+    PersonalAccessTokenInteractor<
+        PersonalAccessTokenRepositoryImpl,
+        RandomPersonalAccessTokenGenerator,
+    >,
+>;
+
+type Auth = AuthInteractor<
+    PasswordManagerImpl,
+    JwtTokenProvider,
+    AuthDataRepositoryImpl,
+    ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>,
+    SessionStorageImpl,
+    MfaInteractor<
+        TotpRepositoryImpl,
+        TotpGenerator,
+        RandomRecoveryCodeGenerator,
+        ProfileRepositoryImpl,
+    >,
+    PersonalAccessTokenInteractor<
+        PersonalAccessTokenRepositoryImpl,
+        RandomPersonalAccessTokenGenerator,
+    >,
+    LoginThrottleImpl,
+    AuthConfig,
+    AuthEventRepositoryImpl,
 >;
 
 pub fn configure_profile_controller(config: &mut ServiceConfig) {
@@ -48,12 +111,24 @@ pub fn configure_profile_controller(config: &mut ServiceConfig) {
             .service(
                 web::scope("/me")
                     .wrap(auth_middleware)
-                    .service(get_current_user),
+                    .service(get_current_user)
+                    .service(change_password)
+                    .service(get_sessions)
+                    .service(revoke_session),
             )
             .service(resend_email)
             .service(verify_user)
+            .service(forgot_password)
+            .service(reset_password)
             .service(create_user),
     );
+    // Has to be configured before the impersonation controller, whose `/admin/users` scope
+    // would otherwise swallow this route.
+    config.service(
+        web::scope("/admin/users/{id}/role")
+            .wrap(require_role(UserRole::ADMIN))
+            .service(change_role),
+    );
 }
 
 #[post("/email/resend")]
@@ -72,8 +147,38 @@ async fn resend_email(
 async fn verify_user(
     interactor: web::Data<Interactor>,
     dto: web::Json<VerifyEmailDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let device_info = get_device_info(&request);
+    let result = interactor.verify_email(&dto.code, &device_info).await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/password/forgot")]
+async fn forgot_password(
+    interactor: web::Data<Interactor>,
+    dto: web::Json<ForgotPasswordDto>,
+) -> impl Responder {
+    let result = interactor.forgot_password(&dto.email).await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[post("/password/reset")]
+async fn reset_password(
+    interactor: web::Data<Interactor>,
+    dto: web::Json<ResetPasswordDto>,
+    request: HttpRequest,
 ) -> impl Responder {
-    let result = interactor.verify_email(&dto.code).await;
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .reset_password(&dto.code, &dto.password, &device_info)
+        .await;
     match result {
         Ok(_) => HttpResponse::new(StatusCode::OK),
         Err(err) => handle_failure(err),
@@ -83,19 +188,64 @@ async fn verify_user(
 #[get("")]
 async fn get_current_user(
     interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileRead>,
+) -> impl Responder {
+    let result = interactor.get_user(&current_user.id).await;
+    match result {
+        Ok(user) => HttpResponse::Ok().json(UserDto::from(user)),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[put("/password")]
+async fn change_password(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<ProfileWrite>,
+    dto: web::Json<ChangePasswordDto>,
     request: HttpRequest,
 ) -> impl Responder {
-    let ext = request.extensions();
-    let current_user = ext.get::<CurrentUserData>();
-    match current_user {
-        None => handle_failure(get_user_not_found_error()),
-        Some(current_user) => {
-            let result = interactor.get_user(&current_user.id).await;
-            match result {
-                Ok(user) => HttpResponse::Ok().json(UserDto::from(user)),
-                Err(err) => handle_failure(err),
-            }
-        }
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .change_password(
+            &current_user,
+            &dto.current_password,
+            &dto.new_password,
+            &device_info,
+        )
+        .await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[get("/sessions")]
+async fn get_sessions(
+    auth: web::Data<Auth>,
+    current_user: ScopedUser<ProfileRead>,
+) -> impl Responder {
+    let result = auth.get_sessions(&current_user.id).await;
+    match result {
+        Ok(sessions) => HttpResponse::Ok().json(
+            sessions
+                .into_iter()
+                .map(|session| SessionDto::from_session(session, &current_user.session_id))
+                .collect::<Vec<SessionDto>>(),
+        ),
+        Err(err) => handle_failure(err),
+    }
+}
+
+#[delete("/sessions/{id}")]
+async fn revoke_session(
+    auth: web::Data<Auth>,
+    current_user: ScopedUser<ProfileWrite>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let result = auth.revoke_session(&current_user, &path).await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
     }
 }
 
@@ -111,3 +261,21 @@ async fn create_user(
         Err(err) => handle_failure(err),
     }
 }
+
+#[put("")]
+async fn change_role(
+    interactor: web::Data<Interactor>,
+    current_user: ScopedUser<AdminWrite>,
+    path: web::Path<i64>,
+    dto: web::Json<ChangeRoleDto>,
+    request: HttpRequest,
+) -> impl Responder {
+    let device_info = get_device_info(&request);
+    let result = interactor
+        .change_role(&current_user, &path.into_inner(), &dto.role, &device_info)
+        .await;
+    match result {
+        Ok(_) => HttpResponse::new(StatusCode::OK),
+        Err(err) => handle_failure(err),
+    }
+}
@@ -76,3 +76,53 @@ pub fn get_invalid_verification_code() -> Failure {
         message: "Invalide email verification code".to_string(),
     };
 }
+
+pub fn get_invalid_password_reset_code() -> Failure {
+    return Failure {
+        error_type: FailureType::NotFound,
+        args: None,
+        code: "error.invalid_password_reset_code".to_string(),
+        message: "Invalid password reset code".to_string(),
+    };
+}
+
+pub fn get_invalid_current_password_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.invalid_current_password".to_string(),
+        message: "Current password is invalid".to_string(),
+    };
+}
+
+pub fn get_invalid_role_error(role: String) -> Failure {
+    let mut args = HashMap::new();
+    args.insert("role".to_string(), role.clone());
+
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.invalid_role".to_string(),
+        message: format!("Unknown role '{}'", role),
+        args: Some(args),
+    };
+}
+
+// An admin demoting themselves could leave the service without any admin.
+pub fn get_own_role_change_error() -> Failure {
+    return Failure {
+        error_type: FailureType::Forbidden,
+        args: None,
+        code: "error.own_role_change".to_string(),
+        message: "You can not change your own role".to_string(),
+    };
+}
+
+// Every broken rule is a key in `args`, the value describes what the rule expects.
+pub fn get_weak_password_error(violations: HashMap<String, String>) -> Failure {
+    return Failure {
+        error_type: FailureType::Validation,
+        code: "error.weak_password".to_string(),
+        message: "Password does not meet the password policy".to_string(),
+        args: Some(violations),
+    };
+}
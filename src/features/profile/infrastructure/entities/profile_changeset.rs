@@ -9,6 +9,7 @@ pub struct ProfileChangeset {
     pub avatar_id: Option<String>,
     pub birthday: Option<DateTime<Utc>>,
     pub email_confirmed_at: Option<DateTime<Utc>>,
+    pub password: Option<String>,
 }
 
 impl From<User> for ProfileChangeset {
@@ -19,6 +20,7 @@ impl From<User> for ProfileChangeset {
             email: Some(user.email),
             email_confirmed_at: user.verified_at,
             name: Some(user.username),
+            password: None,
         }
     }
 }
@@ -48,5 +50,6 @@ mod test {
         assert_eq!(result.email, Some("email".to_string()));
         assert_eq!(result.email_confirmed_at, Some(now));
         assert_eq!(result.name, Some("username".to_string()));
+        assert_eq!(result.password, None);
     }
 }
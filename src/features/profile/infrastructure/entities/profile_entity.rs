@@ -19,35 +19,35 @@ pub struct ProfileEntity {
     pub created_at: DateTime<Utc>,
 }
 
-impl Into<User> for ProfileEntity {
-    fn into(self) -> User {
+impl From<ProfileEntity> for User {
+    fn from(entity: ProfileEntity) -> User {
         return User {
-            id: self.id,
-            username: self.name,
-            email: self.email,
-            avatar_id: self.avatar_id,
-            birthday: self.birthday,
-            verified_at: self.email_confirmed_at,
-            updated_at: self.updated_at,
-            created_at: self.created_at,
+            id: entity.id,
+            username: entity.name,
+            email: entity.email,
+            avatar_id: entity.avatar_id,
+            birthday: entity.birthday,
+            verified_at: entity.email_confirmed_at,
+            updated_at: entity.updated_at,
+            created_at: entity.created_at,
         };
     }
 }
 
-impl Into<AuthData> for ProfileEntity {
-    fn into(self) -> AuthData {
-        let role = match self.role.as_str() {
+impl From<ProfileEntity> for AuthData {
+    fn from(entity: ProfileEntity) -> AuthData {
+        let role = match entity.role.as_str() {
             "ADMIN" => UserRole::ADMIN,
             "MODERATOR" => UserRole::MODERATOR,
             _ => UserRole::USER,
         };
         return AuthData {
-            email: self.email,
-            id: self.id,
-            password: self.password,
+            email: entity.email,
+            id: entity.id,
+            password: entity.password,
             user_role: role,
-            username: self.name,
-            verified_at: self.email_confirmed_at,
+            username: entity.name,
+            verified_at: entity.email_confirmed_at,
         };
     }
 }
@@ -67,9 +67,9 @@ mod tests {
             role: "ADMIN".to_string(),
             avatar_id: None,
             birthday: None,
-            email_confirmed_at: Some(now.clone()),
-            created_at: now.clone(),
-            updated_at: now.clone(),
+            email_confirmed_at: Some(now),
+            created_at: now,
+            updated_at: now,
         };
 
         let result: AuthData = profile_entity.into();
@@ -92,9 +92,9 @@ mod tests {
             role: "USER".to_string(),
             avatar_id: None,
             birthday: None,
-            email_confirmed_at: Some(now.clone()),
-            created_at: now.clone(),
-            updated_at: now.clone(),
+            email_confirmed_at: Some(now),
+            created_at: now,
+            updated_at: now,
         };
 
         let user: User = profile_entity.into();
@@ -1,3 +1,4 @@
+pub mod entities;
+pub mod password_reset_keys_storage_impl;
 pub mod profile_repository_impl;
 pub mod verification_keys_storage_impl;
-pub mod entities;
\ No newline at end of file
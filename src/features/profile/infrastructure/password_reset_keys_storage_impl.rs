@@ -0,0 +1,90 @@
+use crate::features::profile::errors::profile_errors::{
+    get_invalid_password_reset_code, get_redis_connection_error,
+};
+use crate::{
+    common::failure::domain::failure::Failure, config::profile_config::ProfileConfig,
+    features::profile::interactors::profile_interactor::PasswordResetKeysStorage,
+};
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, RedisError};
+
+pub struct PasswordResetKeysStorageImpl {
+    redis_connection: MultiplexedConnection,
+    config: ProfileConfig,
+}
+
+impl PasswordResetKeysStorageImpl {
+    pub fn new(
+        redis: MultiplexedConnection,
+        config: ProfileConfig,
+    ) -> PasswordResetKeysStorageImpl {
+        return PasswordResetKeysStorageImpl {
+            redis_connection: redis,
+            config,
+        };
+    }
+
+    fn get_key(reset_code: &String) -> String {
+        return format!("password_reset:{}", reset_code);
+    }
+}
+
+#[async_trait]
+impl PasswordResetKeysStorage for PasswordResetKeysStorageImpl {
+    async fn save_password_reset_code(
+        &self,
+        email: &String,
+        reset_code: &String,
+    ) -> Result<(), Failure> {
+        let exp = self.config.password_reset_key_exp;
+
+        let result: Result<(), RedisError> = self
+            .redis_connection
+            .clone()
+            .set_ex(
+                PasswordResetKeysStorageImpl::get_key(reset_code),
+                email,
+                exp as usize,
+            )
+            .await;
+
+        return match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn get_email_by_password_reset_code(
+        &self,
+        reset_code: &String,
+    ) -> Result<String, Failure> {
+        let result: Result<Option<String>, RedisError> = self
+            .redis_connection
+            .clone()
+            .get(PasswordResetKeysStorageImpl::get_key(reset_code))
+            .await;
+
+        return match result {
+            Ok(Some(email)) => Ok(email),
+            Ok(None) => Err(get_invalid_password_reset_code()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+
+    async fn take_email_by_password_reset_code(
+        &self,
+        reset_code: &String,
+    ) -> Result<String, Failure> {
+        let result: Result<Option<String>, RedisError> = redis::cmd("GETDEL")
+            .arg(PasswordResetKeysStorageImpl::get_key(reset_code))
+            .query_async(&mut self.redis_connection.clone())
+            .await;
+
+        return match result {
+            Ok(Some(email)) => Ok(email),
+            Ok(None) => Err(get_invalid_password_reset_code()),
+            Err(_) => Err(get_redis_connection_error()),
+        };
+    }
+}
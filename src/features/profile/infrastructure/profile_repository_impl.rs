@@ -1,14 +1,20 @@
 use crate::schema::profile::dsl::*;
 use crate::{
     common::failure::domain::failure::Failure,
-    features::profile::{
-        domain::{create_user_model::CreateUserModel, user::User},
-        errors::profile_errors::{
-            get_db_connection_error, get_unique_violation_error, get_unknown_user_creation_error,
-            get_unknown_user_update_error, get_user_not_found_error,
+    features::{
+        auth::domain::{auth_data::AuthData, user_role::UserRole},
+        impersonation::interactors::impersonation_interactor::ImpersonatedUserProvider,
+        mfa::interactors::mfa_interactor::MfaAccountProvider,
+        profile::{
+            domain::{create_user_model::CreateUserModel, user::User},
+            errors::profile_errors::{
+                get_db_connection_error, get_unique_violation_error,
+                get_unknown_user_creation_error, get_unknown_user_update_error,
+                get_user_not_found_error,
+            },
+            infrastructure::entities::profile_entity::ProfileEntity,
+            interactors::profile_interactor::ProfileRepository,
         },
-        infrastructure::entities::profile_entity::ProfileEntity,
-        interactors::profile_interactor::ProfileRepository,
     },
 };
 use async_trait::async_trait;
@@ -53,20 +59,15 @@ impl ProfileRepository for ProfileRepositoryImpl {
 
         return match result {
             Ok(_) => Ok(()),
-            Err(error) => match error {
-                DatabaseError(error, info) => match error {
-                    DatabaseErrorKind::UniqueViolation => {
-                        let column = match info.constraint_name().unwrap_or("") {
-                            UNIQUE_USERNAME_CONSTRAINT => "username",
-                            UNIQUE_EMAIL_CONSTRAINT => "email",
-                            _ => "",
-                        };
-                        Err(get_unique_violation_error(column.to_string()))
-                    }
-                    _ => Err(get_unknown_user_creation_error()),
-                },
-                _ => Err(get_unknown_user_creation_error()),
-            },
+            Err(DatabaseError(DatabaseErrorKind::UniqueViolation, info)) => {
+                let column = match info.constraint_name().unwrap_or("") {
+                    UNIQUE_USERNAME_CONSTRAINT => "username",
+                    UNIQUE_EMAIL_CONSTRAINT => "email",
+                    _ => "",
+                };
+                Err(get_unique_violation_error(column.to_string()))
+            }
+            Err(_) => Err(get_unknown_user_creation_error()),
         };
     }
 
@@ -105,4 +106,82 @@ impl ProfileRepository for ProfileRepositoryImpl {
             Err(_) => Err(get_unknown_user_update_error()),
         };
     }
+
+    async fn get_password_hash(&self, profile_id: &i64) -> Result<String, Failure> {
+        let connection = self.get_connection()?;
+        let result = profile
+            .filter(id.eq(profile_id))
+            .select(password)
+            .get_result::<String>(&connection);
+
+        return match result {
+            Ok(hash) => Ok(hash),
+            Err(_) => Err(get_user_not_found_error()),
+        };
+    }
+
+    async fn update_password(
+        &self,
+        profile_id: &i64,
+        hashed_password: &String,
+    ) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let changeset = ProfileChangeset {
+            name: None,
+            email: None,
+            avatar_id: None,
+            birthday: None,
+            email_confirmed_at: None,
+            password: Some(hashed_password.to_owned()),
+        };
+        let target = profile.filter(id.eq(profile_id));
+        let result = diesel::update(target).set(&changeset).execute(&connection);
+
+        return match result {
+            Ok(1) => Ok(()),
+            Ok(_) => Err(get_user_not_found_error()),
+            Err(_) => Err(get_unknown_user_update_error()),
+        };
+    }
+
+    async fn update_role(&self, profile_id: &i64, user_role: &UserRole) -> Result<(), Failure> {
+        let connection = self.get_connection()?;
+        let target = profile.filter(id.eq(profile_id));
+        let result = diesel::update(target)
+            .set(role.eq(String::from(user_role)))
+            .execute(&connection);
+
+        return match result {
+            Ok(1) => Ok(()),
+            Ok(_) => Err(get_user_not_found_error()),
+            Err(_) => Err(get_unknown_user_update_error()),
+        };
+    }
+}
+
+#[async_trait]
+impl MfaAccountProvider for ProfileRepositoryImpl {
+    async fn get_account_name(&self, profile_id: &i64) -> Result<String, Failure> {
+        let user = self.get_user_by_id(profile_id).await?;
+
+        return Ok(user.email);
+    }
+}
+
+#[async_trait]
+impl ImpersonatedUserProvider for ProfileRepositoryImpl {
+    async fn get_user_role(&self, profile_id: &i64) -> Result<UserRole, Failure> {
+        let connection = self.get_connection()?;
+        let result = profile
+            .filter(id.eq(profile_id))
+            .get_result::<ProfileEntity>(&connection);
+
+        return match result {
+            Ok(entity) => {
+                let auth_data: AuthData = entity.into();
+                Ok(auth_data.user_role)
+            }
+            Err(_) => Err(get_user_not_found_error()),
+        };
+    }
 }
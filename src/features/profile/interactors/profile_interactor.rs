@@ -1,8 +1,28 @@
 use crate::{
-    common::failure::domain::failure::Failure,
-    features::profile::{
-        domain::{create_user_model::CreateUserModel, user::User},
-        errors::profile_errors::get_user_already_verified_error,
+    common::failure::domain::failure::{Failure, FailureType},
+    features::{
+        auth::{
+            domain::{
+                current_user_data::CurrentUserData, device_info::DeviceInfo, user_role::UserRole,
+            },
+            interactors::auth_interactor::PasswordVerifier,
+        },
+        auth_event::{
+            domain::{
+                auth_event_outcome::AuthEventOutcome, auth_event_type::AuthEventType,
+                new_auth_event::NewAuthEvent,
+            },
+            interactors::auth_event_interactor::AuthEventRecorder,
+        },
+        impersonation::errors::impersonation_errors::get_not_allowed_while_impersonating_error,
+        personal_access_token::interactors::personal_access_token_interactor::PersonalAccessTokenRevoker,
+        profile::{
+            domain::{create_user_model::CreateUserModel, user::User},
+            errors::profile_errors::{
+                get_invalid_current_password_error, get_invalid_role_error,
+                get_own_role_change_error, get_user_already_verified_error,
+            },
+        },
     },
 };
 use async_trait::async_trait;
@@ -13,6 +33,15 @@ pub trait PasswordHasher {
     async fn hash_password(&self, password: &String) -> Result<String, Failure>;
 }
 
+pub trait PasswordPolicy {
+    fn validate_password(
+        &self,
+        password: &String,
+        username: &String,
+        email: &String,
+    ) -> Result<(), Failure>;
+}
+
 #[async_trait]
 pub trait ProfileRepository {
     async fn get_user_by_id(&self, uuid: &i64) -> Result<User, Failure>;
@@ -22,11 +51,19 @@ pub trait ProfileRepository {
     async fn save_user(&self, user: &CreateUserModel) -> Result<(), Failure>;
 
     async fn update_user(&self, user: &User) -> Result<(), Failure>;
+
+    async fn get_password_hash(&self, id: &i64) -> Result<String, Failure>;
+
+    async fn update_password(&self, id: &i64, hashed_password: &String) -> Result<(), Failure>;
+
+    async fn update_role(&self, id: &i64, role: &UserRole) -> Result<(), Failure>;
 }
 
 #[async_trait]
 pub trait VerificationMailer {
     async fn send_verification_code(&self, email: &String, code: &String) -> Result<(), Failure>;
+
+    async fn send_password_reset_code(&self, email: &String, code: &String) -> Result<(), Failure>;
 }
 
 #[async_trait]
@@ -40,44 +77,91 @@ pub trait VerificationKeysStorage {
     async fn get_email_by_code(&self, verification_code: &String) -> Result<String, Failure>;
 }
 
+#[async_trait]
+pub trait PasswordResetKeysStorage {
+    async fn save_password_reset_code(
+        &self,
+        email: &String,
+        reset_code: &String,
+    ) -> Result<(), Failure>;
+
+    async fn get_email_by_password_reset_code(
+        &self,
+        reset_code: &String,
+    ) -> Result<String, Failure>;
+
+    async fn take_email_by_password_reset_code(
+        &self,
+        reset_code: &String,
+    ) -> Result<String, Failure>;
+}
+
+#[async_trait]
+pub trait SessionsInvalidator {
+    async fn invalidate_sessions(&self, user_id: &i64) -> Result<(), Failure>;
+}
+
 #[async_trait]
 pub trait CodeGenerator {
     async fn generate(&self) -> String;
 }
 
-pub struct ProfileInteractor<T, Y, U, I, J> {
+pub struct ProfileInteractor<T, Y, U, I, J, K, L, M, N, O> {
     profile_repository: T,
     code_generator: Y,
     verification_keys_storage: U,
     mailer: I,
     password_hasher: J,
+    password_reset_keys_storage: K,
+    sessions_invalidator: L,
+    password_policy: M,
+    auth_event_recorder: N,
+    personal_access_token_revoker: O,
 }
 
-impl<T, Y, U, I, J> ProfileInteractor<T, Y, U, I, J>
+impl<T, Y, U, I, J, K, L, M, N, O> ProfileInteractor<T, Y, U, I, J, K, L, M, N, O>
 where
     T: ProfileRepository,
     Y: CodeGenerator,
     U: VerificationKeysStorage,
     I: VerificationMailer,
-    J: PasswordHasher,
+    J: PasswordHasher + PasswordVerifier,
+    K: PasswordResetKeysStorage,
+    L: SessionsInvalidator,
+    M: PasswordPolicy,
+    N: AuthEventRecorder,
+    O: PersonalAccessTokenRevoker,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         profile_repository: T,
         code_generator: Y,
         verification_keys_storage: U,
         mailer: I,
         password_hasher: J,
-    ) -> ProfileInteractor<T, Y, U, I, J> {
+        password_reset_keys_storage: K,
+        sessions_invalidator: L,
+        password_policy: M,
+        auth_event_recorder: N,
+        personal_access_token_revoker: O,
+    ) -> ProfileInteractor<T, Y, U, I, J, K, L, M, N, O> {
         ProfileInteractor {
             profile_repository,
             code_generator,
             verification_keys_storage,
             mailer,
             password_hasher,
+            password_reset_keys_storage,
+            sessions_invalidator,
+            password_policy,
+            auth_event_recorder,
+            personal_access_token_revoker,
         }
     }
 
     pub async fn create_user(&self, user: &mut CreateUserModel) -> Result<(), Failure> {
+        self.password_policy
+            .validate_password(&user.password, &user.username, &user.email)?;
         user.password = self
             .password_hasher
             .hash_password(&user.password.clone())
@@ -86,14 +170,28 @@ where
         self.send_verification_email(&user.email).await
     }
 
-    pub async fn verify_email(&self, code: &String) -> Result<(), Failure> {
+    pub async fn verify_email(
+        &self,
+        code: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
         let email = self
             .verification_keys_storage
             .get_email_by_code(code)
             .await?;
         let mut user = self.profile_repository.get_user_by_email(&email).await?;
         user.verified_at = Some(Utc::now());
-        self.profile_repository.update_user(&user).await
+        self.profile_repository.update_user(&user).await?;
+        self.record_event(
+            AuthEventType::EmailVerification,
+            AuthEventOutcome::Success,
+            &user.id,
+            None,
+            device_info,
+        )
+        .await;
+
+        Ok(())
     }
 
     pub async fn get_user(&self, id: &i64) -> Result<User, Failure> {
@@ -108,6 +206,143 @@ where
         self.send_verification_email(&user.email).await
     }
 
+    pub async fn forgot_password(&self, email: &String) -> Result<(), Failure> {
+        let user = match self.profile_repository.get_user_by_email(email).await {
+            Ok(user) => user,
+            // Do not reveal whether an account exists for the given email.
+            Err(failure) if failure.error_type == FailureType::NotFound => return Ok(()),
+            Err(failure) => return Err(failure),
+        };
+        let code = self.code_generator.generate().await;
+        self.password_reset_keys_storage
+            .save_password_reset_code(&user.email, &code)
+            .await?;
+        self.mailer
+            .send_password_reset_code(&user.email, &code)
+            .await
+    }
+
+    pub async fn reset_password(
+        &self,
+        code: &String,
+        password: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
+        let email = self
+            .password_reset_keys_storage
+            .get_email_by_password_reset_code(code)
+            .await?;
+        let user = self.profile_repository.get_user_by_email(&email).await?;
+        self.password_policy
+            .validate_password(password, &user.username, &user.email)?;
+        let hashed_password = self.password_hasher.hash_password(password).await?;
+        // The code is only consumed once the password is accepted, so a rejected password can be
+        // retried. Taking it atomically still keeps it single-use under concurrent resets.
+        self.password_reset_keys_storage
+            .take_email_by_password_reset_code(code)
+            .await?;
+        self.profile_repository
+            .update_password(&user.id, &hashed_password)
+            .await?;
+        self.sessions_invalidator
+            .invalidate_sessions(&user.id)
+            .await?;
+        // Whoever knew the old password could have created tokens that would outlive the reset.
+        self.personal_access_token_revoker
+            .revoke_user_tokens(&user.id)
+            .await?;
+        self.record_event(
+            AuthEventType::PasswordReset,
+            AuthEventOutcome::Success,
+            &user.id,
+            None,
+            device_info,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn change_password(
+        &self,
+        current_user: &CurrentUserData,
+        current_password: &String,
+        new_password: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
+        // An admin acting as the user could otherwise take over the account.
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        let id = &current_user.id;
+        let client_id = &current_user.client_id;
+        let hash = self.profile_repository.get_password_hash(id).await?;
+        let is_valid = self
+            .password_hasher
+            .verify_password(current_password, &hash)
+            .await?;
+        if !is_valid {
+            self.record_event(
+                AuthEventType::PasswordChange,
+                AuthEventOutcome::Failure,
+                id,
+                client_id.clone(),
+                device_info,
+            )
+            .await;
+            return Err(get_invalid_current_password_error());
+        }
+        let user = self.profile_repository.get_user_by_id(id).await?;
+        self.password_policy
+            .validate_password(new_password, &user.username, &user.email)?;
+        let hashed_password = self.password_hasher.hash_password(new_password).await?;
+        self.profile_repository
+            .update_password(id, &hashed_password)
+            .await?;
+        self.record_event(
+            AuthEventType::PasswordChange,
+            AuthEventOutcome::Success,
+            id,
+            client_id.clone(),
+            device_info,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    // Tokens carry the role, so the user's sessions are revoked for the new role to apply
+    // right away, which matters most when an account is demoted.
+    pub async fn change_role(
+        &self,
+        current_user: &CurrentUserData,
+        user_id: &i64,
+        role: &String,
+        device_info: &DeviceInfo,
+    ) -> Result<(), Failure> {
+        if current_user.actor_id.is_some() {
+            return Err(get_not_allowed_while_impersonating_error());
+        }
+        if &current_user.id == user_id {
+            return Err(get_own_role_change_error());
+        }
+        let role = UserRole::parse(role).ok_or_else(|| get_invalid_role_error(role.clone()))?;
+        self.profile_repository.update_role(user_id, &role).await?;
+        self.sessions_invalidator
+            .invalidate_sessions(user_id)
+            .await?;
+        self.record_event(
+            AuthEventType::RoleChange,
+            AuthEventOutcome::Success,
+            user_id,
+            current_user.client_id.clone(),
+            device_info,
+        )
+        .await;
+
+        return Ok(());
+    }
+
     async fn send_verification_email(&self, email: &String) -> Result<(), Failure> {
         let code = self.code_generator.generate().await;
         self.verification_keys_storage
@@ -115,6 +350,18 @@ where
             .await?;
         self.mailer.send_verification_code(email, &code).await
     }
+
+    async fn record_event(
+        &self,
+        event_type: AuthEventType,
+        outcome: AuthEventOutcome,
+        user_id: &i64,
+        client_id: Option<String>,
+        device_info: &DeviceInfo,
+    ) {
+        let event = NewAuthEvent::new(event_type, outcome, Some(*user_id), client_id, device_info);
+        let _ = self.auth_event_recorder.record_event(&event).await;
+    }
 }
 
 #[cfg(test)]
@@ -123,7 +370,13 @@ mod test {
     use mockall::predicate::*;
     use mockall::*;
 
-    use crate::common::failure::domain::failure::FailureType;
+    use std::collections::HashMap;
+
+    use crate::features::auth::domain::scope::Scope;
+    use crate::features::profile::errors::profile_errors::{
+        get_invalid_current_password_error, get_invalid_password_reset_code,
+        get_user_not_found_error, get_weak_password_error,
+    };
 
     use super::*;
 
@@ -134,6 +387,13 @@ mod test {
         impl PasswordHasher for PasswordHasher {
             async fn hash_password(&self, password: &String) -> Result<String, Failure>;
         }
+
+        #[async_trait]
+        impl PasswordVerifier for PasswordHasher {
+            async fn verify_password(&self, password: &String, hash: &String) -> Result<bool, Failure>;
+
+            fn needs_rehash(&self, hash: &String) -> bool;
+        }
     }
 
     mock! {
@@ -172,6 +432,12 @@ mod test {
             async fn get_user_by_email(&self, email: &String) -> Result<User, Failure>;
 
             async fn update_user(&self, user: &User) -> Result<(), Failure>;
+
+            async fn get_password_hash(&self, id: &i64) -> Result<String, Failure>;
+
+            async fn update_password(&self, id: &i64, hashed_password: &String) -> Result<(), Failure>;
+
+            async fn update_role(&self, id: &i64, role: &UserRole) -> Result<(), Failure>;
         }
     }
 
@@ -181,6 +447,71 @@ mod test {
         #[async_trait]
         impl VerificationMailer for VerificationMailer {
             async fn send_verification_code(&self, email: &String, code: &String) -> Result<(), Failure>;
+
+            async fn send_password_reset_code(&self, email: &String, code: &String) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        PasswordResetKeysStorage {}
+
+        #[async_trait]
+        impl PasswordResetKeysStorage for PasswordResetKeysStorage {
+            async fn save_password_reset_code(
+                &self,
+                email: &String,
+                reset_code: &String,
+            ) -> Result<(), Failure>;
+
+            async fn get_email_by_password_reset_code(
+                &self,
+                reset_code: &String,
+            ) -> Result<String, Failure>;
+
+            async fn take_email_by_password_reset_code(
+                &self,
+                reset_code: &String,
+            ) -> Result<String, Failure>;
+        }
+    }
+
+    mock! {
+        PasswordPolicy {}
+
+        impl PasswordPolicy for PasswordPolicy {
+            fn validate_password(
+                &self,
+                password: &String,
+                username: &String,
+                email: &String,
+            ) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        SessionsInvalidator {}
+
+        #[async_trait]
+        impl SessionsInvalidator for SessionsInvalidator {
+            async fn invalidate_sessions(&self, user_id: &i64) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        PersonalAccessTokenRevoker {}
+
+        #[async_trait]
+        impl PersonalAccessTokenRevoker for PersonalAccessTokenRevoker {
+            async fn revoke_user_tokens(&self, user_id: &i64) -> Result<(), Failure>;
+        }
+    }
+
+    mock! {
+        AuthEventRecorder {}
+
+        #[async_trait]
+        impl AuthEventRecorder for AuthEventRecorder {
+            async fn record_event(&self, event: &NewAuthEvent) -> Result<(), Failure>;
         }
     }
 
@@ -200,6 +531,50 @@ mod test {
         return (hasher, code_generator, keys_storage, repo, mailer);
     }
 
+    fn get_password_reset_dependencies() -> (MockPasswordResetKeysStorage, MockSessionsInvalidator)
+    {
+        let reset_keys_storage = MockPasswordResetKeysStorage::new();
+        let sessions_invalidator = MockSessionsInvalidator::new();
+
+        return (reset_keys_storage, sessions_invalidator);
+    }
+
+    fn get_password_policy() -> MockPasswordPolicy {
+        let mut password_policy = MockPasswordPolicy::new();
+        password_policy
+            .expect_validate_password()
+            .returning(|_, __, ___| Ok(()));
+
+        password_policy
+    }
+
+    fn get_auth_event_recorder() -> MockAuthEventRecorder {
+        let mut auth_event_recorder = MockAuthEventRecorder::new();
+        auth_event_recorder
+            .expect_record_event()
+            .returning(|_| Ok(()));
+
+        auth_event_recorder
+    }
+
+    fn get_current_user(actor_id: Option<i64>) -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role: UserRole::USER,
+            session_id: Some("session_id".to_string()),
+            actor_id,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![Scope::ProfileWrite],
+        };
+    }
+
+    fn get_device_info() -> DeviceInfo {
+        return DeviceInfo {
+            user_agent: Some("user_agent".to_string()),
+            ip: Some("127.0.0.1".to_string()),
+        };
+    }
+
     #[actix_rt::test]
     async fn should_update_user_verification_date() {
         let test_code = "test_code".to_string();
@@ -214,7 +589,7 @@ mod test {
             updated_at: Utc::now(),
         };
         let user_clone = user.clone();
-        let email = (&user).email.clone();
+        let email = user.email.clone();
 
         let (password_hasher, code_generator, mut storage, mut repo, mailer) = get_dependencies();
 
@@ -231,10 +606,23 @@ mod test {
             }))
             .return_once(|_| Ok(()));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
-        let result = interactor.verify_email(&test_code).await;
+        let result = interactor
+            .verify_email(&test_code, &get_device_info())
+            .await;
 
         assert_eq!(result, Ok(()));
     }
@@ -256,11 +644,22 @@ mod test {
         let (password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
 
         repo.expect_get_user_by_id()
-            .with(predicate::eq((&user).id.clone()))
+            .with(predicate::eq(user.id))
             .return_once(|_| Ok(user_clone));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.get_user(&user.id).await;
 
@@ -295,20 +694,31 @@ mod test {
         storage
             .expect_save_verification_code()
             .with(
-                predicate::eq((&user).email.clone()),
+                predicate::eq(user.email.clone()),
                 predicate::eq(test_code.clone()),
             )
             .return_once(move |_, __| Ok(()));
         mailer
             .expect_send_verification_code()
             .with(
-                predicate::eq((&user).email.clone()),
+                predicate::eq(user.email.clone()),
                 predicate::eq(test_code.clone()),
             )
             .return_once(move |_, __| Ok(()));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.resend_email(&user.email).await;
 
@@ -335,8 +745,19 @@ mod test {
             .with(predicate::eq(user.email.clone()))
             .return_once(move |_| Ok(user_clone));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.resend_email(&user.email).await;
 
@@ -368,14 +789,14 @@ mod test {
         storage
             .expect_save_verification_code()
             .with(
-                predicate::eq((&user).email.clone()),
+                predicate::eq(user.email.clone()),
                 predicate::eq(test_code.clone()),
             )
             .return_once(move |_, __| Ok(()));
         mailer
             .expect_send_verification_code()
             .with(
-                predicate::eq((&user).email.clone()),
+                predicate::eq(user.email.clone()),
                 predicate::eq(test_code.clone()),
             )
             .return_once(move |_, __| Ok(()));
@@ -384,14 +805,69 @@ mod test {
             .with(predicate::eq(user.password.clone()))
             .return_once(|_| Ok("hashed".to_string()));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.create_user(&mut user).await;
 
         assert_eq!(result, Ok(()));
     }
 
+    #[actix_rt::test]
+    async fn should_not_create_user_with_weak_password() {
+        let mut user = CreateUserModel {
+            email: "test@test.com".to_string(),
+            password: "weak".to_string(),
+            username: "testName".to_string(),
+        };
+        let mut violations = HashMap::new();
+        violations.insert("min_length".to_string(), "8".to_string());
+        let failure = get_weak_password_error(violations);
+        let failure_clone = failure.clone();
+
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let mut password_policy = MockPasswordPolicy::new();
+        password_policy
+            .expect_validate_password()
+            .with(
+                predicate::eq("weak".to_string()),
+                predicate::eq("testName".to_string()),
+                predicate::eq("test@test.com".to_string()),
+            )
+            .return_once(move |_, __, ___| Err(failure_clone));
+        password_hasher.expect_hash_password().never();
+        repo.expect_save_user().never();
+
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            password_policy,
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor.create_user(&mut user).await;
+
+        assert_eq!(result, Err(failure));
+    }
+
     #[actix_rt::test]
     async fn should_return_error_if_cannot_save_user() {
         let mut user = CreateUserModel {
@@ -420,8 +896,19 @@ mod test {
             .with(predicate::eq(user.password.clone()))
             .return_once(|_| Ok("hashed".to_string()));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.create_user(&mut user).await;
 
@@ -451,8 +938,19 @@ mod test {
             .with(predicate::eq(email.clone()), predicate::eq(test_code))
             .return_once(move |_, __| Err(copy));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.send_verification_email(&email).await;
 
@@ -493,11 +991,548 @@ mod test {
             )
             .return_once(move |_, __| Err(copy));
 
-        let interactor =
-            ProfileInteractor::new(repo, code_generator, storage, mailer, password_hasher);
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
 
         let result = interactor.send_verification_email(&email).await;
 
         assert_eq!(result, Err(failure.clone()))
     }
+
+    #[actix_rt::test]
+    async fn should_send_password_reset_code() {
+        let test_code = "test_code".to_string();
+        let test_code_clone = test_code.clone();
+        let user = User {
+            verified_at: None,
+            avatar_id: None,
+            birthday: None,
+            id: 1,
+            email: "test_email".to_string(),
+            username: "test_username".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let user_clone = user.clone();
+
+        let (password_hasher, mut code_generator, storage, mut repo, mut mailer) =
+            get_dependencies();
+        let (mut reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        repo.expect_get_user_by_email()
+            .with(predicate::eq(user.email.clone()))
+            .return_once(move |_| Ok(user_clone));
+        code_generator
+            .expect_generate()
+            .return_once(move || test_code_clone);
+        reset_keys_storage
+            .expect_save_password_reset_code()
+            .with(
+                predicate::eq(user.email.clone()),
+                predicate::eq(test_code.clone()),
+            )
+            .return_once(|_, __| Ok(()));
+        mailer
+            .expect_send_password_reset_code()
+            .with(
+                predicate::eq(user.email.clone()),
+                predicate::eq(test_code.clone()),
+            )
+            .return_once(|_, __| Ok(()));
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor.forgot_password(&user.email).await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_reveal_missing_account_on_forgot_password() {
+        let (password_hasher, code_generator, storage, mut repo, mut mailer) = get_dependencies();
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        repo.expect_get_user_by_email()
+            .with(predicate::eq("test_email".to_string()))
+            .return_once(|_| Err(get_user_not_found_error()));
+        mailer.expect_send_password_reset_code().never();
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor.forgot_password(&"test_email".to_string()).await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_reset_password_and_invalidate_sessions() {
+        let user = User {
+            verified_at: None,
+            avatar_id: None,
+            birthday: None,
+            id: 1,
+            email: "test_email".to_string(),
+            username: "test_username".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let user_clone = user.clone();
+
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (mut reset_keys_storage, mut sessions_invalidator) = get_password_reset_dependencies();
+
+        reset_keys_storage
+            .expect_get_email_by_password_reset_code()
+            .with(predicate::eq("test_code".to_string()))
+            .return_once(|_| Ok("test_email".to_string()));
+        reset_keys_storage
+            .expect_take_email_by_password_reset_code()
+            .with(predicate::eq("test_code".to_string()))
+            .return_once(|_| Ok("test_email".to_string()));
+        repo.expect_get_user_by_email()
+            .with(predicate::eq(user.email.clone()))
+            .return_once(move |_| Ok(user_clone));
+        password_hasher
+            .expect_hash_password()
+            .with(predicate::eq("new_password".to_string()))
+            .return_once(|_| Ok("hashed".to_string()));
+        repo.expect_update_password()
+            .with(predicate::eq(1), predicate::eq("hashed".to_string()))
+            .return_once(|_, __| Ok(()));
+        sessions_invalidator
+            .expect_invalidate_sessions()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok(()));
+
+        let mut personal_access_token_revoker = MockPersonalAccessTokenRevoker::new();
+        personal_access_token_revoker
+            .expect_revoke_user_tokens()
+            .with(predicate::eq(1))
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            personal_access_token_revoker,
+        );
+
+        let result = interactor
+            .reset_password(
+                &"test_code".to_string(),
+                &"new_password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_error_if_password_reset_code_is_invalid() {
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (mut reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        reset_keys_storage
+            .expect_get_email_by_password_reset_code()
+            .with(predicate::eq("test_code".to_string()))
+            .return_once(|_| Err(get_invalid_password_reset_code()));
+        password_hasher.expect_hash_password().never();
+        repo.expect_update_password().never();
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor
+            .reset_password(
+                &"test_code".to_string(),
+                &"new_password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_password_reset_code()));
+    }
+
+    #[actix_rt::test]
+    async fn should_keep_password_reset_code_if_password_is_rejected() {
+        let user = User {
+            verified_at: None,
+            avatar_id: None,
+            birthday: None,
+            id: 1,
+            email: "test_email".to_string(),
+            username: "test_username".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let mut violations = HashMap::new();
+        violations.insert("min_length".to_string(), "8".to_string());
+        let failure = get_weak_password_error(violations);
+        let failure_clone = failure.clone();
+
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (mut reset_keys_storage, mut sessions_invalidator) = get_password_reset_dependencies();
+
+        reset_keys_storage
+            .expect_get_email_by_password_reset_code()
+            .with(predicate::eq("test_code".to_string()))
+            .return_once(|_| Ok("test_email".to_string()));
+        reset_keys_storage
+            .expect_take_email_by_password_reset_code()
+            .never();
+        repo.expect_get_user_by_email()
+            .with(predicate::eq(user.email.clone()))
+            .return_once(move |_| Ok(user));
+        let mut password_policy = MockPasswordPolicy::new();
+        password_policy
+            .expect_validate_password()
+            .with(
+                predicate::eq("weak".to_string()),
+                predicate::eq("test_username".to_string()),
+                predicate::eq("test_email".to_string()),
+            )
+            .return_once(move |_, __, ___| Err(failure_clone));
+        password_hasher.expect_hash_password().never();
+        repo.expect_update_password().never();
+        sessions_invalidator.expect_invalidate_sessions().never();
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            password_policy,
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor
+            .reset_password(
+                &"test_code".to_string(),
+                &"weak".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(failure));
+    }
+
+    #[actix_rt::test]
+    async fn should_change_password() {
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        repo.expect_get_password_hash()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok("old_hash".to_string()));
+        password_hasher
+            .expect_verify_password()
+            .with(
+                predicate::eq("old_password".to_string()),
+                predicate::eq("old_hash".to_string()),
+            )
+            .return_once(|_, __| Ok(true));
+        repo.expect_get_user_by_id()
+            .with(predicate::eq(1))
+            .return_once(|_| {
+                Ok(User {
+                    verified_at: None,
+                    avatar_id: None,
+                    birthday: None,
+                    id: 1,
+                    email: "test_email".to_string(),
+                    username: "test_username".to_string(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+            });
+        password_hasher
+            .expect_hash_password()
+            .with(predicate::eq("new_password".to_string()))
+            .return_once(|_| Ok("new_hash".to_string()));
+        repo.expect_update_password()
+            .with(predicate::eq(1), predicate::eq("new_hash".to_string()))
+            .return_once(|_, __| Ok(()));
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor
+            .change_password(
+                &get_current_user(None),
+                &"old_password".to_string(),
+                &"new_password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_change_password_if_current_password_is_invalid() {
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        repo.expect_get_password_hash()
+            .with(predicate::eq(1))
+            .return_once(|_| Ok("old_hash".to_string()));
+        password_hasher
+            .expect_verify_password()
+            .return_once(|_, __| Ok(false));
+        password_hasher.expect_hash_password().never();
+        repo.expect_update_password().never();
+        let mut auth_event_recorder = MockAuthEventRecorder::new();
+        auth_event_recorder
+            .expect_record_event()
+            .with(predicate::eq(NewAuthEvent::new(
+                AuthEventType::PasswordChange,
+                AuthEventOutcome::Failure,
+                Some(1),
+                Some("client_id".to_string()),
+                &get_device_info(),
+            )))
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            auth_event_recorder,
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor
+            .change_password(
+                &get_current_user(None),
+                &"wrong_password".to_string(),
+                &"new_password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_invalid_current_password_error()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_change_password_while_impersonating() {
+        let (mut password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        repo.expect_get_password_hash().never();
+        password_hasher.expect_hash_password().never();
+        repo.expect_update_password().never();
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor
+            .change_password(
+                &get_current_user(Some(2)),
+                &"old_password".to_string(),
+                &"new_password".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Err(get_not_allowed_while_impersonating_error()));
+    }
+
+    fn get_admin(actor_id: Option<i64>) -> CurrentUserData {
+        return CurrentUserData {
+            id: 1,
+            role: UserRole::ADMIN,
+            session_id: None,
+            actor_id,
+            client_id: Some("client_id".to_string()),
+            scopes: vec![Scope::AdminWrite],
+        };
+    }
+
+    #[actix_rt::test]
+    async fn should_change_role_and_record_event() {
+        let (password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (reset_keys_storage, mut sessions_invalidator) = get_password_reset_dependencies();
+        let mut auth_event_recorder = MockAuthEventRecorder::new();
+
+        repo.expect_update_role()
+            .with(predicate::eq(2), predicate::eq(UserRole::MODERATOR))
+            .times(1)
+            .return_once(|_, __| Ok(()));
+        sessions_invalidator
+            .expect_invalidate_sessions()
+            .with(predicate::eq(2))
+            .times(1)
+            .return_once(|_| Ok(()));
+        auth_event_recorder
+            .expect_record_event()
+            .withf(|event| {
+                event.event_type == AuthEventType::RoleChange
+                    && event.outcome == AuthEventOutcome::Success
+                    && event.user_id == Some(2)
+                    && event.client_id == Some("client_id".to_string())
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            auth_event_recorder,
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let result = interactor
+            .change_role(
+                &get_admin(None),
+                &2,
+                &"MODERATOR".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn should_not_change_own_or_unknown_role() {
+        let (password_hasher, code_generator, storage, mut repo, mailer) = get_dependencies();
+        let (reset_keys_storage, sessions_invalidator) = get_password_reset_dependencies();
+
+        repo.expect_update_role().never();
+
+        let interactor = ProfileInteractor::new(
+            repo,
+            code_generator,
+            storage,
+            mailer,
+            password_hasher,
+            reset_keys_storage,
+            sessions_invalidator,
+            get_password_policy(),
+            get_auth_event_recorder(),
+            MockPersonalAccessTokenRevoker::new(),
+        );
+
+        let own_result = interactor
+            .change_role(
+                &get_admin(None),
+                &1,
+                &"USER".to_string(),
+                &get_device_info(),
+            )
+            .await;
+        let unknown_result = interactor
+            .change_role(
+                &get_admin(None),
+                &2,
+                &"OWNER".to_string(),
+                &get_device_info(),
+            )
+            .await;
+        let impersonated_result = interactor
+            .change_role(
+                &get_admin(Some(3)),
+                &2,
+                &"USER".to_string(),
+                &get_device_info(),
+            )
+            .await;
+
+        assert_eq!(own_result, Err(get_own_role_change_error()));
+        assert_eq!(
+            unknown_result,
+            Err(get_invalid_role_error("OWNER".to_string()))
+        );
+        assert_eq!(
+            impersonated_result,
+            Err(get_not_allowed_while_impersonating_error())
+        );
+    }
 }
@@ -13,6 +13,6 @@ impl VerificationCodeGenerator {
 #[async_trait]
 impl CodeGenerator for VerificationCodeGenerator {
     async fn generate(&self) -> String {
-        return nanoid!();
+        nanoid!()
     }
 }
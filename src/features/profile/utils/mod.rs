@@ -1 +1,2 @@
 pub mod code_generator;
+pub mod password_policy_impl;
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::failure::domain::failure::Failure,
+    config::password_policy_config::PasswordPolicyConfig,
+    features::profile::{
+        errors::profile_errors::get_weak_password_error,
+        interactors::profile_interactor::PasswordPolicy,
+    },
+};
+
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+// Shorter usernames and email names are too likely to appear in passwords by accident.
+const MIN_PERSONAL_INFO_LENGTH: usize = 3;
+
+pub struct PasswordPolicyImpl {
+    config: PasswordPolicyConfig,
+    common_passwords: HashSet<String>,
+}
+
+impl PasswordPolicyImpl {
+    pub fn new(config: PasswordPolicyConfig) -> PasswordPolicyImpl {
+        // The bundled list is a small sample, a larger one can be configured on top of it.
+        let configured_passwords = config.common_passwords_list.as_deref().unwrap_or("");
+        let common_passwords = COMMON_PASSWORDS
+            .lines()
+            .chain(configured_passwords.lines())
+            .map(|line| line.trim().to_lowercase())
+            .filter(|line| !line.is_empty())
+            .collect();
+        return PasswordPolicyImpl {
+            config,
+            common_passwords,
+        };
+    }
+}
+
+fn contains_personal_info(password: &String, value: &str) -> bool {
+    let value = value.to_lowercase();
+
+    return value.chars().count() >= MIN_PERSONAL_INFO_LENGTH && password.contains(&value);
+}
+
+impl PasswordPolicy for PasswordPolicyImpl {
+    fn validate_password(
+        &self,
+        password: &String,
+        username: &String,
+        email: &String,
+    ) -> Result<(), Failure> {
+        let mut violations = HashMap::new();
+        if password.chars().count() < self.config.min_length {
+            violations.insert("min_length".to_string(), self.config.min_length.to_string());
+        }
+        if self.config.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.insert("lowercase".to_string(), "required".to_string());
+        }
+        if self.config.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.insert("uppercase".to_string(), "required".to_string());
+        }
+        if self.config.require_digit && !password.chars().any(|c| c.is_numeric()) {
+            violations.insert("digit".to_string(), "required".to_string());
+        }
+        if self.config.require_special_character && !password.chars().any(|c| !c.is_alphanumeric())
+        {
+            violations.insert("special_character".to_string(), "required".to_string());
+        }
+        let lowercase_password = password.to_lowercase();
+        if contains_personal_info(&lowercase_password, username) {
+            violations.insert("contains_username".to_string(), "forbidden".to_string());
+        }
+        let email_name = email.split('@').next().unwrap_or(email);
+        if contains_personal_info(&lowercase_password, email_name) {
+            violations.insert("contains_email".to_string(), "forbidden".to_string());
+        }
+        if self.config.reject_common_passwords
+            && self.common_passwords.contains(&lowercase_password)
+        {
+            violations.insert("common_password".to_string(), "forbidden".to_string());
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+        return Err(get_weak_password_error(violations));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_policy(common_passwords_list: Option<String>) -> PasswordPolicyImpl {
+        PasswordPolicyImpl::new(PasswordPolicyConfig {
+            min_length: 8,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_special_character: true,
+            reject_common_passwords: true,
+            common_passwords_list,
+        })
+    }
+
+    fn validate(password: &str) -> Result<(), Failure> {
+        get_policy(None).validate_password(
+            &password.to_string(),
+            &"johnny".to_string(),
+            &"john.smith@email.com".to_string(),
+        )
+    }
+
+    fn get_violations(result: Result<(), Failure>) -> Vec<String> {
+        let mut violations = result
+            .unwrap_err()
+            .args
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<String>>();
+        violations.sort();
+
+        violations
+    }
+
+    #[test]
+    fn should_accept_strong_password() {
+        let result = validate("Correct-Horse-7");
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn should_list_every_broken_rule() {
+        let result = validate("abc");
+
+        assert_eq!(
+            get_violations(result),
+            vec!["digit", "min_length", "special_character", "uppercase"]
+        );
+    }
+
+    #[test]
+    fn should_reject_password_with_username_or_email() {
+        let result = validate("xJOHNNYx-1");
+        assert_eq!(get_violations(result), vec!["contains_username"]);
+
+        let result = validate("John.Smith-1");
+        assert_eq!(get_violations(result), vec!["contains_email"]);
+    }
+
+    #[test]
+    fn should_reject_common_password() {
+        let result = validate("P@ssw0rd");
+
+        assert_eq!(get_violations(result), vec!["common_password"]);
+    }
+
+    #[test]
+    fn should_reject_password_from_configured_list() {
+        let policy = get_policy(Some("\nSummer-2024x\r\n".to_string()));
+
+        let result = policy.validate_password(
+            &"summer-2024X".to_string(),
+            &"johnny".to_string(),
+            &"john.smith@email.com".to_string(),
+        );
+
+        assert_eq!(get_violations(result), vec!["common_password"]);
+        assert_eq!(validate("Summer-2024x"), Ok(()));
+    }
+}
@@ -1,3 +1,9 @@
+// The codebase returns explicitly and passes `&String` throughout, interactor traits included.
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+// Emitted by the diesel 1.4 derives on newer compilers.
+#![allow(non_local_definitions)]
+
+mod cli;
 mod common;
 mod config;
 mod features;
@@ -12,26 +18,84 @@ use actix_web::{
     web::{self, Data},
     App, HttpServer,
 };
-use config::{auth_config::AuthConfig, common_config::CommonConfig, profile_config::ProfileConfig};
+use config::{
+    auth_config::AuthConfig, client_config::ClientConfig, common_config::CommonConfig,
+    login_throttle_config::LoginThrottleConfig, magic_link_config::MagicLinkConfig,
+    mfa_config::MfaConfig, oidc_config::OidcConfig, password_config::PasswordConfig,
+    password_policy_config::PasswordPolicyConfig, profile_config::ProfileConfig,
+    proxy_config::ProxyConfig,
+};
 use diesel::{r2d2::ConnectionManager, PgConnection};
 use features::{
     auth::{
-        api::auth_controller::configure_auth_controller,
+        api::auth_controller::{configure_auth_controller, configure_well_known_controller},
         infrastructure::{
             auth_data_repository_impl::AuthDataRepositoryImpl,
-            jwt_token_provider::JwtTokenProvider, password_manager_impl::PasswordManagerImpl,
+            jwt_token_provider::JwtTokenProvider, login_throttle_impl::LoginThrottleImpl,
+            password_manager_impl::PasswordManagerImpl, session_storage_impl::SessionStorageImpl,
         },
         interactors::auth_interactor::AuthInteractor,
     },
+    auth_event::{
+        api::auth_event_controller::configure_auth_event_controller,
+        infrastructure::auth_event_repository_impl::AuthEventRepositoryImpl,
+        interactors::auth_event_interactor::AuthEventInteractor,
+    },
+    client::{
+        api::client_controller::configure_client_controller,
+        infrastructure::client_repository_impl::ClientRepositoryImpl,
+        interactors::client_interactor::ClientInteractor,
+        utils::client_secret_generator::RandomClientSecretGenerator,
+    },
+    impersonation::{
+        api::impersonation_controller::configure_impersonation_controller,
+        infrastructure::impersonation_audit_log_impl::ImpersonationAuditLogImpl,
+        interactors::impersonation_interactor::ImpersonationInteractor,
+    },
+    magic_link::{
+        api::magic_link_controller::configure_magic_link_controller,
+        infrastructure::{
+            magic_link_storage_impl::MagicLinkStorageImpl,
+            magic_link_user_repository_impl::MagicLinkUserRepositoryImpl,
+        },
+        interactors::magic_link_interactor::MagicLinkInteractor,
+    },
     mailer::mailer::Mailer,
+    mfa::{
+        api::mfa_controller::configure_mfa_controller,
+        infrastructure::totp_repository_impl::TotpRepositoryImpl,
+        interactors::mfa_interactor::MfaInteractor,
+        utils::{
+            recovery_code_generator::RandomRecoveryCodeGenerator, totp_generator::TotpGenerator,
+        },
+    },
+    oidc::{
+        api::oidc_controller::configure_oidc_controller,
+        infrastructure::{
+            authorization_request_storage_impl::AuthorizationRequestStorageImpl,
+            oidc_provider_client_impl::OidcProviderClientImpl,
+            profile_identity_repository_impl::ProfileIdentityRepositoryImpl,
+        },
+        interactors::oidc_interactor::OidcInteractor,
+        utils::oidc_secret_generator::RandomOidcSecretGenerator,
+    },
+    personal_access_token::{
+        api::personal_access_token_controller::configure_personal_access_token_controller,
+        infrastructure::personal_access_token_repository_impl::PersonalAccessTokenRepositoryImpl,
+        interactors::personal_access_token_interactor::PersonalAccessTokenInteractor,
+        utils::personal_access_token_generator::RandomPersonalAccessTokenGenerator,
+    },
     profile::{
         api::profile_controller::configure_profile_controller,
         infrastructure::{
+            password_reset_keys_storage_impl::PasswordResetKeysStorageImpl,
             profile_repository_impl::ProfileRepositoryImpl,
             verification_keys_storage_impl::VerificationKeysStorageImpl,
         },
         interactors::profile_interactor::ProfileInteractor,
-        utils::code_generator::VerificationCodeGenerator,
+        utils::{
+            code_generator::VerificationCodeGenerator, password_policy_impl::PasswordPolicyImpl,
+        },
     },
 };
 use r2d2::Pool;
@@ -43,13 +107,75 @@ type Profile = ProfileInteractor<
     VerificationKeysStorageImpl,
     Mailer,
     PasswordManagerImpl,
+    PasswordResetKeysStorageImpl,
+    SessionStorageImpl,
+    PasswordPolicyImpl,
+    AuthEventRepositoryImpl,
+    PersonalAccessTokens,
 >;
 
-type Auth =
-    AuthInteractor<PasswordManagerImpl, JwtTokenProvider, AuthDataRepositoryImpl, AuthConfig>;
+type Auth = AuthInteractor<
+    PasswordManagerImpl,
+    JwtTokenProvider,
+    AuthDataRepositoryImpl,
+    Client,
+    SessionStorageImpl,
+    Mfa,
+    PersonalAccessTokens,
+    LoginThrottleImpl,
+    AuthConfig,
+    AuthEventRepositoryImpl,
+>;
+
+type AuthEvents = AuthEventInteractor<AuthEventRepositoryImpl>;
+
+type Client = ClientInteractor<ClientRepositoryImpl, RandomClientSecretGenerator, ClientConfig>;
+
+type PersonalAccessTokens = PersonalAccessTokenInteractor<
+    PersonalAccessTokenRepositoryImpl,
+    RandomPersonalAccessTokenGenerator,
+>;
+
+type Mfa = MfaInteractor<
+    TotpRepositoryImpl,
+    TotpGenerator,
+    RandomRecoveryCodeGenerator,
+    ProfileRepositoryImpl,
+>;
+
+type Oidc = OidcInteractor<
+    OidcProviderClientImpl,
+    AuthorizationRequestStorageImpl,
+    ProfileIdentityRepositoryImpl,
+    RandomOidcSecretGenerator,
+    Client,
+    Auth,
+>;
+
+type Impersonation =
+    ImpersonationInteractor<ProfileRepositoryImpl, JwtTokenProvider, ImpersonationAuditLogImpl>;
+
+type MagicLink = MagicLinkInteractor<
+    MagicLinkUserRepositoryImpl,
+    VerificationCodeGenerator,
+    MagicLinkStorageImpl,
+    Mailer,
+    Client,
+    Auth,
+    MagicLinkConfig,
+>;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if !args.is_empty() {
+        if let Err(err) = cli::run(args).await {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let common_config = CommonConfig::new();
     let manager = ConnectionManager::<PgConnection>::new(common_config.db_url);
     let pool = r2d2::Pool::new(manager).unwrap();
@@ -59,56 +185,192 @@ async fn main() -> std::io::Result<()> {
         .await
         .unwrap();
 
+    // Shared so that logins and password changes are limited by the same hashing pool.
+    let password_manager = PasswordManagerImpl::new(PasswordConfig::new());
+
     let profile_interactor = Data::new(get_profile_interactor(
         pool.clone(),
         redis_connection.clone(),
+        password_manager.clone(),
+    ));
+    let auth_interactor = Data::new(get_auth_interactor(
+        pool.clone(),
+        redis_connection.clone(),
+        password_manager.clone(),
+    ));
+    let oidc_interactor = Data::new(get_oidc_interactor(
+        pool.clone(),
+        redis_connection.clone(),
+        password_manager.clone(),
+    ));
+    let magic_link_interactor = Data::new(get_magic_link_interactor(
+        pool.clone(),
+        redis_connection.clone(),
+        password_manager,
     ));
-    let auth_interactor = Data::new(get_auth_interactor(pool.clone()));
+    let impersonation_interactor = Data::new(get_impersonation_interactor(pool.clone()));
+    let auth_event_interactor = Data::new(get_auth_event_interactor(pool.clone()));
+    let mfa_interactor = Data::new(get_mfa_interactor(pool.clone()));
+    let client_interactor = Data::new(get_client_interactor(pool.clone()));
+    let personal_access_token_interactor =
+        Data::new(get_personal_access_token_interactor(pool.clone()));
+    let proxy_config = Data::new(ProxyConfig::new());
 
     HttpServer::new(move || {
-        App::new().service(
-            web::scope("/api")
-                .app_data(profile_interactor.clone())
-                .app_data(auth_interactor.clone())
-                .configure(|cfg| {
-                    configure_profile_controller(cfg);
-                })
-                .configure(|cfg| configure_auth_controller(cfg)),
-        )
+        App::new()
+            // Read by `get_device_info` to decide whether forwarded headers can be trusted.
+            .app_data(proxy_config.clone())
+            .app_data(auth_interactor.clone())
+            // Used by the auth middleware to audit impersonated requests in every scope.
+            .app_data(impersonation_interactor.clone())
+            .configure(configure_well_known_controller)
+            .service(
+                web::scope("/api")
+                    .app_data(profile_interactor.clone())
+                    .app_data(mfa_interactor.clone())
+                    .app_data(client_interactor.clone())
+                    .app_data(personal_access_token_interactor.clone())
+                    .app_data(oidc_interactor.clone())
+                    .app_data(magic_link_interactor.clone())
+                    .app_data(auth_event_interactor.clone())
+                    .configure(configure_personal_access_token_controller)
+                    .configure(configure_auth_event_controller)
+                    .configure(|cfg| {
+                        configure_profile_controller(cfg);
+                    })
+                    .configure(configure_oidc_controller)
+                    .configure(configure_magic_link_controller)
+                    .configure(configure_auth_controller)
+                    .configure(configure_mfa_controller)
+                    .configure(configure_client_controller)
+                    .configure(configure_impersonation_controller),
+            )
     })
     .bind(format!("127.0.0.1:{}", common_config.port))?
     .run()
     .await
 }
 
-fn get_auth_interactor(pool: Pool<ConnectionManager<PgConnection>>) -> Auth {
-    let interactor = AuthInteractor::new(
-        PasswordManagerImpl::new(),
+fn get_auth_interactor(
+    pool: Pool<ConnectionManager<PgConnection>>,
+    redis_connection: MultiplexedConnection,
+    password_manager: PasswordManagerImpl,
+) -> Auth {
+    return AuthInteractor::new(
+        password_manager,
         JwtTokenProvider::new(AuthConfig::new()),
-        AuthDataRepositoryImpl::new(pool),
+        AuthDataRepositoryImpl::new(pool.clone()),
+        get_client_interactor(pool.clone()),
+        SessionStorageImpl::new(
+            redis_connection.clone(),
+            AuthConfig::new(),
+            ClientRepositoryImpl::new(pool.clone()),
+        ),
+        get_mfa_interactor(pool.clone()),
+        get_personal_access_token_interactor(pool.clone()),
+        LoginThrottleImpl::new(redis_connection, LoginThrottleConfig::new()),
         AuthConfig::new(),
+        AuthEventRepositoryImpl::new(pool),
     );
+}
 
-    interactor
+fn get_oidc_interactor(
+    pool: Pool<ConnectionManager<PgConnection>>,
+    redis_connection: MultiplexedConnection,
+    password_manager: PasswordManagerImpl,
+) -> Oidc {
+    return OidcInteractor::new(
+        OidcProviderClientImpl::new(OidcConfig::new()),
+        AuthorizationRequestStorageImpl::new(redis_connection.clone(), OidcConfig::new()),
+        ProfileIdentityRepositoryImpl::new(pool.clone()),
+        RandomOidcSecretGenerator::new(),
+        get_client_interactor(pool.clone()),
+        get_auth_interactor(pool, redis_connection, password_manager),
+    );
+}
+
+fn get_magic_link_interactor(
+    pool: Pool<ConnectionManager<PgConnection>>,
+    redis_connection: MultiplexedConnection,
+    password_manager: PasswordManagerImpl,
+) -> MagicLink {
+    return MagicLinkInteractor::new(
+        MagicLinkUserRepositoryImpl::new(pool.clone()),
+        VerificationCodeGenerator::new(),
+        MagicLinkStorageImpl::new(redis_connection.clone(), MagicLinkConfig::new()),
+        Mailer::new(),
+        get_client_interactor(pool.clone()),
+        get_auth_interactor(pool, redis_connection, password_manager),
+        MagicLinkConfig::new(),
+    );
+}
+
+fn get_client_interactor(pool: Pool<ConnectionManager<PgConnection>>) -> Client {
+    return ClientInteractor::new(
+        ClientRepositoryImpl::new(pool),
+        RandomClientSecretGenerator::new(),
+        ClientConfig::new(),
+    );
+}
+
+fn get_personal_access_token_interactor(
+    pool: Pool<ConnectionManager<PgConnection>>,
+) -> PersonalAccessTokens {
+    return PersonalAccessTokenInteractor::new(
+        PersonalAccessTokenRepositoryImpl::new(pool),
+        RandomPersonalAccessTokenGenerator::new(),
+    );
+}
+
+fn get_impersonation_interactor(pool: Pool<ConnectionManager<PgConnection>>) -> Impersonation {
+    return ImpersonationInteractor::new(
+        ProfileRepositoryImpl::new(pool.clone()),
+        JwtTokenProvider::new(AuthConfig::new()),
+        ImpersonationAuditLogImpl::new(pool),
+    );
+}
+
+fn get_auth_event_interactor(pool: Pool<ConnectionManager<PgConnection>>) -> AuthEvents {
+    return AuthEventInteractor::new(AuthEventRepositoryImpl::new(pool));
+}
+
+fn get_mfa_interactor(pool: Pool<ConnectionManager<PgConnection>>) -> Mfa {
+    return MfaInteractor::new(
+        TotpRepositoryImpl::new(pool.clone()),
+        TotpGenerator::new(MfaConfig::new()),
+        RandomRecoveryCodeGenerator::new(),
+        ProfileRepositoryImpl::new(pool),
+    );
 }
 
 fn get_profile_interactor(
     pool: Pool<ConnectionManager<PgConnection>>,
     redis_connection: MultiplexedConnection,
+    password_manager: PasswordManagerImpl,
 ) -> Profile {
-    let password_manager = PasswordManagerImpl::new();
     let config = ProfileConfig::new();
     let code_generator = VerificationCodeGenerator::new();
-    let verification_keys_storage = VerificationKeysStorageImpl::new(redis_connection, config);
-    let profile_repository = ProfileRepositoryImpl::new(pool);
+    let verification_keys_storage =
+        VerificationKeysStorageImpl::new(redis_connection.clone(), config);
+    let password_reset_keys_storage =
+        PasswordResetKeysStorageImpl::new(redis_connection.clone(), ProfileConfig::new());
+    let sessions_invalidator = SessionStorageImpl::new(
+        redis_connection,
+        AuthConfig::new(),
+        ClientRepositoryImpl::new(pool.clone()),
+    );
+    let profile_repository = ProfileRepositoryImpl::new(pool.clone());
     let mailer = Mailer::new();
-    let interactor = ProfileInteractor::new(
+    return ProfileInteractor::new(
         profile_repository,
         code_generator,
         verification_keys_storage,
         mailer,
         password_manager,
+        password_reset_keys_storage,
+        sessions_invalidator,
+        PasswordPolicyImpl::new(PasswordPolicyConfig::new()),
+        AuthEventRepositoryImpl::new(pool.clone()),
+        get_personal_access_token_interactor(pool),
     );
-
-    interactor
 }
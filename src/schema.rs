@@ -1,3 +1,16 @@
+table! {
+    auth_event (id) {
+        id -> Int8,
+        user_id -> Nullable<Int8>,
+        event_type -> Varchar,
+        outcome -> Varchar,
+        ip -> Nullable<Varchar>,
+        user_agent -> Nullable<Text>,
+        client_id -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+    }
+}
+
 table! {
     category (id) {
         id -> Int8,
@@ -8,6 +21,45 @@ table! {
     }
 }
 
+table! {
+    client (id) {
+        id -> Varchar,
+        name -> Varchar,
+        secret_hash -> Varchar,
+        grant_types -> Array<Text>,
+        access_token_exp -> Nullable<Int8>,
+        refresh_token_exp -> Nullable<Int8>,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        scopes -> Array<Text>,
+    }
+}
+
+table! {
+    impersonation_audit_log (id) {
+        id -> Int8,
+        actor_id -> Int8,
+        profile_id -> Int8,
+        event -> Varchar,
+        method -> Nullable<Varchar>,
+        path -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    personal_access_token (id) {
+        id -> Varchar,
+        profile_id -> Int8,
+        name -> Varchar,
+        token_hash -> Varchar,
+        scopes -> Array<Text>,
+        expires_at -> Nullable<Timestamptz>,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
 table! {
     post (id) {
         id -> Int8,
@@ -37,11 +89,51 @@ table! {
     }
 }
 
+table! {
+    profile_identity (id) {
+        id -> Int8,
+        profile_id -> Int8,
+        provider -> Varchar,
+        subject -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    recovery_code (id) {
+        id -> Int8,
+        profile_id -> Int8,
+        code_hash -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    totp (profile_id) {
+        profile_id -> Int8,
+        secret -> Varchar,
+        last_used_step -> Nullable<Int8>,
+        confirmed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+joinable!(personal_access_token -> profile (profile_id));
 joinable!(post -> category (category_id));
 joinable!(post -> profile (profile_id));
+joinable!(profile_identity -> profile (profile_id));
+joinable!(recovery_code -> profile (profile_id));
+joinable!(totp -> profile (profile_id));
 
 allow_tables_to_appear_in_same_query!(
+    auth_event,
     category,
+    client,
+    impersonation_audit_log,
+    personal_access_token,
     post,
     profile,
+    profile_identity,
+    recovery_code,
+    totp,
 );